@@ -0,0 +1,132 @@
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::clock_trait::Clock;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::services::backup::{self, BackupInfo};
+
+use super::BackupAction;
+use super::output::{OutputFormat, print_json, print_tsv_row};
+
+pub fn handle_backup(
+    db: &mut Database,
+    clock: &dyn Clock,
+    db_path: &Path,
+    passphrase: &str,
+    action: BackupAction,
+    out: OutputFormat,
+) -> Result<(), AppError> {
+    match action {
+        BackupAction::Create { path } => {
+            let info = backup::create_backup(
+                db,
+                passphrase,
+                db_path,
+                path.as_deref().map(Path::new),
+                clock.now(),
+            )?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "backup": info }));
+            }
+            println!(
+                "Backed up to {} ({}).",
+                info.path.display(),
+                format_size(info.size)
+            );
+        }
+        BackupAction::List => {
+            let backups = backup::list_backups(db_path)?;
+            match out {
+                OutputFormat::Json => return print_json(&json!({ "backups": backups })),
+                OutputFormat::Tsv => {
+                    print_tsv_row(&["path", "automatic", "size", "modified"]);
+                    for b in &backups {
+                        print_tsv_row(&[
+                            b.path.display().to_string(),
+                            b.automatic.to_string(),
+                            b.size.to_string(),
+                            b.modified.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                        ]);
+                    }
+                    return Ok(());
+                }
+                OutputFormat::Text => {}
+            }
+            if backups.is_empty() {
+                println!("No backups in {}.", backup::backup_dir(db_path).display());
+                return Ok(());
+            }
+            print_listing(&backups);
+        }
+        BackupAction::Restore { file, yes } => {
+            if !yes {
+                eprint!("Replace the current database with '{file}'? [y/N] ");
+                io::stderr().flush().ok();
+
+                let stdin = io::stdin();
+                let answer = stdin.lock().lines().next().transpose().ok().flatten();
+                if answer.as_deref() != Some("y") && answer.as_deref() != Some("Y") {
+                    if out == OutputFormat::Json {
+                        return print_json(&json!({ "cancelled": true }));
+                    }
+                    println!("Cancelled.");
+                    return Ok(());
+                }
+            }
+            let result =
+                backup::restore_backup(db, passphrase, db_path, Path::new(&file), clock.now())?;
+            if out == OutputFormat::Json {
+                return print_json(&result);
+            }
+            println!("Restored from {}.", result.restored_from.display());
+            println!(
+                "The previous database was saved to {}.",
+                result.safety_backup.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn print_listing(backups: &[BackupInfo]) {
+    let width = backups
+        .iter()
+        .map(|b| file_name(b).len())
+        .max()
+        .unwrap_or(0)
+        .max("File".len());
+    println!(
+        "{:<width$}  {:<9}  {:>9}  {:<16}",
+        "File", "Kind", "Size", "Modified"
+    );
+    println!("{}", "-".repeat(width + 40));
+    for b in backups {
+        println!(
+            "{:<width$}  {:<9}  {:>9}  {:<16}",
+            file_name(b),
+            if b.automatic { "automatic" } else { "manual" },
+            format_size(b.size),
+            b.modified.format("%Y-%m-%d %H:%M")
+        );
+    }
+}
+
+fn file_name(info: &BackupInfo) -> String {
+    info.path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
@@ -1,43 +1,96 @@
+use serde_json::json;
+
 use crate::clock_trait::Clock;
+use crate::config::Settings;
 use crate::db::Database;
 use crate::error::AppError;
 use crate::formatting::format_duration;
-use crate::services::clock;
+use crate::services::{ProjectDefaults, clock, template};
 
-use super::ClockAction;
+use super::output::{OutputFormat, print_json, print_tsv_row};
+use super::{ClockAction, split_entry_args};
 
-pub fn handle_clock(db: &Database, clk: &dyn Clock, action: ClockAction) -> Result<(), AppError> {
+pub fn handle_clock(
+    db: &Database,
+    clk: &dyn Clock,
+    settings: &Settings,
+    profile: &str,
+    action: ClockAction,
+    out: OutputFormat,
+) -> Result<(), AppError> {
     match action {
         ClockAction::Start {
             project,
             description,
+            template: template_name,
         } => {
-            let (project_name, time) = clock::start_clock(db, &project, &description, clk)?;
-            println!("Clock started for '{description}' on project '{project_name}' at {time}.");
+            let (project, description) = split_entry_args(project, description);
+            let fields = template::resolve_entry_fields(
+                db,
+                template_name.as_deref(),
+                project.as_deref(),
+                description.as_deref(),
+                &ProjectDefaults::new(settings),
+            )?;
+            let info = clock::start_clock(db, &fields.project, &fields.description, clk)?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "clock": info }));
+            }
+            println!(
+                "Clock started for '{}' on project '{}' at {}.",
+                info.description,
+                info.project_name,
+                info.start_time.format("%H:%M")
+            );
         }
         ClockAction::Stop => {
             let result = clock::stop_clock(db, clk)?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "stopped": result }));
+            }
             println!(
                 "Clock stopped. Logged {} for '{}' on project '{}'.",
-                format_duration(result.duration_min),
-                result.description,
+                format_duration(result.task.duration_min),
+                result.task.description,
                 result.project_name
             );
         }
-        ClockAction::Status => match clock::clock_status(db, clk)? {
-            Some(info) => {
-                println!(
-                    "Clock running: '{}' on project '{}' since {} ({} elapsed).",
-                    info.description,
-                    info.project_name,
-                    info.start_time,
-                    format_duration(info.elapsed_min)
-                );
+        ClockAction::Status => {
+            let status = clock::clock_status(db, clk)?;
+            match out {
+                OutputFormat::Json => {
+                    return print_json(&json!({ "clock": status, "profile": profile }));
+                }
+                OutputFormat::Tsv => {
+                    print_tsv_row(&["project", "description", "start_time", "elapsed_min"]);
+                    if let Some(info) = status {
+                        print_tsv_row(&[
+                            info.project_name,
+                            info.description,
+                            info.start_time.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                            info.elapsed_min.to_string(),
+                        ]);
+                    }
+                    return Ok(());
+                }
+                OutputFormat::Text => {}
             }
-            None => {
-                println!("No clock is running.");
+            println!("Profile: {profile}");
+            match status {
+                Some(info) => {
+                    println!(
+                        "Clock running: '{}' on project '{}' since {} ({} elapsed).",
+                        info.description,
+                        info.project_name,
+                        info.start_time.format("%H:%M"),
+                        format_duration(info.elapsed_min)
+                    );
+                }
+                None => {
+                    println!("No clock is running.");
+                }
             }
-        },
+        }
     }
     Ok(())
 }
@@ -0,0 +1,98 @@
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::config;
+use crate::error::AppError;
+
+use super::ConfigAction;
+use super::output::{OutputFormat, print_json, print_tsv_row};
+
+fn user_error(err: anyhow::Error) -> AppError {
+    AppError::UserError(format!("{err:#}"))
+}
+
+/// Config commands work on the file as written, so they run even when it is invalid.
+pub fn handle_config(
+    path: &Path,
+    action: &ConfigAction,
+    out: OutputFormat,
+) -> Result<(), AppError> {
+    let mut doc = config::read_document(path).map_err(user_error)?;
+    match action {
+        ConfigAction::Get { key } => {
+            let Some(value) = config::get_setting(&doc, key).map_err(user_error)? else {
+                return Err(AppError::UserError(format!("'{key}' is not set.")));
+            };
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "key": key, "value": value }));
+            }
+            println!("{value}");
+        }
+        ConfigAction::Set { key, value } => {
+            config::set_setting(&mut doc, key, value).map_err(user_error)?;
+            config::write_document(path, &doc)?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "key": key, "value": value, "path": path }));
+            }
+            println!("Set {key} = {value} in {}", path.display());
+        }
+        ConfigAction::Unset { key } => {
+            let removed = config::unset_setting(&mut doc, key).map_err(user_error)?;
+            if removed {
+                config::write_document(path, &doc)?;
+            }
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "key": key, "removed": removed }));
+            }
+            if removed {
+                println!("Removed {key}.");
+            } else {
+                println!("'{key}' was not set.");
+            }
+        }
+        ConfigAction::List => {
+            let mut settings = Vec::new();
+            for (key, description) in config::SETTINGS {
+                let value = config::get_setting(&doc, key).map_err(user_error)?;
+                settings.push((key, value, description));
+            }
+            match out {
+                OutputFormat::Json => {
+                    let settings: Vec<_> = settings
+                        .iter()
+                        .map(|(key, value, description)| {
+                            json!({ "key": key, "value": value, "description": description })
+                        })
+                        .collect();
+                    return print_json(&json!({ "path": path, "settings": settings }));
+                }
+                OutputFormat::Tsv => {
+                    print_tsv_row(&["key", "value", "description"]);
+                    for (key, value, description) in &settings {
+                        print_tsv_row(&[key, value.as_deref().unwrap_or(""), description]);
+                    }
+                    return Ok(());
+                }
+                OutputFormat::Text => {}
+            }
+            println!("Config file: {}", path.display());
+            println!();
+            let value_width = settings
+                .iter()
+                .map(|(_, value, _)| value.as_deref().map_or(1, str::len))
+                .max()
+                .unwrap_or(0)
+                .max("Value".len());
+            println!("{:<22} {:<value_width$} Description", "Key", "Value");
+            for (key, value, description) in &settings {
+                println!(
+                    "{:<22} {:<value_width$} {description}",
+                    key,
+                    value.as_deref().unwrap_or("-")
+                );
+            }
+        }
+    }
+    Ok(())
+}
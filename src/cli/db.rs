@@ -0,0 +1,178 @@
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::clock_trait::Clock;
+use crate::crypto;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::services::check::{self, Finding, Repair};
+use crate::services::{backup, encryption, rekey};
+
+use super::DbAction;
+use super::output::{OutputFormat, print_json};
+
+pub fn handle_db(
+    db: &Database,
+    clock: &dyn Clock,
+    db_path: &Path,
+    profile: &str,
+    passphrase: &str,
+    action: DbAction,
+    out: OutputFormat,
+) -> Result<(), AppError> {
+    match action {
+        DbAction::Check { fix, yes } => {
+            let findings = check::check_database(db)?;
+            let repairable = findings.iter().filter(|f| f.repair != Repair::None).count();
+            if !fix || repairable == 0 {
+                if out == OutputFormat::Json {
+                    return print_json(&json!({ "check": { "findings": findings } }));
+                }
+                print_findings(&findings);
+                if repairable > 0 {
+                    println!("Run `vibe-clock db check --fix` to repair {repairable} of them.");
+                }
+                return Ok(());
+            }
+
+            if out == OutputFormat::Text {
+                print_findings(&findings);
+            }
+            if !yes {
+                eprint!("Apply {repairable} repairs? [y/N] ");
+                io::stderr().flush().ok();
+
+                let stdin = io::stdin();
+                let answer = stdin.lock().lines().next().transpose().ok().flatten();
+                if answer.as_deref() != Some("y") && answer.as_deref() != Some("Y") {
+                    if out == OutputFormat::Json {
+                        return print_json(&json!({ "cancelled": true }));
+                    }
+                    println!("Cancelled.");
+                    return Ok(());
+                }
+            }
+
+            let repaired = check::repair_database(db, &findings, clock.now())?;
+            let remaining = check::check_database(db)?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({
+                    "check": { "findings": findings, "repaired": repaired, "remaining": remaining }
+                }));
+            }
+            println!("Applied {repaired} repairs.");
+            if !remaining.is_empty() {
+                println!(
+                    "{} problems remain; restore a backup with `vibe-clock backup restore` if the file itself is damaged.",
+                    remaining.len()
+                );
+            }
+        }
+        DbAction::Rekey => {
+            let prompt = |label: &str| {
+                crypto::prompt_passphrase(label).map_err(|e| AppError::UserError(e.to_string()))
+            };
+            let current = prompt("Current passphrase: ")?;
+            let new = prompt("New passphrase: ")?;
+            let confirmation = prompt("Repeat new passphrase: ")?;
+
+            let result = rekey::rekey(
+                db,
+                db_path,
+                profile,
+                passphrase,
+                &current,
+                &new,
+                &confirmation,
+            )?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "rekeyed": result }));
+            }
+            println!("Passphrase changed.");
+            if !result.backups_rekeyed.is_empty() {
+                println!(
+                    "Re-encrypted {} backups with the new passphrase.",
+                    result.backups_rekeyed.len()
+                );
+            }
+            for failed in &result.backups_not_rekeyed {
+                eprintln!(
+                    "Warning: Backup '{}' still uses the old passphrase ({}).",
+                    failed.path.display(),
+                    failed.error
+                );
+            }
+            eprintln!(
+                "Warning: Backups stored outside '{}' still use the old passphrase.",
+                backup::backup_dir(db_path).display()
+            );
+            if let Some(err) = &result.keyring_error {
+                eprintln!(
+                    "Warning: The OS keyring could not be updated ({err}). Set VIBE_CLOCK_KEY to the new passphrase until it is."
+                );
+            } else if let Some(source) = crypto::external_key_source() {
+                println!("Remember to update {source} to supply the new passphrase.");
+            }
+        }
+        DbAction::Encrypt { plain, output } => {
+            handle_encrypt(&plain, output.as_deref(), passphrase, out)?;
+        }
+        DbAction::Decrypt {
+            output,
+            allow_plaintext,
+        } => {
+            if !allow_plaintext {
+                return Err(AppError::UserError(
+                    "This writes all data unencrypted. Pass --allow-plaintext to confirm."
+                        .to_string(),
+                ));
+            }
+            encryption::decrypt(db, Path::new(&output))?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "decrypted": { "path": output } }));
+            }
+            println!("Unencrypted copy written to {output}. Delete it when you are done.");
+        }
+    }
+    Ok(())
+}
+
+/// `db encrypt` does not need the live database, so `run` calls this before opening it.
+pub fn handle_encrypt(
+    plain: &str,
+    output: Option<&str>,
+    passphrase: &str,
+    out: OutputFormat,
+) -> Result<(), AppError> {
+    let path = encryption::encrypt(Path::new(plain), output.map(Path::new), passphrase)?;
+    if out == OutputFormat::Json {
+        return print_json(&json!({ "encrypted": { "path": path } }));
+    }
+    println!("Encrypted database written to {}.", path.display());
+    Ok(())
+}
+
+fn print_findings(findings: &[Finding]) {
+    if findings.is_empty() {
+        println!("No problems found.");
+        return;
+    }
+    println!("Found {} problems:", findings.len());
+    for finding in findings {
+        let location = match (&finding.table, finding.row_id) {
+            (Some(table), Some(id)) => format!("{table} #{id}"),
+            _ => "database".to_string(),
+        };
+        let repair = match &finding.repair {
+            Repair::None => "no automatic repair".to_string(),
+            Repair::RecomputeDuration { duration_min } => {
+                format!("set duration to {duration_min} min")
+            }
+            Repair::ResetTimestamp { column } => format!("reset {column} to now"),
+            Repair::Quarantine => "quarantine the row".to_string(),
+        };
+        println!("  {location}: {} ({repair})", finding.problem);
+    }
+}
@@ -0,0 +1,118 @@
+use std::io;
+use std::path::PathBuf;
+
+use serde_json::json;
+
+use crate::clock_trait::Clock;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::formatting;
+use crate::services::dump;
+use crate::services::export::{self, ExportEntry};
+use crate::services::render;
+use crate::services::{ics, timeclock, timewarrior};
+
+use super::output::{OutputFormat, print_json};
+use super::report::absolute;
+use super::{ExportArgs, ExportFormat, ExportTarget};
+
+pub fn handle_export(
+    db: &Database,
+    clock: &dyn Clock,
+    args: ExportArgs,
+    out: OutputFormat,
+) -> Result<(), AppError> {
+    let (format, range) = match args.target {
+        Some(ExportTarget::Ics(range)) => (ExportFormat::Ics, range),
+        Some(ExportTarget::All { json: _, output }) => {
+            return export_all(db, clock, output, out);
+        }
+        None => {
+            let format = args
+                .format
+                .ok_or_else(|| AppError::UserError("--format is required.".to_string()))?;
+            (format, args.range)
+        }
+    };
+
+    let parse =
+        |d: &str| formatting::parse_date(d, clock).map_err(|e| AppError::UserError(e.to_string()));
+    let from_date = range.from.as_deref().map(parse).transpose()?;
+    let to_date = range.to.as_deref().map(parse).transpose()?;
+    if let (Some(from), Some(to)) = (from_date, to_date) {
+        if from > to {
+            return Err(AppError::UserError(
+                "--from date must be before or equal to --to date.".to_string(),
+            ));
+        }
+    }
+
+    let entries = export::collect_entries(db, from_date, to_date, range.project.as_deref())?;
+    let Some(output) = range.output else {
+        return write_entries(format, &entries, &mut io::stdout().lock());
+    };
+    let path = absolute(PathBuf::from(output));
+    let mut buf = Vec::new();
+    write_entries(format, &entries, &mut buf)?;
+    let name = match format {
+        ExportFormat::Timeclock => "timeclock",
+        ExportFormat::Timewarrior => "Timewarrior",
+        ExportFormat::Ics => "iCalendar",
+    };
+    render::write_atomic(&path, &buf, name)?;
+
+    if out == OutputFormat::Json {
+        return print_json(&json!({ "exported": { "path": path, "entries": entries.len() } }));
+    }
+    println!("Exported {} entries to {}", entries.len(), path.display());
+    Ok(())
+}
+
+fn write_entries(
+    format: ExportFormat,
+    entries: &[ExportEntry],
+    out: &mut dyn io::Write,
+) -> Result<(), AppError> {
+    match format {
+        ExportFormat::Timeclock => timeclock::write_timeclock(entries, out),
+        ExportFormat::Timewarrior => timewarrior::write_timewarrior(entries, out),
+        ExportFormat::Ics => ics::write_ics(entries, out),
+    }
+}
+
+fn export_all(
+    db: &Database,
+    clock: &dyn Clock,
+    output: Option<String>,
+    out: OutputFormat,
+) -> Result<(), AppError> {
+    let dump = dump::export_all(db, clock.now())?;
+    let json = serde_json::to_string_pretty(&dump)
+        .map_err(|e| AppError::SystemError(format!("Failed to serialize dump: {e}")))?;
+
+    let Some(output) = output else {
+        println!("{json}");
+        return Ok(());
+    };
+    let path = absolute(PathBuf::from(output));
+    render::write_atomic(&path, format!("{json}\n").as_bytes(), "dump")?;
+
+    if out == OutputFormat::Json {
+        return print_json(&json!({ "exported": { "path": path, "tables": counts(&dump) } }));
+    }
+    println!(
+        "Exported {} projects and {} task entries to {}",
+        dump.count("project"),
+        dump.count("task_entry"),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Row count per table, for JSON output.
+pub(super) fn counts(dump: &dump::Dump) -> serde_json::Map<String, serde_json::Value> {
+    dump.tables
+        .iter()
+        .map(|(table, rows)| (table.clone(), rows.len().into()))
+        .collect()
+}
@@ -0,0 +1,186 @@
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::clock_trait::Clock;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::formatting::{self, format_duration};
+use crate::services::csv_export;
+use crate::services::csv_import::{self, ColumnMapping};
+use crate::services::dump::{self, Dump};
+use crate::services::ics::{self, IcsImportOptions, ProjectRule};
+use crate::services::import::{self, ImportOptions, ImportSummary};
+use crate::services::{timeclock, timewarrior, watson};
+
+use super::ImportAction;
+use super::output::{OutputFormat, print_json};
+
+pub fn handle_import(
+    db: &Database,
+    clock: &dyn Clock,
+    action: ImportAction,
+    out: OutputFormat,
+) -> Result<(), AppError> {
+    let now = clock.now();
+    let (file, records, flags) = match action {
+        ImportAction::All { file } => return import_all(db, &file, out),
+        ImportAction::Csv {
+            file,
+            map,
+            delimiter,
+            flags,
+        } => {
+            let mapping = ColumnMapping::parse(map.as_deref())?;
+            let delimiter = csv_export::parse_delimiter(&delimiter)?;
+            let records = csv_import::read_csv(Path::new(&file), &mapping, delimiter, clock)?;
+            (file, records, flags)
+        }
+        ImportAction::Timewarrior { file, flags } => {
+            let records = timewarrior::read_timewarrior(Path::new(&file))?;
+            (file, records, flags)
+        }
+        ImportAction::Watson { file, flags } => {
+            let records = watson::read_watson(Path::new(&file))?;
+            (file, records, flags)
+        }
+        ImportAction::Timeclock { file, flags } => {
+            let records = timeclock::read_timeclock(Path::new(&file))?;
+            (file, records, flags)
+        }
+        ImportAction::Ics {
+            file,
+            from,
+            to,
+            rules,
+            project,
+            me,
+            flags,
+        } => {
+            let parse_date = |d: &str| {
+                formatting::parse_date(d, clock).map_err(|e| AppError::UserError(e.to_string()))
+            };
+            let options = IcsImportOptions {
+                rules: rules
+                    .iter()
+                    .map(|r| ProjectRule::parse(r))
+                    .collect::<Result<_, _>>()?,
+                default_project: project,
+                me,
+                from: from.as_deref().map(parse_date).transpose()?,
+                to: to.as_deref().map(parse_date).transpose()?,
+            };
+            let records = ics::read_ics(Path::new(&file), &options, now)?;
+            (file, records, flags)
+        }
+    };
+
+    let options = ImportOptions {
+        create_projects: !flags.no_create,
+        dry_run: flags.dry_run,
+    };
+    let summary = import::import_entries(db, &file, records, &options, now)?;
+    print_summary(&summary, out)
+}
+
+fn print_summary(summary: &ImportSummary, out: OutputFormat) -> Result<(), AppError> {
+    if out == OutputFormat::Json {
+        print_json(&json!({ "import": summary }))?;
+    } else {
+        if summary.dry_run && !summary.imported.is_empty() {
+            let width = summary
+                .imported
+                .iter()
+                .map(|e| e.location.len())
+                .max()
+                .unwrap_or(0)
+                .max(10);
+            println!(
+                "{:<width$} {:<12} {:<15} {:<30} {:<6} {:<6} {:<8}",
+                "Source", "Date", "Project", "Description", "Start", "End", "Duration"
+            );
+            println!("{}", "-".repeat(width + 83));
+            for entry in &summary.imported {
+                let time = |t: Option<chrono::NaiveDateTime>| {
+                    t.map(|t| t.format("%H:%M").to_string())
+                        .unwrap_or_else(|| "-".to_string())
+                };
+                println!(
+                    "{:<width$} {:<12} {:<15} {:<30} {:<6} {:<6} {:<8}",
+                    entry.location,
+                    entry.task.date().format("%Y-%m-%d"),
+                    entry.project_name,
+                    entry.task.description,
+                    time(entry.task.start_time),
+                    time(entry.task.end_time),
+                    format_duration(entry.task.duration_min)
+                );
+            }
+            println!();
+        }
+
+        for error in &summary.errors {
+            eprintln!("{}: {}", error.location, error.message);
+        }
+
+        for skipped in &summary.skipped {
+            println!("Skipped {}: {}", skipped.location, skipped.message);
+        }
+
+        let created = if summary.created_projects.is_empty() {
+            String::new()
+        } else {
+            let names: Vec<&str> = summary
+                .created_projects
+                .iter()
+                .map(|p| p.name.as_str())
+                .collect();
+            format!(", new projects: {}", names.join(", "))
+        };
+        if summary.committed() {
+            println!(
+                "Imported {} entries{created}{}.",
+                summary.imported.len(),
+                skipped_note(summary)
+            );
+        } else if summary.errors.is_empty() {
+            println!(
+                "Dry run: {} entries would be imported{created}{}. Nothing was changed.",
+                summary.imported.len(),
+                skipped_note(summary)
+            );
+        }
+    }
+
+    if !summary.errors.is_empty() {
+        return Err(AppError::UserError(format!(
+            "{} rows could not be imported; nothing was changed.",
+            summary.errors.len()
+        )));
+    }
+    Ok(())
+}
+
+fn skipped_note(summary: &ImportSummary) -> String {
+    match summary.skipped.len() {
+        0 => String::new(),
+        n => format!(" ({n} skipped)"),
+    }
+}
+
+fn import_all(db: &Database, file: &str, out: OutputFormat) -> Result<(), AppError> {
+    let content = import::read_source(Path::new(file))?;
+    let dump: Dump = serde_json::from_str(&content)
+        .map_err(|e| AppError::UserError(format!("Invalid dump file: {e}")))?;
+    dump::import_all(db, &dump)?;
+
+    if out == OutputFormat::Json {
+        return print_json(&json!({ "restored": super::export::counts(&dump) }));
+    }
+    println!(
+        "Restored {} projects and {} task entries from {file}.",
+        dump.count("project"),
+        dump.count("task_entry")
+    );
+    Ok(())
+}
@@ -1,25 +1,49 @@
-use chrono::Local;
+use serde_json::json;
 
+use crate::clock_trait::Clock;
+use crate::config::Settings;
 use crate::db::Database;
 use crate::error::AppError;
 use crate::formatting::{self, format_duration};
 use crate::services::journal;
 
-pub fn handle_journal(db: &Database, date_arg: Option<&str>) -> Result<(), AppError> {
+use super::output::{OutputFormat, print_entries_tsv, print_json};
+
+pub fn handle_journal(
+    db: &Database,
+    clock: &dyn Clock,
+    settings: &Settings,
+    date_arg: Option<&str>,
+    out: OutputFormat,
+) -> Result<(), AppError> {
     let date = match date_arg {
-        Some(d) => formatting::parse_date(d).map_err(|e| AppError::UserError(e.to_string()))?,
-        None => Local::now().date_naive(),
+        Some(d) => {
+            formatting::parse_date(d, clock).map_err(|e| AppError::UserError(e.to_string()))?
+        }
+        None => clock.now().date(),
     };
 
     let daily = journal::get_daily_journal(db, date)?;
 
+    match out {
+        OutputFormat::Json => return print_json(&json!({ "journal": daily })),
+        OutputFormat::Tsv => {
+            print_entries_tsv(&daily.tasks);
+            return Ok(());
+        }
+        OutputFormat::Text => {}
+    }
+
     if daily.tasks.is_empty() {
-        println!("No tasks logged for {}.", date.format("%Y-%m-%d"));
+        println!(
+            "No tasks logged for {}.",
+            date.format(settings.date_format())
+        );
         return Ok(());
     }
 
     // Text-mode output (used for piped output and integration tests)
-    println!("Journal for {}:", date.format("%Y-%m-%d"));
+    println!("Journal for {}:", date.format(settings.date_format()));
     println!();
     println!(
         "{:<6} {:<15} {:<30} {:<6} {:<6} {:<8}",
@@ -27,22 +51,16 @@ pub fn handle_journal(db: &Database, date_arg: Option<&str>) -> Result<(), AppEr
     );
     println!("{}", "-".repeat(75));
 
-    let projects = db.list_projects()?;
-    for task in &daily.tasks {
-        let project_name = projects
-            .iter()
-            .find(|p| p.id == task.project_id)
-            .map(|p| p.name.as_str())
-            .unwrap_or("?");
-
+    for entry in &daily.tasks {
+        let task = &entry.task;
         let start = task
             .start_time
-            .map(|t| t.format("%H:%M").to_string())
+            .map(|t| t.format(settings.time_format()).to_string())
             .unwrap_or_else(|| "-".to_string());
 
         let end = task
             .end_time
-            .map(|t| t.format("%H:%M").to_string())
+            .map(|t| t.format(settings.time_format()).to_string())
             .unwrap_or_else(|| "-".to_string());
 
         let desc = if task.description.len() > 30 {
@@ -54,7 +72,7 @@ pub fn handle_journal(db: &Database, date_arg: Option<&str>) -> Result<(), AppEr
         println!(
             "{:<6} {:<15} {:<30} {:<6} {:<6} {:<8}",
             task.id,
-            project_name,
+            entry.project_name,
             desc,
             start,
             end,
@@ -64,8 +82,8 @@ pub fn handle_journal(db: &Database, date_arg: Option<&str>) -> Result<(), AppEr
 
     println!();
     println!("Totals:");
-    for (name, minutes) in &daily.project_totals {
-        println!("  {:<20} {}", name, format_duration(*minutes));
+    for summary in &daily.project_totals {
+        println!("  {:<20} {}", summary.name, format_duration(summary.total));
     }
     println!("  {:<20} {}", "TOTAL", format_duration(daily.grand_total));
 
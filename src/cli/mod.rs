@@ -1,10 +1,25 @@
+pub mod backup;
 pub mod clock;
+pub mod config;
+pub mod db;
+pub mod export;
+pub mod import;
 pub mod journal;
+pub mod output;
+pub mod period;
+pub mod profile;
 pub mod project;
 pub mod report;
 pub mod task;
+pub mod template;
+pub mod trash;
+pub mod undo;
 
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+pub use output::OutputFormat;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -13,12 +28,34 @@ use clap::{Parser, Subcommand};
     about = "A daily task journal with time tracking"
 )]
 pub struct Cli {
+    /// Output format: human-readable text, a JSON document, or tab-separated rows
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub output_format: OutputFormat,
+
+    /// Profile to work in, each with its own database (defaults to the one chosen with `profile use`)
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+
+    /// Config file to use instead of <config dir>/vibe-clock/config.toml
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+
     #[command(subcommand)]
     pub command: Command,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Command {
+    /// Show and change settings in the config file
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Keep separate databases, e.g. for personal and employer time
+    Profile {
+        #[command(subcommand)]
+        action: ProfileAction,
+    },
     /// Manage projects
     Project {
         #[command(subcommand)]
@@ -34,26 +71,242 @@ pub enum Command {
         #[command(subcommand)]
         action: TaskAction,
     },
+    /// Manage task templates for frequently logged activities
+    Template {
+        #[command(subcommand)]
+        action: TemplateAction,
+    },
+    /// Lock closed periods against modification
+    Period {
+        #[command(subcommand)]
+        action: PeriodAction,
+    },
+    /// List, restore or permanently remove deleted tasks and projects
+    Trash {
+        #[command(subcommand)]
+        action: TrashAction,
+    },
+    /// Import task entries from other tools and files
+    Import {
+        #[command(subcommand)]
+        action: ImportAction,
+    },
+    /// Revert the most recent project, task or clock change
+    Undo,
+    /// Re-apply the most recently undone change
+    Redo,
     /// View the daily task journal
     Journal {
-        /// Date to view (YYYY-MM-DD, "today", or "yesterday"; defaults to today)
+        /// Date to view (YYYY-MM-DD, "yesterday", "monday", "-3d", "15", ...; defaults to today)
+        #[arg(allow_hyphen_values = true)]
         date: Option<String>,
     },
     /// Generate time reports
-    Report {
-        /// Start date (inclusive, YYYY-MM-DD or "today"/"yesterday")
-        #[arg(long)]
-        from: String,
-        /// End date (inclusive, YYYY-MM-DD or "today"/"yesterday"; defaults to same as --from)
+    Report(ReportArgs),
+    /// Export task entries for other time-tracking tools
+    Export(ExportArgs),
+    /// Create, list and restore encrypted database backups
+    Backup {
+        #[command(subcommand)]
+        action: BackupAction,
+    },
+    /// Database maintenance
+    Db {
+        #[command(subcommand)]
+        action: DbAction,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct ReportArgs {
+    /// Start date (inclusive, YYYY-MM-DD, "monday", "-7d", "2026-W12", ...; defaults to the current report.range period)
+    #[arg(long, allow_hyphen_values = true)]
+    pub from: Option<String>,
+    /// End date (inclusive, same formats as --from; defaults to same as --from)
+    #[arg(long, allow_hyphen_values = true)]
+    pub to: Option<String>,
+    /// Export report as PDF to the current directory
+    #[arg(long)]
+    pub pdf: bool,
+    /// Output file or directory for the PDF, or for the --format output (e.g., report.pdf or /path/to/dir/)
+    #[arg(long)]
+    pub output: Option<String>,
+    /// Also write the report as CSV to this file
+    #[arg(long)]
+    pub csv: Option<String>,
+    /// Report format; anything but text goes to stdout, or to --output if given (defaults to report.format, else text)
+    #[arg(long, value_enum)]
+    pub format: Option<ReportFormat>,
+    /// CSV field delimiter (a single character, or "tab"; defaults to report.csv_delimiter, else ",")
+    #[arg(long)]
+    pub delimiter: Option<String>,
+    /// Omit the CSV header row
+    #[arg(long)]
+    pub no_header: bool,
+    /// CSV with one row per project instead of one row per entry
+    #[arg(long)]
+    pub summary: bool,
+    /// Flag entries added or modified after this date (YYYY-MM-DD, "last friday", "3 days ago", ...)
+    #[arg(long, allow_hyphen_values = true)]
+    pub edited_after: Option<String>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// Aligned terminal tables
+    #[default]
+    Text,
+    /// Comma-separated values (see --delimiter, --no-header, --summary)
+    Csv,
+    /// Markdown tables
+    Md,
+    /// Self-contained HTML page
+    Html,
+    /// PDF document (always written to a file)
+    Pdf,
+}
+
+#[derive(Args, Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct ExportArgs {
+    #[command(subcommand)]
+    pub target: Option<ExportTarget>,
+    /// Export format
+    #[arg(long, value_enum, required = true)]
+    pub format: Option<ExportFormat>,
+    #[command(flatten)]
+    pub range: ExportRange,
+}
+
+#[derive(Args, Debug)]
+pub struct ExportRange {
+    /// First day to export (inclusive, YYYY-MM-DD, "monday", "-7d", ...; defaults to the earliest entry)
+    #[arg(long, allow_hyphen_values = true)]
+    pub from: Option<String>,
+    /// Last day to export (inclusive; defaults to the latest entry)
+    #[arg(long, allow_hyphen_values = true)]
+    pub to: Option<String>,
+    /// Only export entries of this project (name or ID)
+    #[arg(long)]
+    pub project: Option<String>,
+    /// Write to this file instead of stdout
+    #[arg(long)]
+    pub output: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ExportTarget {
+    /// Export entries as calendar events (same as --format ics)
+    Ics(ExportRange),
+    /// Dump the whole database, including trash and history, for `import all`
+    All {
+        /// Write the dump as JSON (the only dump format)
+        #[arg(long, required = true)]
+        json: bool,
+        /// Write to this file instead of stdout
         #[arg(long)]
-        to: Option<String>,
-        /// Export report as PDF to the current directory
+        output: Option<String>,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// ledger/hledger timeclock file
+    Timeclock,
+    /// Timewarrior JSON, as read by `timew import`
+    Timewarrior,
+    /// iCalendar events with stable UIDs, for calendar apps
+    Ics,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BackupAction {
+    /// Back up the database, encrypted with the current passphrase
+    Create {
+        /// Backup file or directory (defaults to a timestamped file in the backup directory)
+        path: Option<String>,
+    },
+    /// List backups in the backup directory, newest first
+    List,
+    /// Replace the database with a backup
+    Restore {
+        /// Backup file, or the name of a file in the backup directory
+        file: String,
+        /// Skip confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DbAction {
+    /// Check the database for corruption and unreadable or inconsistent rows
+    Check {
+        /// Repair what can be repaired (recompute durations, quarantine bad rows)
         #[arg(long)]
-        pdf: bool,
-        /// Output path for PDF file (e.g., report.pdf or /path/to/dir/)
+        fix: bool,
+        /// Skip confirmation of the repairs
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Change the database passphrase
+    Rekey,
+    /// Encrypt a plaintext vibe-clock database with the current passphrase
+    Encrypt {
+        /// Plaintext database file
+        plain: String,
+        /// Write the encrypted database here instead of replacing the plaintext file
         #[arg(long)]
         output: Option<String>,
     },
+    /// Write an unencrypted copy of the database, for debugging
+    Decrypt {
+        /// Where to write the plaintext copy
+        #[arg(long)]
+        output: String,
+        /// Confirm that an unencrypted copy of all data may be written
+        #[arg(long)]
+        allow_plaintext: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Print the value of a setting
+    Get {
+        /// Setting key, e.g. week.start
+        key: String,
+    },
+    /// Change a setting
+    Set {
+        /// Setting key, e.g. week.start
+        key: String,
+        /// New value
+        value: String,
+    },
+    /// Remove a setting, restoring its default
+    Unset {
+        /// Setting key, e.g. week.start
+        key: String,
+    },
+    /// List all settings with their values
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ProfileAction {
+    /// List profiles and their databases
+    List,
+    /// Create a profile with a new database and passphrase
+    Create {
+        /// Profile name (letters, digits, '-' and '_')
+        name: String,
+    },
+    /// Make a profile the one used when --profile is not given
+    Use {
+        /// Profile name
+        name: String,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -87,10 +340,13 @@ pub enum ProjectAction {
 pub enum ClockAction {
     /// Start a time clock
     Start {
-        /// Project name or ID
-        project: String,
+        /// Project name or ID (a single argument is taken as the description; defaults to the .vibe-clock file, then defaults.project)
+        project: Option<String>,
         /// Task description
-        description: String,
+        description: Option<String>,
+        /// Prefill project and description from a template
+        #[arg(long)]
+        template: Option<String>,
     },
     /// Stop the running clock
     Stop,
@@ -102,10 +358,13 @@ pub enum ClockAction {
 pub enum TaskAction {
     /// Manually add a task entry
     Add {
-        /// Project name or ID
-        project: String,
+        /// Project name or ID (a single argument is taken as the description; defaults to the .vibe-clock file, then defaults.project)
+        project: Option<String>,
         /// Task description
-        description: String,
+        description: Option<String>,
+        /// Prefill project, description and duration from a template
+        #[arg(long)]
+        template: Option<String>,
         /// Start time (HH:MM, 24-hour clock, e.g., 9:00 or 14:30)
         #[arg(long)]
         start: Option<String>,
@@ -115,10 +374,15 @@ pub enum TaskAction {
         /// Duration (e.g., 1h30m, 45m, 2h, or 90 for minutes)
         #[arg(long)]
         duration: Option<String>,
-        /// Date for the entry (YYYY-MM-DD, 'today', or 'yesterday'; defaults to today)
-        #[arg(long)]
+        /// Date for the entry (YYYY-MM-DD, 'yesterday', 'friday', '-2d', '15', ...; defaults to today)
+        #[arg(long, allow_hyphen_values = true)]
         date: Option<String>,
     },
+    /// Show the change history of a task entry
+    History {
+        /// Task entry ID
+        id: i64,
+    },
     /// Edit an existing task entry
     Edit {
         /// Task entry ID
@@ -135,11 +399,11 @@ pub enum TaskAction {
         /// New end time (HH:MM, 24-hour clock)
         #[arg(long)]
         end: Option<String>,
-        /// New duration (e.g., 1h30m, 45m, 2h, or 90 for minutes)
+        /// New duration (e.g., 1h30m, 45m, 2h, or 90 for minutes); a timed entry's end moves to match
         #[arg(long)]
         duration: Option<String>,
-        /// Move task to a different date (YYYY-MM-DD, 'today', or 'yesterday')
-        #[arg(long)]
+        /// Move task to a different date (YYYY-MM-DD, 'yesterday', 'friday', '-2d', '15', ...)
+        #[arg(long, allow_hyphen_values = true)]
         date: Option<String>,
     },
     /// Delete a task entry
@@ -151,3 +415,152 @@ pub enum TaskAction {
         yes: bool,
     },
 }
+
+#[derive(Subcommand, Debug)]
+pub enum TemplateAction {
+    /// Create a new template
+    Add {
+        /// Template name
+        name: String,
+        /// Project name or ID
+        project: String,
+        /// Task description
+        description: String,
+        /// Default duration (e.g., 1h30m, 45m, 2h, or 90 for minutes)
+        #[arg(long)]
+        duration: Option<String>,
+    },
+    /// List all templates
+    List,
+    /// Delete a template
+    Delete {
+        /// Template name
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PeriodAction {
+    /// Lock all entries up to and including a date
+    Lock {
+        /// Last locked day (YYYY-MM-DD, "last friday", "-1w", ...)
+        #[arg(long, allow_hyphen_values = true)]
+        to: String,
+    },
+    /// Remove the period lock
+    Unlock,
+    /// Show the locked period and its change history
+    Status,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TrashAction {
+    /// List trashed projects and tasks
+    List,
+    /// Restore a trashed task (or project with --project)
+    Restore {
+        /// Task ID (or project ID with --project)
+        id: i64,
+        /// Restore a project instead of a task
+        #[arg(long)]
+        project: bool,
+    },
+    /// Permanently delete trashed items
+    Empty {
+        /// Only remove items trashed longer ago than this (e.g., 30d, 4w)
+        #[arg(long)]
+        older_than: Option<String>,
+        /// Skip confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ImportAction {
+    /// Import entries from a CSV file with a header row
+    Csv {
+        /// Path to the CSV file
+        file: String,
+        /// Column mapping, e.g. "project=Client,description=Notes,duration=Hours"
+        #[arg(long)]
+        map: Option<String>,
+        /// Field delimiter (a single character, or "tab")
+        #[arg(long, default_value = ",")]
+        delimiter: String,
+        #[command(flatten)]
+        flags: ImportFlags,
+    },
+    /// Import intervals from a Timewarrior JSON export (`timew export`)
+    Timewarrior {
+        /// Path to the export file
+        file: String,
+        #[command(flatten)]
+        flags: ImportFlags,
+    },
+    /// Import frames from Watson's `frames` file
+    Watson {
+        /// Path to the frames file
+        file: String,
+        #[command(flatten)]
+        flags: ImportFlags,
+    },
+    /// Import clock-in/clock-out pairs from a ledger timeclock file
+    Timeclock {
+        /// Path to the timeclock file
+        file: String,
+        #[command(flatten)]
+        flags: ImportFlags,
+    },
+    /// Restore a dump written by `export all --json` into an empty database
+    All {
+        /// Path to the dump file
+        file: String,
+    },
+    /// Import timed calendar events from an iCalendar (.ics) file
+    Ics {
+        /// Path to the .ics file
+        file: String,
+        /// Only import events on or after this day (YYYY-MM-DD, "monday", "-7d", ...)
+        #[arg(long, allow_hyphen_values = true)]
+        from: Option<String>,
+        /// Only import events on or before this day
+        #[arg(long, allow_hyphen_values = true)]
+        to: Option<String>,
+        /// Assign matching events to a project: [calendar:|category:|summary:]PATTERN=PROJECT (repeatable)
+        #[arg(long = "rule")]
+        rules: Vec<String>,
+        /// Project for events no rule matches (unmatched events are skipped otherwise)
+        #[arg(long)]
+        project: Option<String>,
+        /// Your calendar address, to skip events you declined
+        #[arg(long)]
+        me: Option<String>,
+        #[command(flatten)]
+        flags: ImportFlags,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct ImportFlags {
+    /// Fail entries whose project does not exist instead of creating it
+    #[arg(long)]
+    pub no_create: bool,
+    /// Validate and preview the import without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Interpret the optional `[PROJECT] [DESCRIPTION]` positionals.
+///
+/// When only one is given it is the description, so that the project can come
+/// from elsewhere (e.g., a template).
+pub fn split_entry_args(
+    first: Option<String>,
+    second: Option<String>,
+) -> (Option<String>, Option<String>) {
+    match (first, second) {
+        (Some(only), None) => (None, Some(only)),
+        other => other,
+    }
+}
@@ -0,0 +1,81 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::error::AppError;
+use crate::services::report::DailyEntry;
+
+/// How command results are written to stdout (and errors to stderr).
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable aligned tables and messages
+    #[default]
+    Text,
+    /// A single JSON document per command
+    Json,
+    /// Tab-separated rows with a header line (list-style commands only)
+    Tsv,
+}
+
+/// Print `value` as one pretty-printed JSON document on stdout.
+pub fn print_json<T: Serialize>(value: &T) -> Result<(), AppError> {
+    let doc = serde_json::to_string_pretty(value)
+        .map_err(|e| AppError::SystemError(format!("Failed to serialize output: {e}")))?;
+    println!("{doc}");
+    Ok(())
+}
+
+/// Print one tab-separated row. Tabs and line breaks inside fields become spaces.
+pub fn print_tsv_row<S: AsRef<str>>(fields: &[S]) {
+    let row: Vec<String> = fields
+        .iter()
+        .map(|f| f.as_ref().replace(['\t', '\n', '\r'], " "))
+        .collect();
+    println!("{}", row.join("\t"));
+}
+
+/// Print task entries as TSV, one row per entry, with a header line.
+pub fn print_entries_tsv<'a>(entries: impl IntoIterator<Item = &'a DailyEntry>) {
+    print_tsv_row(&[
+        "date",
+        "id",
+        "project",
+        "description",
+        "start_time",
+        "end_time",
+        "duration_min",
+        "edited",
+    ]);
+    for entry in entries {
+        let fmt = |t: Option<chrono::NaiveDateTime>| {
+            t.map(|t| t.format("%Y-%m-%dT%H:%M:%S").to_string())
+                .unwrap_or_default()
+        };
+        print_tsv_row(&[
+            entry.task.date().format("%Y-%m-%d").to_string(),
+            entry.task.id.to_string(),
+            entry.project_name.clone(),
+            entry.task.description.clone(),
+            fmt(entry.task.start_time),
+            fmt(entry.task.end_time),
+            entry.task.duration_min.to_string(),
+            entry.edited.to_string(),
+        ]);
+    }
+}
+
+/// Render an error as the JSON document written to stderr in `--output-format json` mode.
+pub fn error_json(err: &AppError) -> String {
+    let kind = match err {
+        AppError::UserError(_) => "user",
+        AppError::SystemError(_) => "system",
+    };
+    json!({
+        "error": {
+            "kind": kind,
+            "message": err.to_string(),
+            "exit_code": err.exit_code(),
+        }
+    })
+    .to_string()
+}
@@ -0,0 +1,90 @@
+use serde_json::json;
+
+use crate::clock_trait::Clock;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::formatting;
+use crate::services::period;
+
+use super::PeriodAction;
+use super::output::{OutputFormat, print_json, print_tsv_row};
+
+pub fn handle_period(
+    db: &Database,
+    clock: &dyn Clock,
+    action: PeriodAction,
+    out: OutputFormat,
+) -> Result<(), AppError> {
+    match action {
+        PeriodAction::Lock { to } => {
+            let date = formatting::parse_date(&to, clock)
+                .map_err(|e| AppError::UserError(e.to_string()))?;
+            period::lock_period(db, date, clock.now())?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "period": { "locked_to": date } }));
+            }
+            println!("Entries up to {} are now locked.", date.format("%Y-%m-%d"));
+        }
+        PeriodAction::Unlock => {
+            let previous = period::unlock_period(db, clock.now())?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({
+                    "period": { "locked_to": null, "previous_locked_to": previous }
+                }));
+            }
+            println!(
+                "Period unlocked (was locked up to {}).",
+                previous.format("%Y-%m-%d")
+            );
+        }
+        PeriodAction::Status => {
+            let status = period::period_status(db)?;
+            let fmt = |d: Option<chrono::NaiveDate>| {
+                d.map(|d| d.format("%Y-%m-%d").to_string())
+                    .unwrap_or_else(|| "-".to_string())
+            };
+            match out {
+                OutputFormat::Json => return print_json(&json!({ "period": status })),
+                OutputFormat::Tsv => {
+                    print_tsv_row(&["changed_at", "action", "locked_to", "previous_locked_to"]);
+                    for event in &status.history {
+                        print_tsv_row(&[
+                            event.changed_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                            event.action.clone(),
+                            event.locked_to.map(|d| d.to_string()).unwrap_or_default(),
+                            event
+                                .previous_locked_to
+                                .map(|d| d.to_string())
+                                .unwrap_or_default(),
+                        ]);
+                    }
+                    return Ok(());
+                }
+                OutputFormat::Text => {}
+            }
+            match status.locked_to {
+                Some(date) => println!("Entries up to {} are locked.", date.format("%Y-%m-%d")),
+                None => println!("No period is locked."),
+            }
+
+            if !status.history.is_empty() {
+                println!();
+                println!(
+                    "{:<17} {:<8} {:<12} {:<12}",
+                    "Changed", "Action", "Locked to", "Previous"
+                );
+                println!("{}", "-".repeat(52));
+                for event in &status.history {
+                    println!(
+                        "{:<17} {:<8} {:<12} {:<12}",
+                        event.changed_at.format("%Y-%m-%d %H:%M"),
+                        event.action,
+                        fmt(event.locked_to),
+                        fmt(event.previous_locked_to)
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use serde_json::json;
+
+use crate::crypto;
+use crate::error::AppError;
+use crate::services::profile;
+
+use super::ProfileAction;
+use super::output::{OutputFormat, print_json, print_tsv_row};
+
+/// Profile commands run before any database is opened.
+pub fn handle_profile(
+    active: &str,
+    action: &ProfileAction,
+    out: OutputFormat,
+) -> Result<(), AppError> {
+    match action {
+        ProfileAction::List => {
+            let profiles = profile::list_profiles(active)?;
+            match out {
+                OutputFormat::Json => return print_json(&json!({ "profiles": profiles })),
+                OutputFormat::Tsv => {
+                    print_tsv_row(&["name", "active", "path"]);
+                    for p in &profiles {
+                        print_tsv_row(&[
+                            p.name.clone(),
+                            p.active.to_string(),
+                            p.path.display().to_string(),
+                        ]);
+                    }
+                    return Ok(());
+                }
+                OutputFormat::Text => {}
+            }
+            let width = profiles
+                .iter()
+                .map(|p| p.name.len())
+                .max()
+                .unwrap_or(0)
+                .max("Profile".len());
+            println!("  {:<width$}  Database", "Profile");
+            println!("{}", "-".repeat(width + 40));
+            for p in &profiles {
+                let marker = if p.active { '*' } else { ' ' };
+                println!("{marker} {:<width$}  {}", p.name, p.path.display());
+            }
+        }
+        ProfileAction::Create { name } => {
+            profile::ensure_new_profile(name)?;
+            let key = crypto::get_passphrase(name, true)
+                .map_err(|e| AppError::SystemError(format!("{e:#}")))?;
+            let created = profile::create_profile(name, &key.value, active)?;
+            if key.prompted {
+                crypto::store_in_keyring(name, &key.value).ok();
+            }
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "profile": created }));
+            }
+            println!(
+                "Profile '{}' created at {}.",
+                created.name,
+                created.path.display()
+            );
+            println!("Switch to it with: vibe-clock profile use {}", created.name);
+        }
+        ProfileAction::Use { name } => {
+            let info = profile::use_profile(name)?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "profile": info }));
+            }
+            println!("Now using profile '{}'.", info.name);
+        }
+    }
+    Ok(())
+}
@@ -1,24 +1,63 @@
 use std::io::{self, BufRead, Write};
 
+use serde::Serialize;
+use serde_json::json;
+
 use crate::clock_trait::Clock;
 use crate::db::Database;
 use crate::error::AppError;
+use crate::models::Project;
 use crate::services::project::{self, DeleteResult};
 
 use super::ProjectAction;
+use super::output::{OutputFormat, print_json, print_tsv_row};
+
+#[derive(Serialize)]
+struct ProjectListing {
+    #[serde(flatten)]
+    project: Project,
+    task_count: i64,
+}
 
 pub fn handle_project(
     db: &Database,
     clock: &dyn Clock,
     action: ProjectAction,
+    out: OutputFormat,
 ) -> Result<(), AppError> {
     match action {
         ProjectAction::Add { name } => {
             let project = project::create_project(db, &name, clock.now())?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "project": project }));
+            }
             println!("Project '{}' created.", project.name);
         }
         ProjectAction::List => {
-            let projects = project::list_projects(db)?;
+            let projects = project::list_projects(db)?
+                .into_iter()
+                .map(|project| {
+                    Ok(ProjectListing {
+                        task_count: db.count_tasks_for_project(project.id)?,
+                        project,
+                    })
+                })
+                .collect::<Result<Vec<_>, AppError>>()?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "projects": projects }));
+            }
+            if out == OutputFormat::Tsv {
+                print_tsv_row(&["id", "name", "task_count", "created_at"]);
+                for p in &projects {
+                    print_tsv_row(&[
+                        p.project.id.to_string(),
+                        p.project.name.clone(),
+                        p.task_count.to_string(),
+                        p.project.created_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                    ]);
+                }
+                return Ok(());
+            }
             if projects.is_empty() {
                 println!("No projects found. Create one with: vibe-clock project add <name>");
             } else {
@@ -28,42 +67,59 @@ pub fn handle_project(
                 );
                 println!("{}", "-".repeat(56));
                 for p in &projects {
-                    let task_count = db.count_tasks_for_project(p.id)?;
                     println!(
                         "{:<6} {:<20} {:<8} {:<20}",
-                        p.id,
-                        p.name,
-                        task_count,
-                        p.created_at.format("%Y-%m-%d %H:%M")
+                        p.project.id,
+                        p.project.name,
+                        p.task_count,
+                        p.project.created_at.format("%Y-%m-%d %H:%M")
                     );
                 }
             }
         }
         ProjectAction::Edit { id, name } => {
-            project::rename_project(db, id, &name, clock.now())?;
-            println!("Project renamed to '{}'.", name.trim());
-        }
-        ProjectAction::Delete { id, yes } => match project::delete_project(db, id, yes)? {
-            DeleteResult::Deleted { name } => {
-                println!("Project '{name}' deleted.");
+            let project = project::rename_project(db, id, &name, clock.now())?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "project": project }));
             }
-            DeleteResult::NeedsConfirmation { name, task_count } => {
-                eprint!(
-                    "Project '{name}' has {task_count} tasks. Delete project and all tasks? [y/N] "
-                );
-                io::stderr().flush().ok();
+            println!("Project renamed to '{}'.", project.name);
+        }
+        ProjectAction::Delete { id, yes } => {
+            match project::delete_project(db, id, yes, clock.now())? {
+                DeleteResult::Deleted { project } => {
+                    print_deleted(&project, out)?;
+                }
+                DeleteResult::NeedsConfirmation { name, task_count } => {
+                    eprint!(
+                        "Project '{name}' has {task_count} tasks. Delete project and all tasks? [y/N] "
+                    );
+                    io::stderr().flush().ok();
 
-                let stdin = io::stdin();
-                let answer = stdin.lock().lines().next().transpose().ok().flatten();
+                    let stdin = io::stdin();
+                    let answer = stdin.lock().lines().next().transpose().ok().flatten();
 
-                if answer.as_deref() == Some("y") || answer.as_deref() == Some("Y") {
-                    project::delete_project(db, id, true)?;
-                    println!("Project '{name}' deleted.");
-                } else {
-                    println!("Cancelled.");
+                    if answer.as_deref() == Some("y") || answer.as_deref() == Some("Y") {
+                        if let DeleteResult::Deleted { project } =
+                            project::delete_project(db, id, true, clock.now())?
+                        {
+                            print_deleted(&project, out)?;
+                        }
+                    } else if out == OutputFormat::Json {
+                        print_json(&json!({ "cancelled": true }))?;
+                    } else {
+                        println!("Cancelled.");
+                    }
                 }
             }
-        },
+        }
+    }
+    Ok(())
+}
+
+fn print_deleted(project: &Project, out: OutputFormat) -> Result<(), AppError> {
+    if out == OutputFormat::Json {
+        return print_json(&json!({ "deleted_project": project }));
     }
+    println!("Project '{}' deleted.", project.name);
     Ok(())
 }
@@ -1,9 +1,23 @@
+use std::io;
 use std::path::PathBuf;
 
+use clap::ValueEnum;
+use serde_json::json;
+
+use crate::clock_trait::Clock;
+use crate::config::Settings;
 use crate::db::Database;
 use crate::error::AppError;
 use crate::formatting::{self, format_duration};
-use crate::services::{pdf, report};
+use crate::services::csv_export::{self, CsvOptions, CsvRenderer};
+use crate::services::html::HtmlRenderer;
+use crate::services::markdown::MarkdownRenderer;
+use crate::services::pdf::{self, PdfOptions, PdfRenderer};
+use crate::services::render::{self, ReportRenderer};
+use crate::services::report;
+
+use super::output::{OutputFormat, print_entries_tsv, print_json};
+use super::{ReportArgs, ReportFormat};
 
 /// Split a description into lines of at most `width` characters.
 /// Splits at word boundaries where possible; hard-splits at `width` if no space is found.
@@ -29,14 +43,30 @@ fn wrap_description(desc: &str, width: usize) -> Vec<String> {
 
 pub fn handle_report(
     db: &Database,
-    from: &str,
-    to: Option<&str>,
-    pdf_flag: bool,
-    output: Option<&str>,
+    clk: &dyn Clock,
+    settings: &Settings,
+    args: ReportArgs,
+    out: OutputFormat,
 ) -> Result<(), AppError> {
-    let from_date = formatting::parse_date(from).map_err(|e| AppError::UserError(e.to_string()))?;
-    let to_str = to.unwrap_or(from);
-    let to_date = formatting::parse_date(to_str).map_err(|e| AppError::UserError(e.to_string()))?;
+    let parse =
+        |d: &str| formatting::parse_date(d, clk).map_err(|e| AppError::UserError(e.to_string()));
+    let (from_date, to_date) = match args.from.as_deref() {
+        Some(from) => {
+            let from_date = parse(from)?;
+            let to_date = args.to.as_deref().map(parse).transpose()?;
+            (from_date, to_date.unwrap_or(from_date))
+        }
+        None => {
+            let Some(range) = settings.report_range() else {
+                return Err(AppError::UserError(
+                    "--from is required unless report.range is set in the config file.".to_string(),
+                ));
+            };
+            let (start, end) = range.dates(clk.now().date(), settings.week_start());
+            let to_date = args.to.as_deref().map(parse).transpose()?;
+            (start, to_date.unwrap_or(end))
+        }
+    };
 
     if from_date > to_date {
         return Err(AppError::UserError(
@@ -44,24 +74,99 @@ pub fn handle_report(
         ));
     }
 
-    let pdf_path = pdf::resolve_pdf_path(output, pdf_flag, from_date, to_date)?;
+    let edited_after = match args.edited_after.as_deref() {
+        Some(d) => {
+            Some(formatting::parse_date(d, clk).map_err(|e| AppError::UserError(e.to_string()))?)
+        }
+        None => None,
+    };
+
+    let csv_path = args
+        .csv
+        .as_deref()
+        .map(csv_export::resolve_csv_path)
+        .transpose()?;
+    let delimiter = args
+        .delimiter
+        .as_deref()
+        .or(settings.report.csv_delimiter.as_deref())
+        .unwrap_or(",");
+    let csv_options = CsvOptions {
+        delimiter: csv_export::parse_delimiter(delimiter)?,
+        header: !args.no_header,
+        summary: args.summary,
+    };
 
-    let report = report::generate_report(db, from_date, to_date)?;
+    // The configured default format gives way to an explicit --pdf
+    let format = match (args.format, settings.report.format.as_deref()) {
+        (Some(format), _) => format,
+        (None, Some(format)) if !args.pdf => {
+            ReportFormat::from_str(format, true).map_err(AppError::UserError)?
+        }
+        _ => ReportFormat::Text,
+    };
+    let pdf_options = pdf_options(settings);
+    let rounding = settings.rounding();
+    let date_format = settings.date_format();
+    let time_format = settings.time_format();
+
+    if let Some(renderer) = renderer_for(format, csv_options, &pdf_options) {
+        if args.pdf {
+            return Err(AppError::UserError(
+                "--pdf cannot be combined with --format; use --format pdf instead.".to_string(),
+            ));
+        }
+        // Text formats go to stdout unless --output is given; binary ones always to a file
+        let path = render::resolve_output_path(
+            args.output.as_deref(),
+            renderer.extension(),
+            renderer.extension_aliases(),
+            renderer.is_binary(),
+            from_date,
+            to_date,
+        )?;
+        let report = report::generate_report(db, from_date, to_date, edited_after, rounding)?;
+        let (_, csv_path) = write_files(&report, None, csv_path, &csv_options, &pdf_options)?;
+
+        let Some(path) = path else {
+            return renderer.render(&report, &mut io::stdout().lock());
+        };
+        let abs_path = absolute(path);
+        renderer.render_to_file(&report, &abs_path)?;
+        if out == OutputFormat::Json {
+            return print_json(&json!({ "path": abs_path, "csv_path": csv_path }));
+        }
+        println!("{} report saved to {}", renderer.name(), abs_path.display());
+        return Ok(());
+    }
+
+    let pdf_path = pdf::resolve_pdf_path(args.output.as_deref(), args.pdf, from_date, to_date)?;
+    let report = report::generate_report(db, from_date, to_date, edited_after, rounding)?;
+
+    if out != OutputFormat::Text {
+        let (pdf_path, csv_path) =
+            write_files(&report, pdf_path, csv_path, &csv_options, &pdf_options)?;
+        if out == OutputFormat::Json {
+            return print_json(&json!({
+                "report": report,
+                "pdf_path": pdf_path,
+                "csv_path": csv_path,
+            }));
+        }
+        print_entries_tsv(report.daily_sections.iter().flat_map(|s| &s.entries));
+        return Ok(());
+    }
 
     if report.project_summaries.is_empty() {
-        println!(
-            "No tasks found between {} and {}.",
-            from_date.format("%Y-%m-%d"),
-            to_date.format("%Y-%m-%d")
-        );
+        println!("{}", render::no_tasks_message(&report, date_format));
         return Ok(());
     }
 
     // Report header
     println!(
         "Report: {} to {}",
-        from_date.format("%Y-%m-%d"),
-        to_date.format("%Y-%m-%d")
+        from_date.format(date_format),
+        to_date.format(date_format)
     );
     println!();
 
@@ -90,7 +195,7 @@ pub fn handle_report(
     let day_sep = "-".repeat(6 + 1 + 18 + 1 + 40 + 1 + 7 + 1 + 7 + 1 + 8);
 
     for section in &report.daily_sections {
-        println!("{}", section.date.format("%Y-%m-%d"));
+        println!("{}", section.date.format(date_format));
         println!(
             "{:<6} {:<18} {:<40} {:<7} {:<7} {:<8}",
             "ID", "Project", "Description", "Start", "End", "Duration"
@@ -101,19 +206,19 @@ pub fn handle_report(
             let start = entry
                 .task
                 .start_time
-                .map(|t| t.format("%H:%M").to_string())
+                .map(|t| t.format(time_format).to_string())
                 .unwrap_or_else(|| "-".to_string());
             let end = entry
                 .task
                 .end_time
-                .map(|t| t.format("%H:%M").to_string())
+                .map(|t| t.format(time_format).to_string())
                 .unwrap_or_else(|| "-".to_string());
 
             let chunks = wrap_description(&entry.task.description, 40);
 
             println!(
                 "{:<6} {:<18} {:<40} {:<7} {:<7} {:<8}",
-                entry.task.id,
+                render::entry_id(entry, "*"),
                 entry.project_name,
                 chunks[0],
                 start,
@@ -128,22 +233,110 @@ pub fn handle_report(
         println!();
     }
 
-    // Generate PDF if requested
+    if let Some(date) = edited_after {
+        let edited_count = report
+            .daily_sections
+            .iter()
+            .flat_map(|s| &s.entries)
+            .filter(|e| e.edited)
+            .count();
+        println!(
+            "* {edited_count} entries added or edited after {}",
+            date.format(date_format)
+        );
+    }
+
+    let (pdf_path, csv_path) =
+        write_files(&report, pdf_path, csv_path, &csv_options, &pdf_options)?;
     if let Some(path) = pdf_path {
-        let abs_path = if path.is_absolute() {
-            path
-        } else {
-            std::env::current_dir()
-                .unwrap_or_else(|_| PathBuf::from("."))
-                .join(&path)
-        };
-        pdf::render_pdf(&report, &abs_path)?;
-        println!("PDF report saved to {}", abs_path.display());
+        println!("PDF report saved to {}", path.display());
+    }
+    if let Some(path) = csv_path {
+        println!("CSV report saved to {}", path.display());
     }
 
     Ok(())
 }
 
+/// Write the requested PDF and CSV files, returning the absolute paths written.
+///
+/// Nothing is written for an empty report.
+fn write_files(
+    report: &report::Report,
+    pdf_path: Option<PathBuf>,
+    csv_path: Option<PathBuf>,
+    csv_options: &CsvOptions,
+    pdf_options: &PdfOptions,
+) -> Result<(Option<PathBuf>, Option<PathBuf>), AppError> {
+    if report.project_summaries.is_empty() {
+        return Ok((None, None));
+    }
+
+    let pdf_path = match pdf_path {
+        Some(path) => {
+            let abs_path = absolute(path);
+            PdfRenderer {
+                options: pdf_options.clone(),
+            }
+            .render_to_file(report, &abs_path)?;
+            Some(abs_path)
+        }
+        None => None,
+    };
+    let csv_path = match csv_path {
+        Some(path) => {
+            let abs_path = absolute(path);
+            CsvRenderer {
+                options: *csv_options,
+            }
+            .render_to_file(report, &abs_path)?;
+            Some(abs_path)
+        }
+        None => None,
+    };
+    Ok((pdf_path, csv_path))
+}
+
+/// PDF layout taken from the config file.
+fn pdf_options(settings: &Settings) -> PdfOptions {
+    PdfOptions {
+        title: settings.pdf_title().to_string(),
+        font_size: settings.pdf_font_size(),
+        margins: settings.pdf_margins(),
+        date_format: settings.date_format().to_string(),
+        time_format: settings.time_format().to_string(),
+    }
+}
+
+/// The renderer for a `--format` other than text.
+fn renderer_for(
+    format: ReportFormat,
+    csv_options: CsvOptions,
+    pdf_options: &PdfOptions,
+) -> Option<Box<dyn ReportRenderer>> {
+    match format {
+        ReportFormat::Text => None,
+        ReportFormat::Csv => Some(Box::new(CsvRenderer {
+            options: csv_options,
+        })),
+        ReportFormat::Md => Some(Box::new(MarkdownRenderer)),
+        ReportFormat::Html => Some(Box::new(HtmlRenderer)),
+        ReportFormat::Pdf => Some(Box::new(PdfRenderer {
+            options: pdf_options.clone(),
+        })),
+    }
+}
+
+pub(super) fn absolute(path: PathBuf) -> PathBuf {
+    if path.is_absolute() {
+        path
+    } else {
+        std::env::current_dir()
+            .unwrap_or_else(|_| PathBuf::from("."))
+            .join(&path)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,40 +1,126 @@
 use std::io::{self, BufRead, Write};
 
+use serde_json::json;
+
 use crate::clock_trait::Clock;
+use crate::config::Settings;
 use crate::db::Database;
 use crate::error::AppError;
 use crate::formatting::format_duration;
-use crate::services::task;
+use crate::services::{ProjectDefaults, task, template};
 
-use super::TaskAction;
+use super::output::{OutputFormat, print_json, print_tsv_row};
+use super::{TaskAction, split_entry_args};
 
-pub fn handle_task(db: &Database, clock: &dyn Clock, action: TaskAction) -> Result<(), AppError> {
+pub fn handle_task(
+    db: &Database,
+    clock: &dyn Clock,
+    settings: &Settings,
+    action: TaskAction,
+    out: OutputFormat,
+) -> Result<(), AppError> {
     match action {
         TaskAction::Add {
             project,
             description,
+            template: template_name,
             start,
             end,
             duration,
             date,
         } => {
+            let (project, description) = split_entry_args(project, description);
+            let fields = template::resolve_entry_fields(
+                db,
+                template_name.as_deref(),
+                project.as_deref(),
+                description.as_deref(),
+                &ProjectDefaults::new(settings),
+            )?;
+            let duration = duration.or_else(|| fields.duration_min.map(|d| d.to_string()));
             let entry = task::add_task(
                 db,
-                &project,
-                &description,
+                &fields.project,
+                &fields.description,
                 start.as_deref(),
                 end.as_deref(),
                 duration.as_deref(),
                 date.as_deref(),
                 clock,
             )?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "task": entry }));
+            }
+            let project_name = db
+                .find_project_by_id(entry.project_id)?
+                .map(|p| p.name)
+                .unwrap_or_else(|| "?".to_string());
             println!(
                 "Task logged: {} for '{}' on project '{}'.",
                 format_duration(entry.duration_min),
                 entry.description,
-                project
+                project_name
             );
         }
+        TaskAction::History { id } => {
+            let history = task::task_history(db, id)?;
+            match out {
+                OutputFormat::Json => return print_json(&json!({ "history": history })),
+                OutputFormat::Tsv => {
+                    print_tsv_row(&["changed_at", "action", "field", "old_value", "new_value"]);
+                    for h in &history {
+                        print_tsv_row(&[
+                            h.changed_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                            h.action.clone(),
+                            h.field.clone().unwrap_or_default(),
+                            h.old_value.clone().unwrap_or_default(),
+                            h.new_value.clone().unwrap_or_default(),
+                        ]);
+                    }
+                    return Ok(());
+                }
+                OutputFormat::Text => {}
+            }
+            if history.is_empty() {
+                println!("No history recorded for task {id} (created before history tracking).");
+                return Ok(());
+            }
+
+            let mut projects = db.list_projects()?;
+            projects.extend(db.list_deleted_projects()?.into_iter().map(|(p, _)| p));
+
+            println!("History for task {id}:");
+            println!();
+            println!(
+                "{:<17} {:<8} {:<12} {:<25} {:<25}",
+                "Changed", "Action", "Field", "Old", "New"
+            );
+            println!("{}", "-".repeat(91));
+            for h in &history {
+                let render = |value: Option<&str>| match (h.field.as_deref(), value) {
+                    (_, None) => "-".to_string(),
+                    (Some("project_id"), Some(v)) => projects
+                        .iter()
+                        .find(|p| p.id.to_string() == v)
+                        .map(|p| p.name.clone())
+                        .unwrap_or_else(|| format!("#{v}")),
+                    (Some("duration_min"), Some(v)) => v
+                        .parse::<i64>()
+                        .map(format_duration)
+                        .unwrap_or_else(|_| v.to_string()),
+                    (Some("start_time" | "end_time"), Some(v)) => v.replacen('T', " ", 1),
+                    (_, Some(v)) => v.to_string(),
+                };
+                println!(
+                    "{:<17} {:<8} {:<12} {:<25} {:<25}",
+                    h.changed_at.format("%Y-%m-%d %H:%M"),
+                    h.action,
+                    h.field.as_deref().unwrap_or("-"),
+                    render(h.old_value.as_deref()),
+                    render(h.new_value.as_deref())
+                );
+            }
+        }
         TaskAction::Edit {
             id,
             description,
@@ -44,7 +130,7 @@ pub fn handle_task(db: &Database, clock: &dyn Clock, action: TaskAction) -> Resu
             duration,
             date,
         } => {
-            task::edit_task(
+            let entry = task::edit_task(
                 db,
                 id,
                 description.as_deref(),
@@ -55,6 +141,9 @@ pub fn handle_task(db: &Database, clock: &dyn Clock, action: TaskAction) -> Resu
                 date.as_deref(),
                 clock,
             )?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "task": entry }));
+            }
             println!("Task {id} updated.");
         }
         TaskAction::Delete { id, yes } => {
@@ -74,12 +163,18 @@ pub fn handle_task(db: &Database, clock: &dyn Clock, action: TaskAction) -> Resu
                 let answer = stdin.lock().lines().next().transpose().ok().flatten();
 
                 if answer.as_deref() != Some("y") && answer.as_deref() != Some("Y") {
+                    if out == OutputFormat::Json {
+                        return print_json(&json!({ "cancelled": true }));
+                    }
                     println!("Cancelled.");
                     return Ok(());
                 }
             }
 
-            task::delete_task(db, id)?;
+            let entry = task::delete_task(db, id, clock.now())?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "deleted_task": entry }));
+            }
             println!("Task {id} deleted.");
         }
     }
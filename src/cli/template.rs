@@ -0,0 +1,98 @@
+use serde_json::json;
+
+use crate::clock_trait::Clock;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::formatting::format_duration;
+use crate::services::template;
+
+use super::TemplateAction;
+use super::output::{OutputFormat, print_json, print_tsv_row};
+
+pub fn handle_template(
+    db: &Database,
+    clock: &dyn Clock,
+    action: TemplateAction,
+    out: OutputFormat,
+) -> Result<(), AppError> {
+    match action {
+        TemplateAction::Add {
+            name,
+            project,
+            description,
+            duration,
+        } => {
+            let tpl = template::create_template(
+                db,
+                &name,
+                &project,
+                &description,
+                duration.as_deref(),
+                clock.now(),
+            )?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "template": tpl }));
+            }
+            println!("Template '{}' created.", tpl.name);
+        }
+        TemplateAction::List => {
+            let templates = template::list_templates(db)?;
+            match out {
+                OutputFormat::Json => return print_json(&json!({ "templates": templates })),
+                OutputFormat::Tsv => {
+                    let projects = db.list_projects()?;
+                    print_tsv_row(&["name", "project", "description", "duration_min"]);
+                    for t in &templates {
+                        print_tsv_row(&[
+                            t.name.clone(),
+                            projects
+                                .iter()
+                                .find(|p| p.id == t.project_id)
+                                .map(|p| p.name.clone())
+                                .unwrap_or_else(|| "?".to_string()),
+                            t.description.clone(),
+                            t.duration_min.map(|d| d.to_string()).unwrap_or_default(),
+                        ]);
+                    }
+                    return Ok(());
+                }
+                OutputFormat::Text => {}
+            }
+            if templates.is_empty() {
+                println!(
+                    "No templates found. Create one with: vibe-clock template add <name> <project> <description>"
+                );
+            } else {
+                let projects = db.list_projects()?;
+                println!(
+                    "{:<15} {:<20} {:<30} {:<8}",
+                    "Name", "Project", "Description", "Duration"
+                );
+                println!("{}", "-".repeat(76));
+                for t in &templates {
+                    let project_name = projects
+                        .iter()
+                        .find(|p| p.id == t.project_id)
+                        .map(|p| p.name.as_str())
+                        .unwrap_or("?");
+                    let duration = t
+                        .duration_min
+                        .map(format_duration)
+                        .unwrap_or_else(|| "-".to_string());
+                    println!(
+                        "{:<15} {:<20} {:<30} {:<8}",
+                        t.name, project_name, t.description, duration
+                    );
+                }
+            }
+        }
+        TemplateAction::Delete { name } => {
+            let tpl = template::delete_template(db, &name)?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "deleted_template": tpl }));
+            }
+            println!("Template '{name}' deleted.");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use std::io::{self, BufRead, Write};
+
+use serde_json::json;
+
+use crate::clock_trait::Clock;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::formatting::{self, format_duration};
+use crate::services::trash;
+
+use super::TrashAction;
+use super::output::{OutputFormat, print_json, print_tsv_row};
+
+pub fn handle_trash(
+    db: &Database,
+    clock: &dyn Clock,
+    action: TrashAction,
+    out: OutputFormat,
+) -> Result<(), AppError> {
+    match action {
+        TrashAction::List => {
+            let contents = trash::list_trash(db)?;
+            match out {
+                OutputFormat::Json => return print_json(&json!({ "trash": contents })),
+                OutputFormat::Tsv => {
+                    let ts = |t: chrono::NaiveDateTime| t.format("%Y-%m-%dT%H:%M:%S").to_string();
+                    print_tsv_row(&[
+                        "kind",
+                        "id",
+                        "project",
+                        "description",
+                        "duration_min",
+                        "deleted_at",
+                    ]);
+                    for p in &contents.projects {
+                        print_tsv_row(&[
+                            "project".to_string(),
+                            p.project.id.to_string(),
+                            p.project.name.clone(),
+                            String::new(),
+                            String::new(),
+                            ts(p.deleted_at),
+                        ]);
+                    }
+                    for t in &contents.tasks {
+                        print_tsv_row(&[
+                            "task".to_string(),
+                            t.task.id.to_string(),
+                            t.project_name.clone(),
+                            t.task.description.clone(),
+                            t.task.duration_min.to_string(),
+                            ts(t.deleted_at),
+                        ]);
+                    }
+                    return Ok(());
+                }
+                OutputFormat::Text => {}
+            }
+            if contents.projects.is_empty() && contents.tasks.is_empty() {
+                println!("Trash is empty.");
+                return Ok(());
+            }
+
+            if !contents.projects.is_empty() {
+                println!("Projects:");
+                println!(
+                    "{:<6} {:<20} {:<8} {:<20}",
+                    "ID", "Name", "Tasks", "Deleted"
+                );
+                println!("{}", "-".repeat(56));
+                for p in &contents.projects {
+                    println!(
+                        "{:<6} {:<20} {:<8} {:<20}",
+                        p.project.id,
+                        p.project.name,
+                        p.task_count,
+                        p.deleted_at.format("%Y-%m-%d %H:%M")
+                    );
+                }
+                println!();
+            }
+
+            if !contents.tasks.is_empty() {
+                println!("Tasks:");
+                println!(
+                    "{:<6} {:<15} {:<30} {:<8} {:<20}",
+                    "ID", "Project", "Description", "Duration", "Deleted"
+                );
+                println!("{}", "-".repeat(82));
+                for t in &contents.tasks {
+                    println!(
+                        "{:<6} {:<15} {:<30} {:<8} {:<20}",
+                        t.task.id,
+                        t.project_name,
+                        t.task.description,
+                        format_duration(t.task.duration_min),
+                        t.deleted_at.format("%Y-%m-%d %H:%M")
+                    );
+                }
+            }
+        }
+        TrashAction::Restore { id, project: true } => {
+            let project = trash::restore_project(db, id, clock.now())?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "restored_project": project }));
+            }
+            println!("Project '{}' restored.", project.name);
+        }
+        TrashAction::Restore { id, project: false } => {
+            let task = trash::restore_task(db, id, clock.now())?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "restored_task": task }));
+            }
+            println!("Task {id} restored.");
+        }
+        TrashAction::Empty { older_than, yes } => {
+            let days = match &older_than {
+                Some(age) => Some(
+                    formatting::parse_age_days(age)
+                        .map_err(|e| AppError::UserError(e.to_string()))?,
+                ),
+                None => None,
+            };
+
+            if !yes {
+                match &older_than {
+                    Some(age) => eprint!(
+                        "Permanently delete items trashed more than {age} ago? This cannot be undone. [y/N] "
+                    ),
+                    None => eprint!(
+                        "Permanently delete everything in the trash? This cannot be undone. [y/N] "
+                    ),
+                }
+                io::stderr().flush().ok();
+
+                let stdin = io::stdin();
+                let answer = stdin.lock().lines().next().transpose().ok().flatten();
+                if answer.as_deref() != Some("y") && answer.as_deref() != Some("Y") {
+                    if out == OutputFormat::Json {
+                        return print_json(&json!({ "cancelled": true }));
+                    }
+                    println!("Cancelled.");
+                    return Ok(());
+                }
+            }
+
+            let result = trash::empty_trash(db, days, clock.now())?;
+            if out == OutputFormat::Json {
+                return print_json(&json!({ "purged": result }));
+            }
+            println!(
+                "Permanently deleted {} projects and {} tasks.",
+                result.projects, result.tasks
+            );
+        }
+    }
+    Ok(())
+}
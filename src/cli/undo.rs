@@ -0,0 +1,32 @@
+use serde_json::json;
+
+use crate::clock_trait::Clock;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::services::undo;
+
+use super::output::{OutputFormat, print_json};
+
+pub fn handle_undo(db: &Database, clock: &dyn Clock, out: OutputFormat) -> Result<(), AppError> {
+    let op = undo::undo(db, clock.now())?;
+    if out == OutputFormat::Json {
+        return print_json(&json!({ "undone": op }));
+    }
+    match op {
+        Some(op) => println!("Undone: {}.", op.describe()),
+        None => println!("Nothing to undo."),
+    }
+    Ok(())
+}
+
+pub fn handle_redo(db: &Database, clock: &dyn Clock, out: OutputFormat) -> Result<(), AppError> {
+    let op = undo::redo(db, clock.now())?;
+    if out == OutputFormat::Json {
+        return print_json(&json!({ "redone": op }));
+    }
+    match op {
+        Some(op) => println!("Redone: {}.", op.describe()),
+        None => println!("Nothing to redo."),
+    }
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use chrono::NaiveDateTime;
+use chrono::{NaiveDateTime, Timelike};
 
 pub trait Clock {
     fn now(&self) -> NaiveDateTime;
@@ -7,7 +7,9 @@ pub trait Clock {
 pub struct SystemClock;
 
 impl Clock for SystemClock {
+    /// Current local time, truncated to whole seconds to match what the database stores.
     fn now(&self) -> NaiveDateTime {
-        chrono::Local::now().naive_local()
+        let now = chrono::Local::now().naive_local();
+        now.with_nanosecond(0).unwrap_or(now)
     }
 }
@@ -0,0 +1,35 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// Per-directory defaults, e.g. at the root of a repository checkout.
+pub const DIRECTORY_FILE: &str = ".vibe-clock";
+
+/// Contents of a `.vibe-clock` file.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DirectoryDefaults {
+    pub project: Option<String>,
+    pub description: Option<String>,
+    /// The file these defaults were read from.
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+/// Read the nearest `.vibe-clock` file in `start` or any of its ancestors.
+pub fn find_directory_defaults(start: &Path) -> Result<Option<DirectoryDefaults>> {
+    let Some(path) = start
+        .ancestors()
+        .map(|dir| dir.join(DIRECTORY_FILE))
+        .find(|path| path.is_file())
+    else {
+        return Ok(None);
+    };
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut defaults: DirectoryDefaults =
+        toml::from_str(&text).with_context(|| format!("Invalid {}", path.display()))?;
+    defaults.path = path;
+    Ok(Some(defaults))
+}
@@ -0,0 +1,155 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+
+mod directory;
+mod settings;
+
+pub use directory::{DIRECTORY_FILE, DirectoryDefaults, find_directory_defaults};
+pub use settings::{
+    ReportRange, Rounding, RoundingMode, SETTINGS, Settings, config_path, get_setting,
+    load_settings, read_document, set_setting, unset_setting, write_document,
+};
+
+const DEFAULT_BACKUP_RETENTION: usize = 7;
+
+/// The profile used when none is selected; its database predates profiles.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// File in the data directory naming the profile chosen with `profile use`.
+const ACTIVE_PROFILE_FILE: &str = "active-profile";
+
+/// Directory holding all vibe-clock data.
+///
+/// `VIBE_CLOCK_DATA_DIR` if set, otherwise `<data_dir>/vibe-clock`.
+pub fn data_dir() -> Result<PathBuf> {
+    if let Some(dir) = std::env::var_os("VIBE_CLOCK_DATA_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+    let data_dir = dirs::data_dir().context("Could not determine platform data directory")?;
+    Ok(data_dir.join("vibe-clock"))
+}
+
+/// Profile names become directory and keyring entry names, so keep them plain.
+pub fn validate_profile_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        bail!(
+            "Invalid profile name '{name}': use letters, digits, '-' and '_' (at most 64 characters)"
+        );
+    }
+    Ok(())
+}
+
+/// Name of the profile to use.
+///
+/// Priority:
+/// 1. `--profile` flag
+/// 2. `VIBE_CLOCK_PROFILE` environment variable
+/// 3. The profile chosen with `profile use`
+/// 4. `default`
+pub fn active_profile(flag: Option<&str>) -> Result<String> {
+    let name = match flag {
+        Some(name) => name.to_string(),
+        None => match std::env::var("VIBE_CLOCK_PROFILE") {
+            Ok(name) => name,
+            Err(_) => match std::fs::read_to_string(data_dir()?.join(ACTIVE_PROFILE_FILE)) {
+                Ok(name) => name.trim().to_string(),
+                Err(_) => DEFAULT_PROFILE.to_string(),
+            },
+        },
+    };
+    validate_profile_name(&name)?;
+    Ok(name)
+}
+
+/// Remember `name` as the profile for later invocations.
+pub fn set_active_profile(name: &str) -> Result<()> {
+    let dir = data_dir()?;
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+    let path = dir.join(ACTIVE_PROFILE_FILE);
+    std::fs::write(&path, format!("{name}\n"))
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Database file of a profile, ignoring `VIBE_CLOCK_DB`.
+///
+/// The default profile keeps `<data_dir>/vibe-clock.db`; others live under
+/// `<data_dir>/profiles/<name>/`, next to their own backups.
+pub fn profile_db_path(profile: &str) -> Result<PathBuf> {
+    let dir = data_dir()?;
+    if profile == DEFAULT_PROFILE {
+        return Ok(dir.join("vibe-clock.db"));
+    }
+    Ok(dir.join("profiles").join(profile).join("vibe-clock.db"))
+}
+
+/// Whether a profile has been created. The default profile always exists.
+pub fn profile_exists(profile: &str) -> Result<bool> {
+    if profile == DEFAULT_PROFILE {
+        return Ok(true);
+    }
+    Ok(profile_db_path(profile)?.exists())
+}
+
+/// The default profile followed by every created profile, sorted by name.
+pub fn list_profiles() -> Result<Vec<String>> {
+    let mut names = Vec::new();
+    let dir = data_dir()?.join("profiles");
+    if dir.is_dir() {
+        for entry in
+            std::fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))?
+        {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if entry.path().join("vibe-clock.db").exists() && validate_profile_name(&name).is_ok() {
+                names.push(name);
+            }
+        }
+    }
+    names.sort();
+    names.insert(0, DEFAULT_PROFILE.to_string());
+    Ok(names)
+}
+
+/// Resolve the database file path for `profile`.
+///
+/// Priority:
+/// 1. `VIBE_CLOCK_DB` environment variable
+/// 2. `database.path` in the config file, for the default profile only
+/// 3. The profile's database, see [`profile_db_path`]
+///
+/// Creates parent directories if they don't exist.
+pub fn resolve_db_path(profile: &str, settings: &Settings) -> Result<PathBuf> {
+    let path = if let Ok(env_path) = std::env::var("VIBE_CLOCK_DB") {
+        PathBuf::from(env_path)
+    } else if let (DEFAULT_PROFILE, Some(path)) = (profile, &settings.database.path) {
+        path.clone()
+    } else {
+        profile_db_path(profile)?
+    };
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    Ok(path)
+}
+
+/// Number of daily automatic backups to keep.
+///
+/// Read from `VIBE_CLOCK_BACKUP_KEEP`; defaults to 7. Zero turns automatic backups off.
+pub fn backup_retention() -> Result<usize> {
+    match std::env::var("VIBE_CLOCK_BACKUP_KEEP") {
+        Ok(value) => value.trim().parse().with_context(|| {
+            format!("VIBE_CLOCK_BACKUP_KEEP must be a number of backups, got '{value}'")
+        }),
+        Err(_) => Ok(DEFAULT_BACKUP_RETENTION),
+    }
+}
@@ -0,0 +1,534 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use toml::Table;
+use toml_edit::{DocumentMut, Item, Value};
+
+/// Every setting as `(key, description)`; a key is `section.name` in the file.
+pub const SETTINGS: [(&str, &str); 13] = [
+    ("database.path", "Database file of the default profile"),
+    (
+        "defaults.project",
+        "Project used when a command leaves it out",
+    ),
+    ("week.start", "First day of the week: monday ... sunday"),
+    (
+        "rounding.minutes",
+        "Round report durations to this many minutes",
+    ),
+    ("rounding.mode", "How to round: up, down or nearest"),
+    (
+        "display.date_format",
+        "strftime format for dates in text and PDF output",
+    ),
+    (
+        "display.time_format",
+        "strftime format for times in text and PDF output",
+    ),
+    (
+        "report.format",
+        "Default report --format: text, csv, md, html or pdf",
+    ),
+    (
+        "report.range",
+        "Report period when --from is left out: day, week or month",
+    ),
+    (
+        "report.csv_delimiter",
+        "Default CSV delimiter: one character, or \"tab\"",
+    ),
+    ("pdf.title", "Title printed on PDF reports"),
+    ("pdf.font_size", "PDF font size in points"),
+    ("pdf.margins", "PDF page margins in millimetres"),
+];
+
+/// Settings stored as TOML integers rather than strings.
+const INTEGER_SETTINGS: [&str; 3] = ["rounding.minutes", "pdf.font_size", "pdf.margins"];
+
+const REPORT_FORMATS: [&str; 5] = ["text", "csv", "md", "html", "pdf"];
+
+#[derive(Deserialize, Serialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub database: DatabaseSettings,
+    pub defaults: DefaultSettings,
+    pub week: WeekSettings,
+    pub rounding: RoundingSettings,
+    pub display: DisplaySettings,
+    pub report: ReportSettings,
+    pub pdf: PdfSettings,
+}
+
+#[derive(Deserialize, Serialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseSettings {
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Deserialize, Serialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct DefaultSettings {
+    pub project: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct WeekSettings {
+    pub start: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct RoundingSettings {
+    pub minutes: Option<i64>,
+    pub mode: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct DisplaySettings {
+    pub date_format: Option<String>,
+    pub time_format: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReportSettings {
+    pub format: Option<String>,
+    pub range: Option<String>,
+    pub csv_delimiter: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct PdfSettings {
+    pub title: Option<String>,
+    pub font_size: Option<i64>,
+    pub margins: Option<i64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundingMode {
+    Up,
+    Down,
+    Nearest,
+}
+
+/// Rounding applied to each entry's duration in reports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rounding {
+    pub minutes: i64,
+    pub mode: RoundingMode,
+}
+
+impl Rounding {
+    /// Round `duration_min` to a multiple of the step.
+    pub fn apply(&self, duration_min: i64) -> i64 {
+        let step = self.minutes;
+        let down = duration_min / step * step;
+        match self.mode {
+            RoundingMode::Down => down,
+            RoundingMode::Up if down < duration_min => down + step,
+            RoundingMode::Up => down,
+            RoundingMode::Nearest if (duration_min - down) * 2 >= step => down + step,
+            RoundingMode::Nearest => down,
+        }
+    }
+}
+
+/// The period a report covers when no `--from` is given.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportRange {
+    Day,
+    Week,
+    Month,
+}
+
+impl ReportRange {
+    /// First and last day of the period containing `today`.
+    pub fn dates(self, today: NaiveDate, week_start: Weekday) -> (NaiveDate, NaiveDate) {
+        match self {
+            ReportRange::Day => (today, today),
+            ReportRange::Week => {
+                let back = u64::from(today.weekday().days_since(week_start));
+                let start = today - Days::new(back);
+                (start, start + Days::new(6))
+            }
+            ReportRange::Month => {
+                let start = today.with_day(1).unwrap_or(today);
+                let end = start + Months::new(1) - Days::new(1);
+                (start, end)
+            }
+        }
+    }
+}
+
+/// Whether `format` renders a date.
+///
+/// chrono panics when a formatted value is displayed with a specifier it cannot
+/// fill (`%H` for a date, `%z` for a time without a zone), so try it with `write!`.
+fn renders_date(format: &str) -> bool {
+    let sample = NaiveDate::from_ymd_opt(2026, 1, 31).expect("valid sample date");
+    !format.is_empty() && write!(String::new(), "{}", sample.format(format)).is_ok()
+}
+
+/// Whether `format` renders a local date and time, like an entry's start and end.
+fn renders_time(format: &str) -> bool {
+    let sample = NaiveDate::from_ymd_opt(2026, 1, 31)
+        .and_then(|d| d.and_hms_opt(14, 5, 9))
+        .expect("valid sample time");
+    !format.is_empty() && write!(String::new(), "{}", sample.format(format)).is_ok()
+}
+
+impl Settings {
+    /// Parse and validate the contents of a config file.
+    pub fn from_table(table: &Table) -> Result<Self> {
+        let settings: Settings = toml::from_str(&toml::to_string(table)?)?;
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if let Some(start) = &self.week.start {
+            if start.parse::<Weekday>().is_err() {
+                bail!("week.start must be a day of the week such as 'monday', got '{start}'");
+            }
+        }
+        if let Some(minutes) = self.rounding.minutes {
+            if !(1..=1440).contains(&minutes) {
+                bail!("rounding.minutes must be between 1 and 1440, got {minutes}");
+            }
+        }
+        if let Some(mode) = &self.rounding.mode {
+            if !["up", "down", "nearest"].contains(&mode.as_str()) {
+                bail!("rounding.mode must be up, down or nearest, got '{mode}'");
+            }
+        }
+        for (key, format, renders) in [
+            (
+                "display.date_format",
+                &self.display.date_format,
+                renders_date as fn(&str) -> bool,
+            ),
+            (
+                "display.time_format",
+                &self.display.time_format,
+                renders_time,
+            ),
+        ] {
+            if let Some(format) = format {
+                if !renders(format) {
+                    bail!("{key} is not a valid strftime format: '{format}'");
+                }
+            }
+        }
+        if let Some(format) = &self.report.format {
+            if !REPORT_FORMATS.contains(&format.as_str()) {
+                bail!(
+                    "report.format must be one of {}, got '{format}'",
+                    REPORT_FORMATS.join(", ")
+                );
+            }
+        }
+        if let Some(range) = &self.report.range {
+            if !["day", "week", "month"].contains(&range.as_str()) {
+                bail!("report.range must be day, week or month, got '{range}'");
+            }
+        }
+        if let Some(delimiter) = &self.report.csv_delimiter {
+            let usable = match delimiter.as_bytes() {
+                &[b] => b.is_ascii() && !b.is_ascii_control() && b != b'"',
+                _ => delimiter == "tab",
+            };
+            if !usable {
+                bail!(
+                    "report.csv_delimiter must be one printable character other than '\"', or \"tab\", got '{}'",
+                    delimiter.escape_default()
+                );
+            }
+        }
+        if let Some(size) = self.pdf.font_size {
+            if !(6..=36).contains(&size) {
+                bail!("pdf.font_size must be between 6 and 36, got {size}");
+            }
+        }
+        if let Some(margins) = self.pdf.margins {
+            if !(0..=60).contains(&margins) {
+                bail!("pdf.margins must be between 0 and 60, got {margins}");
+            }
+        }
+        Ok(())
+    }
+
+    pub fn week_start(&self) -> Weekday {
+        self.week
+            .start
+            .as_deref()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(Weekday::Mon)
+    }
+
+    /// Report rounding, if `rounding.minutes` is set; the mode defaults to nearest.
+    pub fn rounding(&self) -> Option<Rounding> {
+        let mode = match self.rounding.mode.as_deref() {
+            Some("up") => RoundingMode::Up,
+            Some("down") => RoundingMode::Down,
+            _ => RoundingMode::Nearest,
+        };
+        self.rounding
+            .minutes
+            .map(|minutes| Rounding { minutes, mode })
+    }
+
+    pub fn date_format(&self) -> &str {
+        self.display.date_format.as_deref().unwrap_or("%Y-%m-%d")
+    }
+
+    pub fn time_format(&self) -> &str {
+        self.display.time_format.as_deref().unwrap_or("%H:%M")
+    }
+
+    pub fn report_range(&self) -> Option<ReportRange> {
+        match self.report.range.as_deref()? {
+            "day" => Some(ReportRange::Day),
+            "week" => Some(ReportRange::Week),
+            _ => Some(ReportRange::Month),
+        }
+    }
+
+    pub fn pdf_title(&self) -> &str {
+        self.pdf.title.as_deref().unwrap_or("Time Report")
+    }
+
+    pub fn pdf_font_size(&self) -> u8 {
+        self.pdf
+            .font_size
+            .and_then(|s| u8::try_from(s).ok())
+            .unwrap_or(10)
+    }
+
+    pub fn pdf_margins(&self) -> u8 {
+        self.pdf
+            .margins
+            .and_then(|m| u8::try_from(m).ok())
+            .unwrap_or(15)
+    }
+}
+
+/// The config file: `--config` if given, else `VIBE_CLOCK_CONFIG`, else
+/// `<config_dir>/vibe-clock/config.toml`.
+pub fn config_path(flag: Option<&Path>) -> Result<PathBuf> {
+    if let Some(path) = flag {
+        return Ok(path.to_path_buf());
+    }
+    if let Ok(path) = std::env::var("VIBE_CLOCK_CONFIG") {
+        return Ok(PathBuf::from(path));
+    }
+    let config_dir = dirs::config_dir().context("Could not determine platform config directory")?;
+    Ok(config_dir.join("vibe-clock").join("config.toml"))
+}
+
+/// The config file as written, comments and layout included; a missing file is empty.
+pub fn read_document(path: &Path) -> Result<DocumentMut> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(DocumentMut::new()),
+        Err(e) => {
+            return Err(e)
+                .with_context(|| format!("Failed to read config file {}", path.display()));
+        }
+    };
+    text.parse::<DocumentMut>()
+        .with_context(|| format!("Invalid config file {}", path.display()))
+}
+
+/// The plain values of `doc`, as the settings are read from.
+fn to_table(doc: &DocumentMut) -> Result<Table> {
+    Ok(doc.to_string().parse::<Table>()?)
+}
+
+/// Load and validate the config file.
+///
+/// A missing file means all defaults, unless it was named explicitly with `--config`.
+pub fn load_settings(path: &Path, explicit: bool) -> Result<Settings> {
+    if explicit && !path.exists() {
+        bail!("Config file {} does not exist", path.display());
+    }
+    let table = to_table(&read_document(path)?)
+        .with_context(|| format!("Invalid config file {}", path.display()))?;
+    Settings::from_table(&table).with_context(|| format!("Invalid config file {}", path.display()))
+}
+
+/// Write the config file through a temporary file.
+pub fn write_document(path: &Path, doc: &DocumentMut) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    std::fs::write(&tmp, doc.to_string())
+        .and_then(|()| std::fs::rename(&tmp, path))
+        .with_context(|| format!("Failed to write config file {}", path.display()))
+}
+
+fn split_key(key: &str) -> Result<(&str, &str)> {
+    if !SETTINGS.iter().any(|(k, _)| *k == key) {
+        bail!("Unknown setting '{key}'. Run `vibe-clock config list` to see all settings");
+    }
+    Ok(key.split_once('.').expect("setting keys have a section"))
+}
+
+/// The value of `key` in `doc`, rendered as text.
+pub fn get_setting(doc: &DocumentMut, key: &str) -> Result<Option<String>> {
+    let (section, name) = split_key(key)?;
+    let value = doc
+        .get(section)
+        .and_then(Item::as_table_like)
+        .and_then(|t| t.get(name))
+        .and_then(Item::as_value);
+    Ok(value.map(|v| match v {
+        Value::String(s) => s.value().clone(),
+        other => other.clone().decorated("", "").to_string(),
+    }))
+}
+
+/// Set `key` to `value`, refusing values the settings would not accept.
+///
+/// Only the one value changes; comments and the rest of the file are kept.
+pub fn set_setting(doc: &mut DocumentMut, key: &str, value: &str) -> Result<()> {
+    let (section, name) = split_key(key)?;
+    let mut value = if INTEGER_SETTINGS.contains(&key) {
+        Value::from(
+            value
+                .trim()
+                .parse::<i64>()
+                .with_context(|| format!("{key} must be a whole number, got '{value}'"))?,
+        )
+    } else {
+        Value::from(value)
+    };
+
+    let mut updated = doc.clone();
+    let section_table = updated.entry(section).or_insert_with(toml_edit::table);
+    let Some(section_table) = section_table.as_table_like_mut() else {
+        bail!("'{section}' in the config file is not a table");
+    };
+    match section_table.get_mut(name) {
+        // Keep the comments and spacing around an existing value
+        Some(Item::Value(existing)) => {
+            *value.decor_mut() = existing.decor().clone();
+            *existing = value;
+        }
+        _ => {
+            section_table.insert(name, Item::Value(value));
+        }
+    }
+    Settings::from_table(&to_table(&updated)?)?;
+    *doc = updated;
+    Ok(())
+}
+
+/// Remove `key`, returning whether it was set.
+pub fn unset_setting(doc: &mut DocumentMut, key: &str) -> Result<bool> {
+    let (section, name) = split_key(key)?;
+    let Some(section_table) = doc.get_mut(section).and_then(Item::as_table_like_mut) else {
+        return Ok(false);
+    };
+    let removed = section_table.remove(name).is_some();
+    if section_table.is_empty() {
+        doc.remove(section);
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn rounding_modes() {
+        let round = |mode, minutes| Rounding { minutes: 15, mode }.apply(minutes);
+        assert_eq!(round(RoundingMode::Up, 7), 15);
+        assert_eq!(round(RoundingMode::Up, 30), 30);
+        assert_eq!(round(RoundingMode::Down, 29), 15);
+        assert_eq!(round(RoundingMode::Nearest, 22), 15);
+        assert_eq!(round(RoundingMode::Nearest, 23), 30);
+    }
+
+    #[test]
+    fn report_range_dates() {
+        let wed = date("2026-02-25");
+        assert_eq!(ReportRange::Day.dates(wed, Weekday::Mon), (wed, wed));
+        assert_eq!(
+            ReportRange::Week.dates(wed, Weekday::Mon),
+            (date("2026-02-23"), date("2026-03-01"))
+        );
+        assert_eq!(
+            ReportRange::Week.dates(wed, Weekday::Sun),
+            (date("2026-02-22"), date("2026-02-28"))
+        );
+        assert_eq!(
+            ReportRange::Month.dates(wed, Weekday::Mon),
+            (date("2026-02-01"), date("2026-02-28"))
+        );
+    }
+
+    #[test]
+    fn display_formats_must_fit_their_values() {
+        let mut doc = DocumentMut::new();
+        set_setting(&mut doc, "display.date_format", "%d.%m.%Y").unwrap();
+        set_setting(&mut doc, "display.time_format", "%I:%M %p").unwrap();
+        for (key, format) in [
+            ("display.date_format", "%d %H"),
+            ("display.date_format", "%Q"),
+            ("display.date_format", ""),
+            ("display.time_format", "%H:%M %z"),
+            ("display.time_format", "%Q"),
+        ] {
+            let err = set_setting(&mut doc, key, format).unwrap_err();
+            assert!(
+                err.to_string().contains("not a valid strftime format"),
+                "{key} = {format}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn set_setting_validates_before_changing() {
+        let mut doc = DocumentMut::new();
+        set_setting(&mut doc, "pdf.font_size", "12").unwrap();
+        assert!(set_setting(&mut doc, "pdf.font_size", "100").is_err());
+        assert_eq!(
+            get_setting(&doc, "pdf.font_size").unwrap().as_deref(),
+            Some("12")
+        );
+        assert!(unset_setting(&mut doc, "pdf.font_size").unwrap());
+        assert!(doc.is_empty());
+    }
+
+    #[test]
+    fn set_and_unset_keep_comments() {
+        let mut doc: DocumentMut = "# Personal settings\n\n[week]\nstart = \"monday\" # ISO weeks\n\n# Keep this\n[pdf]\nfont_size = 10\n"
+            .parse()
+            .unwrap();
+        set_setting(&mut doc, "week.start", "sunday").unwrap();
+        set_setting(&mut doc, "rounding.minutes", "15").unwrap();
+        assert!(unset_setting(&mut doc, "pdf.font_size").unwrap());
+        let text = doc.to_string();
+        assert!(text.starts_with("# Personal settings\n"), "{text}");
+        assert!(text.contains("start = \"sunday\" # ISO weeks\n"), "{text}");
+        assert!(text.contains("[rounding]\nminutes = 15\n"), "{text}");
+        assert!(!text.contains("[pdf]"), "{text}");
+    }
+}
@@ -1,60 +1,247 @@
-use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result, bail};
+
+use crate::config::DEFAULT_PROFILE;
 
 const KEYRING_SERVICE: &str = "vibe-clock";
 const KEYRING_USER: &str = "db-passphrase";
+const KEY_ENV: &str = "VIBE_CLOCK_KEY";
+const KEY_FILE_ENV: &str = "VIBE_CLOCK_KEY_FILE";
+const KEY_COMMAND_ENV: &str = "VIBE_CLOCK_KEY_COMMAND";
+
+/// Shown when no source yields a passphrase.
+const RESOLUTION_ORDER: &str = "vibe-clock looks for the passphrase in this order: \
+     1. VIBE_CLOCK_KEY, 2. the file named by VIBE_CLOCK_KEY_FILE, \
+     3. the output of VIBE_CLOCK_KEY_COMMAND, 4. the OS keyring, 5. a terminal prompt";
+
+/// A passphrase and whether it was typed at the prompt.
+pub struct Passphrase {
+    pub value: String,
+    /// Typed by the user; worth storing in the keyring once it has opened the database.
+    pub prompted: bool,
+}
 
 /// Retrieve the database passphrase.
 ///
 /// Priority:
 /// 1. `VIBE_CLOCK_KEY` environment variable (checked first — instant, non-blocking)
-/// 2. OS keyring (via keyring crate — may block on locked keyrings)
-/// 3. Terminal prompt (stores to keyring on first use)
-pub fn get_passphrase() -> Result<String> {
+/// 2. `VIBE_CLOCK_KEY_FILE`, a file holding the passphrase (must not be world-readable)
+/// 3. `VIBE_CLOCK_KEY_COMMAND`, a shell command printing the passphrase
+/// 4. OS keyring entry of `profile` (via keyring crate — may block on locked keyrings)
+/// 5. Terminal prompt, asking twice when `new_database` is set
+///
+/// A configured file or command that fails is an error rather than a fall-through,
+/// so a typo never silently ends in a prompt or a different passphrase.
+pub fn get_passphrase(profile: &str, new_database: bool) -> Result<Passphrase> {
+    let found = |value| {
+        Ok(Passphrase {
+            value,
+            prompted: false,
+        })
+    };
+
     // Try environment variable first (non-blocking)
-    if let Ok(passphrase) = std::env::var("VIBE_CLOCK_KEY") {
-        return Ok(passphrase);
+    if let Ok(passphrase) = std::env::var(KEY_ENV) {
+        return found(passphrase);
+    }
+
+    if let Some(path) = std::env::var_os(KEY_FILE_ENV) {
+        return found(read_key_file(Path::new(&path))?);
+    }
+
+    if let Ok(command) = std::env::var(KEY_COMMAND_ENV) {
+        return found(run_key_command(&command)?);
     }
 
     // Try keyring
-    if let Ok(passphrase) = get_from_keyring() {
-        return Ok(passphrase);
+    if let Ok(passphrase) = get_from_keyring(profile) {
+        return found(passphrase);
     }
 
     // Fall back to terminal prompt
-    let passphrase = prompt_passphrase()?;
-    store_in_keyring(&passphrase).ok();
-    Ok(passphrase)
+    let no_passphrase = || format!("No database passphrase available. {RESOLUTION_ORDER}");
+    if !new_database {
+        let value = prompt_passphrase("Enter database passphrase: ").with_context(no_passphrase)?;
+        return Ok(Passphrase {
+            value,
+            prompted: true,
+        });
+    }
+
+    // A typo here would lock the new database for good, so ask twice
+    let value = prompt_passphrase("Choose a passphrase for the new database: ")
+        .with_context(no_passphrase)?;
+    let confirmation = prompt_passphrase("Repeat the passphrase: ")?;
+    if confirmation != value {
+        bail!("Passphrases do not match; no database was created");
+    }
+    Ok(Passphrase {
+        value,
+        prompted: true,
+    })
+}
+
+/// Keyring entry name of a profile; the default profile keeps the original name.
+fn keyring_user(profile: &str) -> String {
+    if profile == DEFAULT_PROFILE {
+        KEYRING_USER.to_string()
+    } else {
+        format!("{KEYRING_USER}:{profile}")
+    }
 }
 
-fn get_from_keyring() -> Result<String> {
-    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)?;
+fn get_from_keyring(profile: &str) -> Result<String> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, &keyring_user(profile))?;
     let passphrase = entry.get_password()?;
     Ok(passphrase)
 }
 
-fn store_in_keyring(passphrase: &str) -> Result<()> {
-    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)?;
+/// The environment variable supplying the passphrase, if it does not come from the
+/// keyring or a prompt.
+pub fn external_key_source() -> Option<&'static str> {
+    [KEY_ENV, KEY_FILE_ENV, KEY_COMMAND_ENV]
+        .into_iter()
+        .find(|name| std::env::var_os(name).is_some())
+}
+
+/// Strip the line break that files and command output usually end with.
+fn trim_newline(mut passphrase: String) -> String {
+    if passphrase.ends_with('\n') {
+        passphrase.pop();
+        if passphrase.ends_with('\r') {
+            passphrase.pop();
+        }
+    }
+    passphrase
+}
+
+fn read_key_file(path: &Path) -> Result<String> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = std::fs::metadata(path)
+            .with_context(|| format!("Cannot read {KEY_FILE_ENV} {}", path.display()))?
+            .permissions()
+            .mode();
+        if mode & 0o004 != 0 {
+            bail!(
+                "{KEY_FILE_ENV} {} is readable by everyone; restrict it with `chmod 600`",
+                path.display()
+            );
+        }
+    }
+
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Cannot read {KEY_FILE_ENV} {}", path.display()))?;
+    let passphrase = trim_newline(contents);
+    if passphrase.is_empty() {
+        bail!("{KEY_FILE_ENV} {} is empty", path.display());
+    }
+    Ok(passphrase)
+}
+
+/// Run the key command with the terminal's stdin and stderr, so it can prompt
+/// (e.g. for a GPG pin), and read the passphrase from its stdout.
+fn run_key_command(command: &str) -> Result<String> {
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    };
+    let output = cmd
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .stdout(Stdio::piped())
+        .output()
+        .with_context(|| format!("Failed to run {KEY_COMMAND_ENV} '{command}'"))?;
+
+    if !output.status.success() {
+        bail!("{KEY_COMMAND_ENV} '{command}' failed ({})", output.status);
+    }
+    let passphrase = String::from_utf8(output.stdout)
+        .with_context(|| format!("{KEY_COMMAND_ENV} '{command}' printed invalid UTF-8"))
+        .map(trim_newline)?;
+    if passphrase.is_empty() {
+        bail!("{KEY_COMMAND_ENV} '{command}' printed nothing");
+    }
+    Ok(passphrase)
+}
+
+/// Replace the passphrase of `profile` stored in the OS keyring.
+pub fn store_in_keyring(profile: &str, passphrase: &str) -> Result<()> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, &keyring_user(profile))?;
     entry.set_password(passphrase)?;
     Ok(())
 }
 
-fn prompt_passphrase() -> Result<String> {
-    use std::io::{self, BufRead, Write};
+/// Ask for a passphrase on the terminal, showing `prompt` on stderr.
+///
+/// Typing is not echoed when stdin is a terminal; piped input is read line by line.
+pub fn prompt_passphrase(prompt: &str) -> Result<String> {
+    use std::io::{self, BufRead, IsTerminal, Write};
 
-    eprint!("Enter database passphrase: ");
+    eprint!("{prompt}");
     io::stderr().flush()?;
 
-    let stdin = io::stdin();
-    let passphrase = stdin
-        .lock()
-        .lines()
-        .next()
-        .context("No input received")?
-        .context("Failed to read passphrase")?;
+    let passphrase = if io::stdin().is_terminal() {
+        read_hidden()?
+    } else {
+        io::stdin()
+            .lock()
+            .lines()
+            .next()
+            .context("No input received")?
+            .context("Failed to read passphrase")?
+    };
 
     if passphrase.is_empty() {
-        anyhow::bail!("Passphrase cannot be empty");
+        bail!("Passphrase cannot be empty");
     }
 
     Ok(passphrase)
 }
+
+/// Read one line from the terminal in raw mode, so nothing is echoed.
+fn read_hidden() -> Result<String> {
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+    use crossterm::terminal;
+
+    /// Restores the terminal however the read ends.
+    struct RawMode;
+    impl Drop for RawMode {
+        fn drop(&mut self) {
+            let _ = terminal::disable_raw_mode();
+            eprintln!();
+        }
+    }
+
+    terminal::enable_raw_mode().context("Failed to turn off terminal echo")?;
+    let _raw = RawMode;
+
+    let mut passphrase = String::new();
+    loop {
+        let Event::Key(key) = event::read().context("Failed to read passphrase")? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Enter => return Ok(passphrase),
+            KeyCode::Backspace => {
+                passphrase.pop();
+            }
+            KeyCode::Char('c' | 'd') if ctrl => bail!("Passphrase entry cancelled"),
+            KeyCode::Char(c) if !ctrl => passphrase.push(c),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use rusqlite::Connection;
+use rusqlite::backup::Backup;
+
+use super::{Database, SCHEMA_VERSION};
+
+/// Pages copied per backup step; small steps keep other work responsive on large files.
+const PAGES_PER_STEP: std::ffi::c_int = 256;
+
+/// Open an encrypted database file without migrating it.
+fn open_keyed(path: &Path, passphrase: &str) -> Result<Connection> {
+    let conn = Connection::open(path)
+        .with_context(|| format!("Failed to open database: {}", path.display()))?;
+    conn.pragma_update(None, "key", passphrase)?;
+    Ok(conn)
+}
+
+impl Database {
+    /// Copy the live database to `path` with SQLite's online backup API.
+    ///
+    /// The copy is encrypted with `passphrase`, which must be the one the live database uses.
+    pub fn backup_to(&self, path: &Path, passphrase: &str) -> Result<()> {
+        let mut dest = open_keyed(path, passphrase)?;
+        Backup::new(&self.conn, &mut dest)?
+            .run_to_completion(PAGES_PER_STEP, Duration::ZERO, None)
+            .with_context(|| format!("Failed to write backup: {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Schema version of a backup file, which also proves `passphrase` opens it.
+    pub fn backup_schema_version(path: &Path, passphrase: &str) -> Result<i64> {
+        let conn = open_keyed(path, passphrase)?;
+        conn.query_row("SELECT MAX(version) FROM schema_version", [], |row| {
+            row.get(0)
+        })
+        .with_context(|| {
+            format!(
+                "Cannot read backup {}: wrong passphrase or not a vibe-clock database",
+                path.display()
+            )
+        })
+    }
+
+    /// Re-encrypt the backup at `path` from `passphrase` to `new_passphrase` in place.
+    ///
+    /// Fails without touching the file if `passphrase` does not open it.
+    pub fn rekey_backup(path: &Path, passphrase: &str, new_passphrase: &str) -> Result<()> {
+        Self::backup_schema_version(path, passphrase)?;
+        let conn = open_keyed(path, passphrase)?;
+        conn.pragma_update(None, "rekey", new_passphrase)
+            .with_context(|| format!("Failed to re-encrypt backup: {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Replace the contents of the live database with the backup at `path`,
+    /// then upgrade it if the backup was made by an older version.
+    pub fn restore_from(&mut self, path: &Path, passphrase: &str) -> Result<()> {
+        let version = Self::backup_schema_version(path, passphrase)?;
+        if version > SCHEMA_VERSION {
+            bail!(
+                "Backup schema version {version} is newer than this vibe-clock supports \
+                 (version {SCHEMA_VERSION})"
+            );
+        }
+        let source = open_keyed(path, passphrase)?;
+        Backup::new(&source, &mut self.conn)?
+            .run_to_completion(PAGES_PER_STEP, Duration::ZERO, None)
+            .with_context(|| format!("Failed to restore backup: {}", path.display()))?;
+        self.migrate()
+    }
+}
@@ -0,0 +1,173 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use rusqlite::params;
+use rusqlite::types::Value as SqlValue;
+
+use super::Database;
+use super::dump::row_object;
+
+/// Columns holding `%Y-%m-%dT%H:%M:%S` datetimes, as `(table, column)`.
+pub const DATETIME_COLUMNS: [(&str, &str); 17] = [
+    ("project", "created_at"),
+    ("project", "updated_at"),
+    ("project", "deleted_at"),
+    ("task_entry", "start_time"),
+    ("task_entry", "end_time"),
+    ("task_entry", "created_at"),
+    ("task_entry", "updated_at"),
+    ("task_entry", "deleted_at"),
+    ("clock_state", "start_time"),
+    ("task_template", "created_at"),
+    ("task_template", "updated_at"),
+    ("task_entry_history", "changed_at"),
+    ("period_lock", "updated_at"),
+    ("period_lock_history", "changed_at"),
+    ("operation_log", "created_at"),
+    ("import_key", "imported_at"),
+    ("quarantine", "quarantined_at"),
+];
+
+/// Columns holding `%Y-%m-%d` dates, as `(table, column)`.
+pub const DATE_COLUMNS: [(&str, &str); 3] = [
+    ("period_lock", "locked_to"),
+    ("period_lock_history", "locked_to"),
+    ("period_lock_history", "previous_locked_to"),
+];
+
+/// A row whose foreign key points at a missing parent.
+pub struct ForeignKeyViolation {
+    pub table: String,
+    pub rowid: i64,
+    pub parent: String,
+}
+
+/// A task entry's stored times, unparsed.
+pub struct RawTaskTimes {
+    pub id: i64,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    pub duration_min: i64,
+}
+
+impl Database {
+    /// Problems reported by `PRAGMA integrity_check`; empty when the file is sound.
+    pub fn integrity_problems(&self) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare("PRAGMA integrity_check")?;
+        let messages = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(messages.into_iter().filter(|m| m != "ok").collect())
+    }
+
+    /// Rows reported by `PRAGMA foreign_key_check`.
+    pub fn foreign_key_violations(&self) -> Result<Vec<ForeignKeyViolation>> {
+        let mut stmt = self.conn.prepare("PRAGMA foreign_key_check")?;
+        let violations = stmt
+            .query_map([], |row| {
+                Ok(ForeignKeyViolation {
+                    table: row.get(0)?,
+                    rowid: row.get::<_, Option<i64>>(1)?.unwrap_or_default(),
+                    parent: row.get(2)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(violations)
+    }
+
+    /// Every non-null value of `table.column` by rowid, rendered as text.
+    pub fn column_values(&self, table: &str, column: &str) -> Result<Vec<(i64, String)>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT rowid, {column} FROM {table} WHERE {column} IS NOT NULL ORDER BY rowid"
+        ))?;
+        let values = stmt
+            .query_map([], |row| {
+                let text = match row.get::<_, SqlValue>(1)? {
+                    SqlValue::Text(s) => s,
+                    SqlValue::Integer(n) => n.to_string(),
+                    SqlValue::Real(f) => f.to_string(),
+                    SqlValue::Blob(_) => "<binary>".to_string(),
+                    SqlValue::Null => String::new(),
+                };
+                Ok((row.get(0)?, text))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(values)
+    }
+
+    /// Start, end and duration of every task entry, trashed ones included.
+    pub fn raw_task_times(&self) -> Result<Vec<RawTaskTimes>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, CAST(start_time AS TEXT), CAST(end_time AS TEXT), duration_min \
+             FROM task_entry ORDER BY id",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(RawTaskTimes {
+                    id: row.get(0)?,
+                    start_time: row.get(1)?,
+                    end_time: row.get(2)?,
+                    duration_min: row.get(3)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows)
+    }
+
+    /// Overwrite one datetime column of a row with `now`.
+    pub fn reset_timestamp(
+        &self,
+        table: &str,
+        column: &str,
+        rowid: i64,
+        now: NaiveDateTime,
+    ) -> Result<()> {
+        self.conn.execute(
+            &format!("UPDATE {table} SET {column} = ?1 WHERE rowid = ?2"),
+            params![now.format("%Y-%m-%dT%H:%M:%S").to_string(), rowid],
+        )?;
+        Ok(())
+    }
+
+    /// Store a corrected duration without touching `updated_at`.
+    pub fn set_task_duration(&self, id: i64, duration_min: i64) -> Result<()> {
+        self.conn.execute(
+            "UPDATE task_entry SET duration_min = ?1 WHERE id = ?2",
+            params![duration_min, id],
+        )?;
+        Ok(())
+    }
+
+    /// Move a row into the `quarantine` table as JSON, so it stops breaking reads
+    /// but can still be inspected. Returns false if the row no longer exists.
+    pub fn quarantine_row(
+        &self,
+        table: &str,
+        rowid: i64,
+        reason: &str,
+        now: NaiveDateTime,
+    ) -> Result<bool> {
+        let columns = self.table_columns(table)?;
+        let mut stmt = self
+            .conn
+            .prepare(&format!("SELECT * FROM {table} WHERE rowid = ?1"))?;
+        let mut rows = stmt.query([rowid])?;
+        let Some(row) = rows.next()? else {
+            return Ok(false);
+        };
+        let data = serde_json::Value::Object(row_object(table, &columns, row)?);
+
+        self.conn.execute(
+            "INSERT INTO quarantine (source_table, row_data, reason, quarantined_at) \
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                table,
+                data.to_string(),
+                reason,
+                now.format("%Y-%m-%dT%H:%M:%S").to_string()
+            ],
+        )?;
+        self.conn
+            .execute(&format!("DELETE FROM {table} WHERE rowid = ?1"), [rowid])?;
+        Ok(true)
+    }
+}
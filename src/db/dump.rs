@@ -0,0 +1,117 @@
+use anyhow::{Result, bail};
+use rusqlite::types::Value as SqlValue;
+use serde_json::{Map, Number, Value};
+
+use super::Database;
+
+/// Tables in a full dump, parents before children so a restore satisfies foreign keys.
+pub const DUMP_TABLES: [&str; 10] = [
+    "project",
+    "task_entry",
+    "clock_state",
+    "task_template",
+    "task_entry_history",
+    "period_lock",
+    "period_lock_history",
+    "operation_log",
+    "import_key",
+    "quarantine",
+];
+
+/// A table row keyed by column name.
+pub type Row = Map<String, Value>;
+
+impl Database {
+    /// Whether none of the dumped tables has any rows.
+    pub fn is_empty(&self) -> Result<bool> {
+        for table in DUMP_TABLES {
+            let count: i64 =
+                self.conn
+                    .query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+                        row.get(0)
+                    })?;
+            if count > 0 {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    pub(super) fn table_columns(&self, table: &str) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!("SELECT name FROM pragma_table_info('{table}')"))?;
+        let names = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(names)
+    }
+
+    /// Every row of `table`, including trashed ones, in primary key order.
+    pub fn dump_table(&self, table: &str) -> Result<Vec<Row>> {
+        let columns = self.table_columns(table)?;
+        let mut stmt = self
+            .conn
+            .prepare(&format!("SELECT * FROM {table} ORDER BY rowid"))?;
+        let mut rows = stmt.query([])?;
+        let mut dumped = Vec::new();
+        while let Some(row) = rows.next()? {
+            dumped.push(row_object(table, &columns, row)?);
+        }
+        Ok(dumped)
+    }
+
+    /// Insert dumped rows into `table` as-is, keeping their IDs.
+    pub fn restore_rows(&self, table: &str, rows: &[Row]) -> Result<()> {
+        let known = self.table_columns(table)?;
+        for row in rows {
+            if let Some(unknown) = row.keys().find(|k| !known.contains(k)) {
+                bail!("Unknown column '{unknown}' in table '{table}'");
+            }
+            let columns: Vec<&String> = row.keys().collect();
+            let placeholders: Vec<String> = (1..=columns.len()).map(|i| format!("?{i}")).collect();
+            let values = row
+                .values()
+                .map(|v| match v {
+                    Value::Null => Ok(SqlValue::Null),
+                    Value::Bool(b) => Ok(SqlValue::Integer(i64::from(*b))),
+                    Value::Number(n) => Ok(n
+                        .as_i64()
+                        .map(SqlValue::Integer)
+                        .unwrap_or_else(|| SqlValue::Real(n.as_f64().unwrap_or_default()))),
+                    Value::String(s) => Ok(SqlValue::Text(s.clone())),
+                    _ => bail!("Unsupported value in table '{table}': {v}"),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            self.conn.execute(
+                &format!(
+                    "INSERT INTO {table} ({}) VALUES ({})",
+                    columns
+                        .iter()
+                        .map(|c| c.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    placeholders.join(", ")
+                ),
+                rusqlite::params_from_iter(values),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Convert a `SELECT *` row of `table` into a JSON object.
+pub(super) fn row_object(table: &str, columns: &[String], row: &rusqlite::Row) -> Result<Row> {
+    let mut object = Map::new();
+    for (i, column) in columns.iter().enumerate() {
+        let value = match row.get::<_, SqlValue>(i)? {
+            SqlValue::Null => Value::Null,
+            SqlValue::Integer(n) => Value::from(n),
+            SqlValue::Real(f) => Number::from_f64(f).map_or(Value::Null, Value::Number),
+            SqlValue::Text(s) => Value::String(s),
+            SqlValue::Blob(_) => bail!("Column '{column}' of '{table}' holds binary data"),
+        };
+        object.insert(column.clone(), value);
+    }
+    Ok(object)
+}
@@ -0,0 +1,66 @@
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use rusqlite::Connection;
+
+use super::{Database, SCHEMA_VERSION};
+
+impl Database {
+    /// Write an encrypted copy of the plaintext vibe-clock database at `plain` to `output`.
+    ///
+    /// The copy is made with `sqlcipher_export`, so `plain` is only read. Files that are
+    /// not plaintext vibe-clock databases, or come from a newer schema, are refused.
+    pub fn encrypt_plaintext(plain: &Path, output: &Path, passphrase: &str) -> Result<()> {
+        let conn = Connection::open(plain)
+            .with_context(|| format!("Failed to open database: {}", plain.display()))?;
+        let has_version: bool = conn
+            .query_row(
+                "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_version')",
+                [],
+                |row| row.get(0),
+            )
+            .with_context(|| {
+                format!(
+                    "{} is not a plaintext SQLite database (is it already encrypted?)",
+                    plain.display()
+                )
+            })?;
+        if !has_version {
+            bail!("{} is not a vibe-clock database", plain.display());
+        }
+        let version: i64 = conn.query_row(
+            "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+            [],
+            |row| row.get(0),
+        )?;
+        if version > SCHEMA_VERSION {
+            bail!(
+                "Database schema version {version} is newer than this vibe-clock supports \
+                 (version {SCHEMA_VERSION})"
+            );
+        }
+
+        conn.execute(
+            "ATTACH DATABASE ?1 AS encrypted KEY ?2",
+            (output.to_string_lossy(), passphrase),
+        )?;
+        conn.query_row("SELECT sqlcipher_export('encrypted')", [], |_| Ok(()))
+            .with_context(|| format!("Failed to write {}", output.display()))?;
+        conn.execute("DETACH DATABASE encrypted", [])?;
+        Ok(())
+    }
+
+    /// Write an unencrypted copy of the live database to `output`.
+    pub fn export_plaintext(&self, output: &Path) -> Result<()> {
+        self.conn.execute(
+            "ATTACH DATABASE ?1 AS plaintext KEY ''",
+            [output.to_string_lossy()],
+        )?;
+        let exported = self
+            .conn
+            .query_row("SELECT sqlcipher_export('plaintext')", [], |_| Ok(()))
+            .with_context(|| format!("Failed to write {}", output.display()));
+        self.conn.execute("DETACH DATABASE plaintext", [])?;
+        exported
+    }
+}
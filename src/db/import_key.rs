@@ -0,0 +1,40 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use rusqlite::params;
+
+use super::Database;
+
+impl Database {
+    /// The live task entry previously imported under `key`, if any.
+    ///
+    /// Keys whose entry has since been trashed don't count, so an undone import can be re-run.
+    pub fn find_imported_task_id(&self, key: &str) -> Result<Option<i64>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT k.task_entry_id FROM import_key k \
+             JOIN task_entry t ON t.id = k.task_entry_id \
+             WHERE k.key = ?1 AND t.deleted_at IS NULL",
+        )?;
+        let mut rows = stmt.query_map(params![key], |row| row.get::<_, i64>(0))?;
+        match rows.next() {
+            Some(r) => Ok(Some(r?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Remember that the record identified by `key` was imported as `task_entry_id`.
+    ///
+    /// Replaces the key of an entry that has since been trashed.
+    pub fn insert_import_key(
+        &self,
+        key: &str,
+        task_entry_id: i64,
+        now: NaiveDateTime,
+    ) -> Result<()> {
+        let now_str = now.format("%Y-%m-%dT%H:%M:%S").to_string();
+        self.conn.execute(
+            "INSERT OR REPLACE INTO import_key (key, task_entry_id, imported_at) VALUES (?1, ?2, ?3)",
+            params![key, task_entry_id, now_str],
+        )?;
+        Ok(())
+    }
+}
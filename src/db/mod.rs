@@ -1,16 +1,30 @@
+mod backup;
+mod check;
 mod clock_state;
+mod dump;
+mod encryption;
+mod import_key;
+mod operation_log;
+mod period_lock;
 mod project;
 mod task_entry;
+mod task_history;
+mod task_template;
 
-use std::path::Path;
+use std::fmt;
+use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use chrono::NaiveDateTime;
 use rusqlite::Connection;
 
+pub use check::{DATE_COLUMNS, DATETIME_COLUMNS, ForeignKeyViolation, RawTaskTimes};
+pub use dump::{DUMP_TABLES, Row};
+
 pub(crate) fn parse_datetime(s: &str) -> Result<NaiveDateTime> {
-    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S")
-        .with_context(|| format!("Invalid datetime in database: '{s}'"))
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").with_context(|| {
+        format!("Invalid datetime in database: '{s}'. Run `vibe-clock db check` to repair it.")
+    })
 }
 
 pub(crate) fn parse_optional_datetime(s: Option<&str>) -> Result<Option<NaiveDateTime>> {
@@ -24,6 +38,22 @@ pub struct Database {
     pub conn: Connection,
 }
 
+/// The passphrase does not decrypt the database file.
+#[derive(Debug)]
+pub struct WrongPassphrase(pub PathBuf);
+
+impl fmt::Display for WrongPassphrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Wrong passphrase for {} (or the file is not a vibe-clock database).",
+            self.0.display()
+        )
+    }
+}
+
+impl std::error::Error for WrongPassphrase {}
+
 impl Database {
     /// Open an encrypted database at the given path with the provided passphrase.
     pub fn open(path: &Path, passphrase: &str) -> Result<Self> {
@@ -32,6 +62,13 @@ impl Database {
 
         // Apply encryption key
         conn.pragma_update(None, "key", passphrase)?;
+        // SQLCipher only notices a wrong key on the first read
+        if let Err(err) = conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |_| Ok(())) {
+            if err.sqlite_error_code() == Some(rusqlite::ErrorCode::NotADatabase) {
+                return Err(WrongPassphrase(path.to_path_buf()).into());
+            }
+            return Err(err.into());
+        }
 
         Self::configure(&conn)?;
         let db = Self { conn };
@@ -59,21 +96,83 @@ impl Database {
         Ok(())
     }
 
+    /// Bring the schema up to [`SCHEMA_VERSION`], applying every pending migration in
+    /// one transaction. A database written by a newer binary is refused untouched.
     fn migrate(&self) -> Result<()> {
-        self.conn
-            .execute_batch(SCHEMA_V1)
+        let current = self
+            .schema_version()
             .context("Failed to initialize database schema")?;
+        if current > SCHEMA_VERSION {
+            bail!(
+                "Database schema version {current} is newer than this vibe-clock supports \
+                 (version {SCHEMA_VERSION}). Upgrade vibe-clock to open it."
+            );
+        }
+
+        let tx = self.conn.unchecked_transaction()?;
+        for (version, sql) in (1..).zip(MIGRATIONS).skip(usize::try_from(current)?) {
+            tx.execute_batch(sql)
+                .with_context(|| format!("Failed to apply schema version {version}"))?;
+            tx.execute(
+                "INSERT OR IGNORE INTO schema_version (version) VALUES (?1)",
+                [version],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// The newest schema version recorded in the database, or 0 for a new database.
+    pub fn schema_version(&self) -> Result<i64> {
+        let has_table: bool = self.conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_version')",
+            [],
+            |row| row.get(0),
+        )?;
+        if !has_table {
+            return Ok(0);
+        }
+        Ok(self.conn.query_row(
+            "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+            [],
+            |row| row.get(0),
+        )?)
+    }
+
+    /// Re-encrypt the database with a new passphrase.
+    ///
+    /// SQLCipher rewrites every page in one transaction, so a failed rekey leaves the
+    /// file readable with the old passphrase.
+    pub fn rekey(&self, new_passphrase: &str) -> Result<()> {
+        if new_passphrase.is_empty() {
+            bail!("Passphrase cannot be empty");
+        }
+        self.conn
+            .pragma_update(None, "rekey", new_passphrase)
+            .context("Failed to change the database passphrase")?;
+        self.conn
+            .query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| {
+                row.get::<_, i64>(0)
+            })
+            .context("Database is unreadable after changing the passphrase")?;
         Ok(())
     }
 }
 
+/// Schema migrations in order: `MIGRATIONS[n - 1]` upgrades a database from version
+/// `n - 1` to `n`. Released migrations must never change; add a new one instead.
+const MIGRATIONS: [&str; 8] = [
+    SCHEMA_V1, SCHEMA_V2, SCHEMA_V3, SCHEMA_V4, SCHEMA_V5, SCHEMA_V6, SCHEMA_V7, SCHEMA_V8,
+];
+
+/// The schema version this binary creates and understands.
+pub const SCHEMA_VERSION: i64 = MIGRATIONS.len() as i64;
+
 const SCHEMA_V1: &str = "
 CREATE TABLE IF NOT EXISTS schema_version (
     version INTEGER PRIMARY KEY
 );
 
-INSERT OR IGNORE INTO schema_version (version) VALUES (1);
-
 CREATE TABLE IF NOT EXISTS project (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
     name TEXT NOT NULL UNIQUE,
@@ -104,3 +203,81 @@ CREATE TABLE IF NOT EXISTS clock_state (
 CREATE INDEX IF NOT EXISTS idx_task_entry_project_id ON task_entry(project_id);
 CREATE INDEX IF NOT EXISTS idx_task_entry_start_time ON task_entry(start_time);
 ";
+
+const SCHEMA_V2: &str = "
+CREATE TABLE IF NOT EXISTS task_template (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    project_id INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    duration_min INTEGER CHECK(duration_min IS NULL OR duration_min > 0),
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES project(id) ON DELETE CASCADE
+);
+";
+
+const SCHEMA_V3: &str = "
+CREATE TABLE IF NOT EXISTS operation_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    undone INTEGER NOT NULL DEFAULT 0
+);
+";
+
+const SCHEMA_V4: &str = "
+ALTER TABLE project ADD COLUMN deleted_at TEXT;
+ALTER TABLE task_entry ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_task_entry_deleted_at ON task_entry(deleted_at);
+";
+
+const SCHEMA_V5: &str = "
+CREATE TABLE IF NOT EXISTS task_entry_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_entry_id INTEGER NOT NULL,
+    changed_at TEXT NOT NULL,
+    action TEXT NOT NULL,
+    field TEXT,
+    old_value TEXT,
+    new_value TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_entry_history_task ON task_entry_history(task_entry_id);
+";
+
+const SCHEMA_V6: &str = "
+CREATE TABLE IF NOT EXISTS period_lock (
+    id INTEGER PRIMARY KEY CHECK(id = 1),
+    locked_to TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS period_lock_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    action TEXT NOT NULL,
+    locked_to TEXT,
+    previous_locked_to TEXT,
+    changed_at TEXT NOT NULL
+);
+";
+
+const SCHEMA_V7: &str = "
+CREATE TABLE IF NOT EXISTS import_key (
+    key TEXT PRIMARY KEY,
+    task_entry_id INTEGER NOT NULL REFERENCES task_entry(id) ON DELETE CASCADE,
+    imported_at TEXT NOT NULL
+);
+";
+
+const SCHEMA_V8: &str = "
+CREATE TABLE IF NOT EXISTS quarantine (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    source_table TEXT NOT NULL,
+    row_data TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    quarantined_at TEXT NOT NULL
+);
+";
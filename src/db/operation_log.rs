@@ -0,0 +1,63 @@
+use anyhow::{Context, Result};
+use chrono::NaiveDateTime;
+use rusqlite::params;
+
+use crate::models::Operation;
+
+use super::Database;
+
+impl Database {
+    /// Append an operation to the log.
+    ///
+    /// Recording a new operation discards any undone operations, so they can no
+    /// longer be redone.
+    pub fn insert_operation(&self, op: &Operation, now: NaiveDateTime) -> Result<i64> {
+        let payload = serde_json::to_string(op).context("Failed to serialize operation")?;
+        let now_str = now.format("%Y-%m-%dT%H:%M:%S").to_string();
+        self.conn
+            .execute("DELETE FROM operation_log WHERE undone = 1", [])?;
+        self.conn.execute(
+            "INSERT INTO operation_log (kind, payload, created_at) VALUES (?1, ?2, ?3)",
+            params![op.kind(), payload, now_str],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// The most recent operation that has not been undone.
+    pub fn last_applied_operation(&self) -> Result<Option<(i64, Operation)>> {
+        self.find_operation(
+            "SELECT id, payload FROM operation_log WHERE undone = 0 ORDER BY id DESC LIMIT 1",
+        )
+    }
+
+    /// The oldest undone operation, i.e. the next one to redo.
+    pub fn next_undone_operation(&self) -> Result<Option<(i64, Operation)>> {
+        self.find_operation(
+            "SELECT id, payload FROM operation_log WHERE undone = 1 ORDER BY id ASC LIMIT 1",
+        )
+    }
+
+    pub fn set_operation_undone(&self, id: i64, undone: bool) -> Result<()> {
+        self.conn.execute(
+            "UPDATE operation_log SET undone = ?1 WHERE id = ?2",
+            params![undone, id],
+        )?;
+        Ok(())
+    }
+
+    fn find_operation(&self, sql: &str) -> Result<Option<(i64, Operation)>> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let mut rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?;
+        match rows.next() {
+            Some(r) => {
+                let (id, payload) = r?;
+                let op = serde_json::from_str(&payload)
+                    .with_context(|| format!("Invalid operation log entry {id}"))?;
+                Ok(Some((id, op)))
+            }
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, NaiveDateTime};
+use rusqlite::params;
+
+use crate::models::PeriodLockEvent;
+
+use super::{Database, parse_datetime};
+
+fn parse_date(s: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .with_context(|| format!("Invalid date in database: '{s}'"))
+}
+
+fn parse_optional_date(s: Option<&str>) -> Result<Option<NaiveDate>> {
+    s.map(parse_date).transpose()
+}
+
+impl Database {
+    /// The last day of the locked period, if any.
+    pub fn get_period_lock(&self) -> Result<Option<NaiveDate>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT locked_to FROM period_lock WHERE id = 1")?;
+        let mut rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        match rows.next() {
+            Some(r) => Ok(Some(parse_date(&r?)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Lock every day up to and including `locked_to`, recording the change.
+    pub fn set_period_lock(&self, locked_to: NaiveDate, now: NaiveDateTime) -> Result<()> {
+        let previous = self.get_period_lock()?;
+        let date_str = locked_to.format("%Y-%m-%d").to_string();
+        let now_str = now.format("%Y-%m-%dT%H:%M:%S").to_string();
+        self.conn.execute(
+            "INSERT INTO period_lock (id, locked_to, updated_at) VALUES (1, ?1, ?2) \
+             ON CONFLICT(id) DO UPDATE SET locked_to = excluded.locked_to, updated_at = excluded.updated_at",
+            params![date_str, now_str],
+        )?;
+        self.insert_period_lock_event("lock", Some(locked_to), previous, now)
+    }
+
+    /// Remove the lock entirely, recording the change.
+    pub fn clear_period_lock(&self, now: NaiveDateTime) -> Result<()> {
+        let previous = self.get_period_lock()?;
+        self.conn
+            .execute("DELETE FROM period_lock WHERE id = 1", [])?;
+        self.insert_period_lock_event("unlock", None, previous, now)
+    }
+
+    fn insert_period_lock_event(
+        &self,
+        action: &str,
+        locked_to: Option<NaiveDate>,
+        previous_locked_to: Option<NaiveDate>,
+        now: NaiveDateTime,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO period_lock_history (action, locked_to, previous_locked_to, changed_at) \
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                action,
+                locked_to.map(|d| d.format("%Y-%m-%d").to_string()),
+                previous_locked_to.map(|d| d.format("%Y-%m-%d").to_string()),
+                now.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn list_period_lock_history(&self) -> Result<Vec<PeriodLockEvent>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, action, locked_to, previous_locked_to, changed_at \
+             FROM period_lock_history ORDER BY id",
+        )?;
+        let events = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, String>(4)?,
+                ))
+            })?
+            .map(|r| {
+                let (id, action, locked_to, previous_locked_to, changed_at) = r?;
+                Ok(PeriodLockEvent {
+                    id,
+                    action,
+                    locked_to: parse_optional_date(locked_to.as_deref())?,
+                    previous_locked_to: parse_optional_date(previous_locked_to.as_deref())?,
+                    changed_at: parse_datetime(&changed_at)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(events)
+    }
+}
@@ -22,10 +22,24 @@ impl Database {
         })
     }
 
+    /// Re-insert a previously deleted project, preserving its ID and timestamps.
+    pub fn restore_project(&self, project: &Project) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO project (id, name, created_at, updated_at) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                project.id,
+                project.name,
+                project.created_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                project.updated_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            ],
+        )?;
+        Ok(())
+    }
+
     pub fn list_projects(&self) -> Result<Vec<Project>> {
         let mut stmt = self
             .conn
-            .prepare("SELECT id, name, created_at, updated_at FROM project ORDER BY name")?;
+            .prepare("SELECT id, name, created_at, updated_at FROM project WHERE deleted_at IS NULL ORDER BY name")?;
         let projects = stmt
             .query_map([], |row| {
                 Ok((
@@ -51,7 +65,9 @@ impl Database {
     pub fn find_project_by_id(&self, id: i64) -> Result<Option<Project>> {
         let mut stmt = self
             .conn
-            .prepare("SELECT id, name, created_at, updated_at FROM project WHERE id = ?1")?;
+            .prepare(
+            "SELECT id, name, created_at, updated_at FROM project WHERE id = ?1 AND deleted_at IS NULL",
+        )?;
         let mut rows = stmt.query_map(params![id], |row| {
             Ok((
                 row.get::<_, i64>(0)?,
@@ -77,7 +93,9 @@ impl Database {
     pub fn find_project_by_name(&self, name: &str) -> Result<Option<Project>> {
         let mut stmt = self
             .conn
-            .prepare("SELECT id, name, created_at, updated_at FROM project WHERE name = ?1")?;
+            .prepare(
+            "SELECT id, name, created_at, updated_at FROM project WHERE name = ?1 AND deleted_at IS NULL",
+        )?;
         let mut rows = stmt.query_map(params![name], |row| {
             Ok((
                 row.get::<_, i64>(0)?,
@@ -103,22 +121,106 @@ impl Database {
     pub fn update_project_name(&self, id: i64, new_name: &str, now: NaiveDateTime) -> Result<bool> {
         let now_str = now.format("%Y-%m-%dT%H:%M:%S").to_string();
         let rows = self.conn.execute(
-            "UPDATE project SET name = ?1, updated_at = ?2 WHERE id = ?3",
+            "UPDATE project SET name = ?1, updated_at = ?2 WHERE id = ?3 AND deleted_at IS NULL",
             params![new_name, now_str, id],
         )?;
         Ok(rows > 0)
     }
 
-    pub fn delete_project(&self, id: i64) -> Result<bool> {
-        let rows = self
-            .conn
-            .execute("DELETE FROM project WHERE id = ?1", params![id])?;
+    /// Move a project to the trash. Its tasks are hidden along with it.
+    pub fn delete_project(&self, id: i64, now: NaiveDateTime) -> Result<bool> {
+        let now_str = now.format("%Y-%m-%dT%H:%M:%S").to_string();
+        let rows = self.conn.execute(
+            "UPDATE project SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+            params![now_str, id],
+        )?;
+        if rows > 0 {
+            self.record_project_tasks(id, "delete", true, now)?;
+        }
         Ok(rows > 0)
     }
 
+    /// Take a project out of the trash.
+    pub fn undelete_project(&self, id: i64, now: NaiveDateTime) -> Result<bool> {
+        let rows = self.conn.execute(
+            "UPDATE project SET deleted_at = NULL WHERE id = ?1 AND deleted_at IS NOT NULL",
+            params![id],
+        )?;
+        if rows > 0 {
+            self.record_project_tasks(id, "restore", true, now)?;
+        }
+        Ok(rows > 0)
+    }
+
+    /// Permanently remove a trashed project together with its tasks.
+    pub fn purge_project(&self, id: i64, now: NaiveDateTime) -> Result<bool> {
+        let trashed = self.conn.query_row(
+            "SELECT COUNT(*) FROM project WHERE id = ?1 AND deleted_at IS NOT NULL",
+            params![id],
+            |row| row.get::<_, i64>(0),
+        )? > 0;
+        if !trashed {
+            return Ok(false);
+        }
+        self.record_project_tasks(id, "purge", false, now)?;
+        self.conn
+            .execute("DELETE FROM project WHERE id = ?1", params![id])?;
+        Ok(true)
+    }
+
+    /// List trashed projects with the time they were deleted, most recent first.
+    pub fn list_deleted_projects(&self) -> Result<Vec<(Project, NaiveDateTime)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, created_at, updated_at, deleted_at FROM project \
+             WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC, id DESC",
+        )?;
+        let projects = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                ))
+            })?
+            .map(|r| {
+                let (id, name, created_at, updated_at, deleted_at) = r?;
+                Ok((
+                    Project {
+                        id,
+                        name,
+                        created_at: parse_datetime(&created_at)?,
+                        updated_at: parse_datetime(&updated_at)?,
+                    },
+                    parse_datetime(&deleted_at)?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(projects)
+    }
+
+    /// Find a trashed project by ID.
+    pub fn find_deleted_project_by_id(&self, id: i64) -> Result<Option<Project>> {
+        Ok(self
+            .list_deleted_projects()?
+            .into_iter()
+            .map(|(p, _)| p)
+            .find(|p| p.id == id))
+    }
+
+    /// Find a trashed project by name. Trashed projects keep their name reserved.
+    pub fn find_deleted_project_by_name(&self, name: &str) -> Result<Option<Project>> {
+        Ok(self
+            .list_deleted_projects()?
+            .into_iter()
+            .map(|(p, _)| p)
+            .find(|p| p.name == name))
+    }
+
     pub fn count_tasks_for_project(&self, project_id: i64) -> Result<i64> {
         let count: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM task_entry WHERE project_id = ?1",
+            "SELECT COUNT(*) FROM task_entry WHERE project_id = ?1 AND deleted_at IS NULL",
             params![project_id],
             |row| row.get(0),
         )?;
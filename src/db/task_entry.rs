@@ -6,6 +6,11 @@ use crate::models::TaskEntry;
 
 use super::{Database, parse_datetime, parse_optional_datetime};
 
+/// Restricts a `task_entry` query to entries that are neither trashed themselves
+/// nor belong to a trashed project.
+const VISIBLE: &str = "task_entry.deleted_at IS NULL AND NOT EXISTS \
+     (SELECT 1 FROM project p WHERE p.id = task_entry.project_id AND p.deleted_at IS NOT NULL)";
+
 impl Database {
     pub fn insert_task_entry(
         &self,
@@ -26,7 +31,7 @@ impl Database {
             params![project_id, description, start_str, end_str, duration_min, now_str, now_str],
         )?;
         let id = self.conn.last_insert_rowid();
-        Ok(TaskEntry {
+        let task = TaskEntry {
             id,
             project_id,
             description: description.to_string(),
@@ -35,14 +40,36 @@ impl Database {
             duration_min,
             created_at: now,
             updated_at: now,
-        })
+        };
+        self.record_task_created(&task, now)?;
+        Ok(task)
+    }
+
+    /// Re-insert a previously deleted task entry, preserving its ID and timestamps.
+    pub fn restore_task_entry(&self, task: &TaskEntry, now: NaiveDateTime) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO task_entry (id, project_id, description, start_time, end_time, duration_min, created_at, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                task.id,
+                task.project_id,
+                task.description,
+                task.start_time.map(|t| t.format("%Y-%m-%dT%H:%M:%S").to_string()),
+                task.end_time.map(|t| t.format("%Y-%m-%dT%H:%M:%S").to_string()),
+                task.duration_min,
+                task.created_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                task.updated_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            ],
+        )?;
+        self.insert_task_history(task.id, "restore", None, None, None, now)?;
+        Ok(())
     }
 
     pub fn find_task_entry_by_id(&self, id: i64) -> Result<Option<TaskEntry>> {
-        let mut stmt = self.conn.prepare(
+        let mut stmt = self.conn.prepare(&format!(
             "SELECT id, project_id, description, start_time, end_time, duration_min, created_at, updated_at \
-             FROM task_entry WHERE id = ?1",
-        )?;
+             FROM task_entry WHERE id = ?1 AND {VISIBLE}"
+        ))?;
         let mut rows = stmt.query_map(params![id], |row| {
             Ok((
                 row.get::<_, i64>(0)?,
@@ -84,12 +111,12 @@ impl Database {
 
     pub fn list_tasks_for_date(&self, date: &str) -> Result<Vec<TaskEntry>> {
         // Match tasks where start_time date matches, or created_at date matches (for duration-only entries)
-        let mut stmt = self.conn.prepare(
+        let mut stmt = self.conn.prepare(&format!(
             "SELECT id, project_id, description, start_time, end_time, duration_min, created_at, updated_at \
              FROM task_entry \
-             WHERE substr(COALESCE(start_time, created_at), 1, 10) = ?1 \
-             ORDER BY COALESCE(start_time, created_at)",
-        )?;
+             WHERE substr(COALESCE(start_time, created_at), 1, 10) = ?1 AND {VISIBLE} \
+             ORDER BY COALESCE(start_time, created_at)"
+        ))?;
         let tasks = stmt
             .query_map(params![date], |row| {
                 Ok((
@@ -129,17 +156,61 @@ impl Database {
         Ok(tasks)
     }
 
+    pub fn list_tasks_for_project(&self, project_id: i64) -> Result<Vec<TaskEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, project_id, description, start_time, end_time, duration_min, created_at, updated_at \
+             FROM task_entry WHERE project_id = ?1 AND deleted_at IS NULL ORDER BY id",
+        )?;
+        let tasks = stmt
+            .query_map(params![project_id], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, i64>(5)?,
+                    row.get::<_, String>(6)?,
+                    row.get::<_, String>(7)?,
+                ))
+            })?
+            .map(|r| {
+                let (
+                    id,
+                    project_id,
+                    description,
+                    start_time,
+                    end_time,
+                    duration_min,
+                    created_at,
+                    updated_at,
+                ) = r?;
+                Ok(TaskEntry {
+                    id,
+                    project_id,
+                    description,
+                    start_time: parse_optional_datetime(start_time.as_deref())?,
+                    end_time: parse_optional_datetime(end_time.as_deref())?,
+                    duration_min,
+                    created_at: parse_datetime(&created_at)?,
+                    updated_at: parse_datetime(&updated_at)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(tasks)
+    }
+
     pub fn list_tasks_for_date_range(
         &self,
         from_date: &str,
         to_date: &str,
     ) -> Result<Vec<TaskEntry>> {
-        let mut stmt = self.conn.prepare(
+        let mut stmt = self.conn.prepare(&format!(
             "SELECT id, project_id, description, start_time, end_time, duration_min, created_at, updated_at \
              FROM task_entry \
-             WHERE substr(COALESCE(start_time, created_at), 1, 10) BETWEEN ?1 AND ?2 \
-             ORDER BY COALESCE(start_time, created_at)",
-        )?;
+             WHERE substr(COALESCE(start_time, created_at), 1, 10) BETWEEN ?1 AND ?2 AND {VISIBLE} \
+             ORDER BY COALESCE(start_time, created_at)"
+        ))?;
         let tasks = stmt
             .query_map(params![from_date, to_date], |row| {
                 Ok((
@@ -211,13 +282,104 @@ impl Database {
             "UPDATE task_entry SET description = ?1, project_id = ?2, start_time = ?3, end_time = ?4, duration_min = ?5, updated_at = ?6 WHERE id = ?7",
             params![desc, proj, start_str, end_str, dur, now_str, id],
         )?;
+
+        let updated = TaskEntry {
+            description: desc.to_string(),
+            project_id: proj,
+            start_time: start,
+            end_time: end,
+            duration_min: dur,
+            updated_at: now,
+            ..current.clone()
+        };
+        self.record_task_updated(&current, &updated, now)?;
+        Ok(rows > 0)
+    }
+
+    /// Move a task entry to the trash.
+    pub fn delete_task_entry(&self, id: i64, now: NaiveDateTime) -> Result<bool> {
+        let now_str = now.format("%Y-%m-%dT%H:%M:%S").to_string();
+        let rows = self.conn.execute(
+            "UPDATE task_entry SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+            params![now_str, id],
+        )?;
+        if rows > 0 {
+            self.insert_task_history(id, "delete", None, None, None, now)?;
+        }
+        Ok(rows > 0)
+    }
+
+    /// Take a task entry out of the trash.
+    pub fn undelete_task_entry(&self, id: i64, now: NaiveDateTime) -> Result<bool> {
+        let rows = self.conn.execute(
+            "UPDATE task_entry SET deleted_at = NULL WHERE id = ?1 AND deleted_at IS NOT NULL",
+            params![id],
+        )?;
+        if rows > 0 {
+            self.insert_task_history(id, "restore", None, None, None, now)?;
+        }
         Ok(rows > 0)
     }
 
-    pub fn delete_task_entry(&self, id: i64) -> Result<bool> {
-        let rows = self
-            .conn
-            .execute("DELETE FROM task_entry WHERE id = ?1", params![id])?;
+    /// Permanently remove a trashed task entry.
+    pub fn purge_task_entry(&self, id: i64, now: NaiveDateTime) -> Result<bool> {
+        let rows = self.conn.execute(
+            "DELETE FROM task_entry WHERE id = ?1 AND deleted_at IS NOT NULL",
+            params![id],
+        )?;
+        if rows > 0 {
+            self.insert_task_history(id, "purge", None, None, None, now)?;
+        }
         Ok(rows > 0)
     }
+
+    /// List individually trashed task entries with their deletion time, most recent first.
+    pub fn list_deleted_task_entries(&self) -> Result<Vec<(TaskEntry, NaiveDateTime)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, project_id, description, start_time, end_time, duration_min, created_at, updated_at, deleted_at \
+             FROM task_entry WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC, id DESC",
+        )?;
+        let tasks = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, i64>(5)?,
+                    row.get::<_, String>(6)?,
+                    row.get::<_, String>(7)?,
+                    row.get::<_, String>(8)?,
+                ))
+            })?
+            .map(|r| {
+                let (
+                    id,
+                    project_id,
+                    description,
+                    start_time,
+                    end_time,
+                    duration_min,
+                    created_at,
+                    updated_at,
+                    deleted_at,
+                ) = r?;
+                Ok((
+                    TaskEntry {
+                        id,
+                        project_id,
+                        description,
+                        start_time: parse_optional_datetime(start_time.as_deref())?,
+                        end_time: parse_optional_datetime(end_time.as_deref())?,
+                        duration_min,
+                        created_at: parse_datetime(&created_at)?,
+                        updated_at: parse_datetime(&updated_at)?,
+                    },
+                    parse_datetime(&deleted_at)?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(tasks)
+    }
 }
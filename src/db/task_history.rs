@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use rusqlite::params;
+
+use crate::models::{TaskEntry, TaskHistoryEntry};
+
+use super::{Database, parse_datetime};
+
+fn format_optional_datetime(t: Option<NaiveDateTime>) -> Option<String> {
+    t.map(|t| t.format("%Y-%m-%dT%H:%M:%S").to_string())
+}
+
+/// Field-level values of a task entry as stored in the history table.
+fn field_values(task: &TaskEntry) -> [(&'static str, Option<String>); 5] {
+    [
+        ("description", Some(task.description.clone())),
+        ("project_id", Some(task.project_id.to_string())),
+        ("start_time", format_optional_datetime(task.start_time)),
+        ("end_time", format_optional_datetime(task.end_time)),
+        ("duration_min", Some(task.duration_min.to_string())),
+    ]
+}
+
+impl Database {
+    pub(crate) fn insert_task_history(
+        &self,
+        task_entry_id: i64,
+        action: &str,
+        field: Option<&str>,
+        old_value: Option<&str>,
+        new_value: Option<&str>,
+        now: NaiveDateTime,
+    ) -> Result<()> {
+        let now_str = now.format("%Y-%m-%dT%H:%M:%S").to_string();
+        self.conn.execute(
+            "INSERT INTO task_entry_history (task_entry_id, changed_at, action, field, old_value, new_value) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![task_entry_id, now_str, action, field, old_value, new_value],
+        )?;
+        Ok(())
+    }
+
+    /// Record the initial field values of a newly created entry.
+    pub(crate) fn record_task_created(&self, task: &TaskEntry, now: NaiveDateTime) -> Result<()> {
+        for (field, value) in field_values(task) {
+            if value.is_some() {
+                self.insert_task_history(
+                    task.id,
+                    "create",
+                    Some(field),
+                    None,
+                    value.as_deref(),
+                    now,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Record `action` for the tasks of a project, e.g. when trashing the project hides them.
+    ///
+    /// With `live_only`, tasks that are in the trash on their own are left out.
+    pub(crate) fn record_project_tasks(
+        &self,
+        project_id: i64,
+        action: &str,
+        live_only: bool,
+        now: NaiveDateTime,
+    ) -> Result<()> {
+        let now_str = now.format("%Y-%m-%dT%H:%M:%S").to_string();
+        let filter = if live_only {
+            " AND deleted_at IS NULL"
+        } else {
+            ""
+        };
+        self.conn.execute(
+            &format!(
+                "INSERT INTO task_entry_history (task_entry_id, changed_at, action) \
+                 SELECT id, ?1, ?2 FROM task_entry WHERE project_id = ?3{filter} ORDER BY id"
+            ),
+            params![now_str, action, project_id],
+        )?;
+        Ok(())
+    }
+
+    /// Record every field that differs between two versions of an entry.
+    pub(crate) fn record_task_updated(
+        &self,
+        before: &TaskEntry,
+        after: &TaskEntry,
+        now: NaiveDateTime,
+    ) -> Result<()> {
+        for ((field, old), (_, new)) in field_values(before).into_iter().zip(field_values(after)) {
+            if old != new {
+                self.insert_task_history(
+                    before.id,
+                    "update",
+                    Some(field),
+                    old.as_deref(),
+                    new.as_deref(),
+                    now,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn list_task_history(&self, task_entry_id: i64) -> Result<Vec<TaskHistoryEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, task_entry_id, changed_at, action, field, old_value, new_value \
+             FROM task_entry_history WHERE task_entry_id = ?1 ORDER BY id",
+        )?;
+        let entries = stmt
+            .query_map(params![task_entry_id], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                    row.get::<_, Option<String>>(6)?,
+                ))
+            })?
+            .map(|r| {
+                let (id, task_entry_id, changed_at, action, field, old_value, new_value) = r?;
+                Ok(TaskHistoryEntry {
+                    id,
+                    task_entry_id,
+                    changed_at: parse_datetime(&changed_at)?,
+                    action,
+                    field,
+                    old_value,
+                    new_value,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(entries)
+    }
+
+    /// IDs of task entries that were created or modified at or after `since`.
+    ///
+    /// Creation counts, so an entry logged late for an earlier day is included.
+    pub fn task_ids_modified_since(&self, since: NaiveDateTime) -> Result<HashSet<i64>> {
+        let since_str = since.format("%Y-%m-%dT%H:%M:%S").to_string();
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT task_entry_id FROM task_entry_history WHERE changed_at >= ?1",
+        )?;
+        let ids = stmt
+            .query_map(params![since_str], |row| row.get::<_, i64>(0))?
+            .collect::<rusqlite::Result<HashSet<_>>>()?;
+        Ok(ids)
+    }
+}
@@ -0,0 +1,126 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use rusqlite::params;
+
+use crate::models::TaskTemplate;
+
+use super::{Database, parse_datetime};
+
+impl Database {
+    pub fn insert_task_template(
+        &self,
+        name: &str,
+        project_id: i64,
+        description: &str,
+        duration_min: Option<i64>,
+        now: NaiveDateTime,
+    ) -> Result<TaskTemplate> {
+        let now_str = now.format("%Y-%m-%dT%H:%M:%S").to_string();
+        self.conn.execute(
+            "INSERT INTO task_template (name, project_id, description, duration_min, created_at, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![name, project_id, description, duration_min, now_str, now_str],
+        )?;
+        let id = self.conn.last_insert_rowid();
+        Ok(TaskTemplate {
+            id,
+            name: name.to_string(),
+            project_id,
+            description: description.to_string(),
+            duration_min,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Re-insert a previously deleted template, preserving its ID and timestamps.
+    pub fn restore_task_template(&self, template: &TaskTemplate) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO task_template (id, name, project_id, description, duration_min, created_at, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                template.id,
+                template.name,
+                template.project_id,
+                template.description,
+                template.duration_min,
+                template.created_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                template.updated_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn list_task_templates(&self) -> Result<Vec<TaskTemplate>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, project_id, description, duration_min, created_at, updated_at \
+             FROM task_template ORDER BY name",
+        )?;
+        let templates = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, Option<i64>>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, String>(6)?,
+                ))
+            })?
+            .map(|r| {
+                let (id, name, project_id, description, duration_min, created_at, updated_at) = r?;
+                Ok(TaskTemplate {
+                    id,
+                    name,
+                    project_id,
+                    description,
+                    duration_min,
+                    created_at: parse_datetime(&created_at)?,
+                    updated_at: parse_datetime(&updated_at)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(templates)
+    }
+
+    pub fn find_task_template_by_name(&self, name: &str) -> Result<Option<TaskTemplate>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, project_id, description, duration_min, created_at, updated_at \
+             FROM task_template WHERE name = ?1",
+        )?;
+        let mut rows = stmt.query_map(params![name], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, Option<i64>>(4)?,
+                row.get::<_, String>(5)?,
+                row.get::<_, String>(6)?,
+            ))
+        })?;
+        match rows.next() {
+            Some(r) => {
+                let (id, name, project_id, description, duration_min, created_at, updated_at) = r?;
+                Ok(Some(TaskTemplate {
+                    id,
+                    name,
+                    project_id,
+                    description,
+                    duration_min,
+                    created_at: parse_datetime(&created_at)?,
+                    updated_at: parse_datetime(&updated_at)?,
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn delete_task_template(&self, id: i64) -> Result<bool> {
+        let rows = self
+            .conn
+            .execute("DELETE FROM task_template WHERE id = ?1", params![id])?;
+        Ok(rows > 0)
+    }
+}
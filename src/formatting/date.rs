@@ -0,0 +1,242 @@
+use anyhow::{Result, bail};
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+
+use crate::clock_trait::Clock;
+
+const DATE_FORMATS: &str = "YYYY-MM-DD, today, yesterday, tomorrow, a weekday (monday, last friday), \
+     -3d, 2 weeks ago, an ISO week (2026-W12) or a day of this month (15)";
+
+/// Parse a date string into a NaiveDate, relative to the clock's current date.
+///
+/// Supported formats (case-insensitive):
+/// - ISO 8601 date (e.g., "2026-02-28")
+/// - "today", "yesterday", "tomorrow"
+/// - Weekday name (e.g., "monday" or "mon") → the most recent such day, today included
+/// - "last" + weekday (e.g., "last friday") → the most recent such day before today
+/// - Offset in days or weeks (e.g., "-3d", "+1d", "-2w")
+/// - "N days ago" / "N weeks ago" (e.g., "3 days ago", "1 week ago")
+/// - ISO week (e.g., "2026-W12") → the Monday of that week
+/// - Day of the current month (e.g., "15")
+pub fn parse_date(input: &str, clock: &dyn Clock) -> Result<NaiveDate> {
+    let input = input.trim().to_lowercase();
+    let today = clock.now().date();
+
+    match input.as_str() {
+        "today" => return Ok(today),
+        "yesterday" => return shift(today, -1),
+        "tomorrow" => return shift(today, 1),
+        _ => {}
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(&input, "%Y-%m-%d") {
+        return Ok(date);
+    }
+
+    if let Ok(weekday) = input.parse::<Weekday>() {
+        let back = today.weekday().days_since(weekday);
+        return shift(today, -i64::from(back));
+    }
+    if let Some(day) = input.strip_prefix("last ") {
+        if let Ok(weekday) = day.trim().parse::<Weekday>() {
+            let back = match today.weekday().days_since(weekday) {
+                0 => 7,
+                n => n,
+            };
+            return shift(today, -i64::from(back));
+        }
+    }
+
+    if let Some(days) = parse_offset(&input) {
+        return shift(today, days);
+    }
+    if let Some(days) = parse_ago(&input) {
+        return shift(today, -days);
+    }
+
+    if let Some((year, week)) = input.split_once("-w") {
+        if let (Ok(year), Ok(week)) = (year.parse::<i32>(), week.parse::<u32>()) {
+            return NaiveDate::from_isoywd_opt(year, week, Weekday::Mon).ok_or_else(|| {
+                anyhow::anyhow!("Invalid date: '{input}'. Week {week} does not exist in {year}")
+            });
+        }
+    }
+
+    if let Ok(day) = input.parse::<u32>() {
+        return match today.with_day(day) {
+            Some(date) => Ok(date),
+            None => bail!(
+                "Invalid date: '{input}'. {} has no day {day}",
+                today.format("%B %Y")
+            ),
+        };
+    }
+
+    bail!("Invalid date: '{input}'. Use {DATE_FORMATS}")
+}
+
+/// Days in a `-3d` / `+2w` offset.
+fn parse_offset(input: &str) -> Option<i64> {
+    let sign = match input.chars().next()? {
+        '-' => -1,
+        '+' => 1,
+        _ => return None,
+    };
+    let rest = &input[1..];
+    let (count, unit_days) = if let Some(count) = rest.strip_suffix('d') {
+        (count, 1)
+    } else {
+        (rest.strip_suffix('w')?, 7)
+    };
+    let count: u32 = count.parse().ok()?;
+    Some(sign * unit_days * i64::from(count))
+}
+
+/// Days in `N days ago` / `N weeks ago`.
+fn parse_ago(input: &str) -> Option<i64> {
+    let rest = input.strip_suffix(" ago")?;
+    let (count, unit) = rest.split_once(' ')?;
+    let count: u32 = count.trim().parse().ok()?;
+    let unit_days = match unit.trim() {
+        "day" | "days" => 1,
+        "week" | "weeks" => 7,
+        _ => return None,
+    };
+    Some(unit_days * i64::from(count))
+}
+
+fn shift(date: NaiveDate, days: i64) -> Result<NaiveDate> {
+    let shifted = if days >= 0 {
+        date.checked_add_days(Days::new(days.unsigned_abs()))
+    } else {
+        date.checked_sub_days(Days::new(days.unsigned_abs()))
+    };
+    shifted.ok_or_else(|| anyhow::anyhow!("Date out of range"))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+
+    use super::*;
+
+    struct FixedClock(NaiveDateTime);
+
+    impl Clock for FixedClock {
+        fn now(&self) -> NaiveDateTime {
+            self.0
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    /// Wednesday 2026-03-18, mid-afternoon.
+    fn wednesday() -> FixedClock {
+        FixedClock(date("2026-03-18").and_hms_opt(15, 30, 0).unwrap())
+    }
+
+    fn parse(input: &str) -> NaiveDate {
+        parse_date(input, &wednesday()).unwrap()
+    }
+
+    #[test]
+    fn parse_date_keywords() {
+        assert_eq!(parse("today"), date("2026-03-18"));
+        assert_eq!(parse("yesterday"), date("2026-03-17"));
+        assert_eq!(parse("tomorrow"), date("2026-03-19"));
+        assert_eq!(parse("  Today "), date("2026-03-18"));
+    }
+
+    #[test]
+    fn parse_date_keywords_cross_month_and_year() {
+        let clock = FixedClock(date("2026-01-01").and_hms_opt(0, 0, 0).unwrap());
+        assert_eq!(parse_date("yesterday", &clock).unwrap(), date("2025-12-31"));
+    }
+
+    #[test]
+    fn parse_date_iso() {
+        assert_eq!(parse("2026-01-15"), date("2026-01-15"));
+        assert!(parse_date("2026-02-30", &wednesday()).is_err());
+    }
+
+    #[test]
+    fn parse_date_weekday_is_most_recent_including_today() {
+        assert_eq!(parse("wednesday"), date("2026-03-18"));
+        assert_eq!(parse("monday"), date("2026-03-16"));
+        assert_eq!(parse("Mon"), date("2026-03-16"));
+        assert_eq!(parse("tuesday"), date("2026-03-17"));
+        assert_eq!(parse("thursday"), date("2026-03-12"));
+        assert_eq!(parse("sunday"), date("2026-03-15"));
+    }
+
+    #[test]
+    fn parse_date_last_weekday_is_before_today() {
+        assert_eq!(parse("last wednesday"), date("2026-03-11"));
+        assert_eq!(parse("last friday"), date("2026-03-13"));
+        assert_eq!(parse("last tuesday"), date("2026-03-17"));
+        assert_eq!(parse("LAST  Fri"), date("2026-03-13"));
+    }
+
+    #[test]
+    fn parse_date_offsets() {
+        assert_eq!(parse("-3d"), date("2026-03-15"));
+        assert_eq!(parse("-0d"), date("2026-03-18"));
+        assert_eq!(parse("+1d"), date("2026-03-19"));
+        assert_eq!(parse("-2w"), date("2026-03-04"));
+        assert_eq!(parse("-18d"), date("2026-02-28"));
+    }
+
+    #[test]
+    fn parse_date_ago() {
+        assert_eq!(parse("3 days ago"), date("2026-03-15"));
+        assert_eq!(parse("1 day ago"), date("2026-03-17"));
+        assert_eq!(parse("1 week ago"), date("2026-03-11"));
+        assert_eq!(parse("2 weeks ago"), date("2026-03-04"));
+    }
+
+    #[test]
+    fn parse_date_iso_week() {
+        assert_eq!(parse("2026-W12"), date("2026-03-16"));
+        assert_eq!(parse("2026-w01"), date("2025-12-29"));
+        assert_eq!(parse("2026-W53"), date("2026-12-28"));
+        assert!(parse_date("2025-W53", &wednesday()).is_err());
+        assert!(parse_date("2026-W00", &wednesday()).is_err());
+    }
+
+    #[test]
+    fn parse_date_day_of_month() {
+        assert_eq!(parse("15"), date("2026-03-15"));
+        assert_eq!(parse("1"), date("2026-03-01"));
+        assert_eq!(parse("31"), date("2026-03-31"));
+        assert!(parse_date("0", &wednesday()).is_err());
+        let february = FixedClock(date("2026-02-10").and_hms_opt(9, 0, 0).unwrap());
+        let err = parse_date("30", &february).unwrap_err();
+        assert!(
+            err.to_string().contains("February 2026 has no day 30"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn parse_date_invalid() {
+        for input in [
+            "not-a-date",
+            "",
+            "last",
+            "last month",
+            "-3",
+            "-d",
+            "3y",
+            "-3y",
+            "3 months ago",
+            "days ago",
+            "2026-Wx",
+            "--3d",
+            "-3 days ago",
+        ] {
+            let err = parse_date(input, &wednesday()).unwrap_err();
+            assert!(err.to_string().contains("Invalid date"), "{input}: {err}");
+        }
+    }
+}
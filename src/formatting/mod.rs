@@ -1,32 +1,14 @@
+pub mod date;
 pub mod duration;
 
 use anyhow::{Result, bail};
-use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 
+pub use date::parse_date;
 pub use duration::{format_duration, parse_duration};
 
 use crate::models::TaskEntry;
 
-/// Parse a date string into a NaiveDate.
-///
-/// Supported formats:
-/// - "today" → current date
-/// - "yesterday" → yesterday's date
-/// - ISO 8601 date (e.g., "2026-02-28")
-pub fn parse_date(input: &str) -> Result<NaiveDate> {
-    let input = input.trim().to_lowercase();
-    match input.as_str() {
-        "today" => Ok(Local::now().date_naive()),
-        "yesterday" => Ok(Local::now()
-            .date_naive()
-            .pred_opt()
-            .expect("date underflow")),
-        _ => NaiveDate::parse_from_str(&input, "%Y-%m-%d").map_err(|_| {
-            anyhow::anyhow!("Invalid date: '{input}'. Use YYYY-MM-DD, 'today', or 'yesterday'")
-        }),
-    }
-}
-
 /// Parse a time string into a NaiveDateTime on the given date.
 ///
 /// Supported formats:
@@ -42,6 +24,29 @@ pub fn parse_time(input: &str, date: NaiveDate) -> Result<NaiveDateTime> {
     bail!("Invalid time: '{input}'. Use HH:MM format (e.g., 9:00 or 14:30)")
 }
 
+/// Parse an age such as "30d" or "4w" into a number of days.
+///
+/// A plain integer is interpreted as days.
+pub fn parse_age_days(input: &str) -> Result<i64> {
+    let trimmed = input.trim().to_lowercase();
+    let (number, factor) = if let Some(n) = trimmed.strip_suffix('d') {
+        (n, 1)
+    } else if let Some(n) = trimmed.strip_suffix('w') {
+        (n, 7)
+    } else {
+        (trimmed.as_str(), 1)
+    };
+
+    match number
+        .parse::<i64>()
+        .ok()
+        .and_then(|n| n.checked_mul(factor))
+    {
+        Some(days) if days >= 0 => Ok(days),
+        _ => bail!("Invalid age: '{input}'. Use days or weeks (e.g., 30d, 4w)"),
+    }
+}
+
 /// Format a table of task entries as plain text with aligned columns.
 pub fn format_task_table(tasks: &[TaskEntry], project_names: &[(&str, i64)]) -> String {
     if tasks.is_empty() {
@@ -111,30 +116,6 @@ pub fn format_totals(per_project: &[(&str, i64)], grand_total: i64) -> String {
 mod tests {
     use super::*;
 
-    #[test]
-    fn parse_date_today() {
-        let result = parse_date("today").unwrap();
-        assert_eq!(result, Local::now().date_naive());
-    }
-
-    #[test]
-    fn parse_date_yesterday() {
-        let result = parse_date("yesterday").unwrap();
-        let expected = Local::now().date_naive().pred_opt().unwrap();
-        assert_eq!(result, expected);
-    }
-
-    #[test]
-    fn parse_date_iso() {
-        let result = parse_date("2026-01-15").unwrap();
-        assert_eq!(result, NaiveDate::from_ymd_opt(2026, 1, 15).unwrap());
-    }
-
-    #[test]
-    fn parse_date_invalid() {
-        assert!(parse_date("not-a-date").is_err());
-    }
-
     #[test]
     fn parse_time_hhmm() {
         let date = NaiveDate::from_ymd_opt(2026, 3, 21).unwrap();
@@ -163,6 +144,16 @@ mod tests {
         assert!(parse_time("nope", date).is_err());
     }
 
+    #[test]
+    fn parse_age_days_units() {
+        assert_eq!(parse_age_days("30d").unwrap(), 30);
+        assert_eq!(parse_age_days("4w").unwrap(), 28);
+        assert_eq!(parse_age_days("7").unwrap(), 7);
+        assert!(parse_age_days("-1d").is_err());
+        assert!(parse_age_days("soon").is_err());
+        assert!(parse_age_days("9223372036854775807w").is_err());
+    }
+
     #[test]
     fn format_totals_output() {
         let per_project = vec![("Acme", 120i64), ("Beta", 45i64)];
@@ -1,58 +1,162 @@
 use clap::Parser;
 
+use serde_json::json;
+
+use vibe_clock::cli::output::{OutputFormat, error_json};
 use vibe_clock::cli::{self, Cli};
-use vibe_clock::clock_trait::SystemClock;
+use vibe_clock::clock_trait::{Clock, SystemClock};
 use vibe_clock::error::AppError;
 use vibe_clock::formatting::format_duration;
+use vibe_clock::services::backup as backup_service;
 use vibe_clock::services::clock as clock_service;
 use vibe_clock::{config, crypto, db};
 
 fn main() {
     let cli = Cli::parse();
+    let out = cli.output_format;
 
     if let Err(err) = run(cli) {
-        eprintln!("Error: {err}");
+        if out == OutputFormat::Json {
+            eprintln!("{}", error_json(&err));
+        } else {
+            eprintln!("Error: {err}");
+        }
         std::process::exit(err.exit_code());
     }
 }
 
 fn run(cli: Cli) -> Result<(), AppError> {
-    let db_path = config::resolve_db_path().map_err(|e| AppError::SystemError(e.to_string()))?;
-    let passphrase = crypto::get_passphrase().map_err(|e| AppError::SystemError(e.to_string()))?;
-    let db = db::Database::open(&db_path, &passphrase)?;
+    let out = cli.output_format;
+    let config_path = config::config_path(cli.config.as_deref())
+        .map_err(|e| AppError::SystemError(e.to_string()))?;
+    if let cli::Command::Config { action } = &cli.command {
+        return cli::config::handle_config(&config_path, action, out);
+    }
+    let settings = config::load_settings(&config_path, cli.config.is_some())
+        .map_err(|e| AppError::UserError(format!("{e:#}")))?;
+
+    let profile = config::active_profile(cli.profile.as_deref())
+        .map_err(|e| AppError::UserError(e.to_string()))?;
+    if let cli::Command::Profile { action } = &cli.command {
+        return cli::profile::handle_profile(&profile, action, out);
+    }
+    // VIBE_CLOCK_DB names the database explicitly; otherwise a mistyped profile
+    // must not quietly create a new one
+    if std::env::var_os("VIBE_CLOCK_DB").is_none() && !config::profile_exists(&profile)? {
+        return Err(AppError::UserError(format!(
+            "Profile '{profile}' does not exist. Create it with: vibe-clock profile create {profile}"
+        )));
+    }
+
+    let db_path = config::resolve_db_path(&profile, &settings)
+        .map_err(|e| AppError::SystemError(e.to_string()))?;
+    let key = crypto::get_passphrase(&profile, !db_path.exists())
+        .map_err(|e| AppError::SystemError(format!("{e:#}")))?;
+    let passphrase = key.value;
+
+    if let cli::Command::Db {
+        action: cli::DbAction::Encrypt { plain, output },
+    } = &cli.command
+    {
+        return cli::db::handle_encrypt(plain, output.as_deref(), &passphrase, out);
+    }
+
+    let mut db = db::Database::open(&db_path, &passphrase).map_err(|e| {
+        if e.is::<db::WrongPassphrase>() {
+            AppError::UserError(e.to_string())
+        } else {
+            AppError::from(e)
+        }
+    })?;
+    // Only a passphrase that opened the database is worth remembering
+    if key.prompted {
+        crypto::store_in_keyring(&profile, &passphrase).ok();
+    }
     let clock = SystemClock;
 
-    // Clock crash recovery (FR-016): warn if clock was left running
-    if let Some(info) = clock_service::recover_clock(&db, &clock)? {
-        eprintln!(
-            "Warning: Clock still running for '{}' on project '{}' since {} ({} elapsed).",
-            info.description,
-            info.project_name,
-            info.start_time,
-            format_duration(info.elapsed_min)
-        );
+    // Clock crash recovery (FR-016): warn if clock was left running. Database
+    // maintenance skips it, so `db check` works even on an unreadable clock state.
+    let maintenance = matches!(cli.command, cli::Command::Db { .. });
+    let recovered = if maintenance {
+        None
+    } else {
+        clock_service::recover_clock(&db, &clock)?
+    };
+    if let Some(info) = recovered {
+        if out == OutputFormat::Json {
+            eprintln!("{}", json!({ "warning": { "clock_running": info } }));
+        } else {
+            eprintln!(
+                "Warning: Clock still running for '{}' on project '{}' since {} ({} elapsed).",
+                info.description,
+                info.project_name,
+                info.start_time.format("%H:%M"),
+                format_duration(info.elapsed_min)
+            );
+        }
+    }
+
+    // Daily automatic backup; a failure must not block the command itself
+    let backup = config::backup_retention()
+        .map_err(|e| AppError::UserError(e.to_string()))
+        .and_then(|keep| {
+            backup_service::auto_backup(&db, &passphrase, &db_path, keep, clock.now())
+        });
+    if let Err(err) = backup {
+        if out == OutputFormat::Json {
+            eprintln!(
+                "{}",
+                json!({ "warning": { "backup_failed": err.to_string() } })
+            );
+        } else {
+            eprintln!("Warning: Automatic backup failed: {err}");
+        }
     }
 
     match cli.command {
+        cli::Command::Config { .. } => unreachable!("config commands return early"),
+        cli::Command::Profile { .. } => unreachable!("profile commands return early"),
         cli::Command::Project { action } => {
-            cli::project::handle_project(&db, &clock, action)?;
+            cli::project::handle_project(&db, &clock, action, out)?;
         }
         cli::Command::Clock { action } => {
-            cli::clock::handle_clock(&db, &clock, action)?;
+            cli::clock::handle_clock(&db, &clock, &settings, &profile, action, out)?;
         }
         cli::Command::Task { action } => {
-            cli::task::handle_task(&db, &clock, action)?;
+            cli::task::handle_task(&db, &clock, &settings, action, out)?;
+        }
+        cli::Command::Template { action } => {
+            cli::template::handle_template(&db, &clock, action, out)?;
+        }
+        cli::Command::Period { action } => {
+            cli::period::handle_period(&db, &clock, action, out)?;
+        }
+        cli::Command::Trash { action } => {
+            cli::trash::handle_trash(&db, &clock, action, out)?;
+        }
+        cli::Command::Import { action } => {
+            cli::import::handle_import(&db, &clock, action, out)?;
+        }
+        cli::Command::Undo => {
+            cli::undo::handle_undo(&db, &clock, out)?;
+        }
+        cli::Command::Redo => {
+            cli::undo::handle_redo(&db, &clock, out)?;
         }
         cli::Command::Journal { date } => {
-            cli::journal::handle_journal(&db, date.as_deref())?;
-        }
-        cli::Command::Report {
-            from,
-            to,
-            pdf,
-            output,
-        } => {
-            cli::report::handle_report(&db, &from, to.as_deref(), pdf, output.as_deref())?;
+            cli::journal::handle_journal(&db, &clock, &settings, date.as_deref(), out)?;
+        }
+        cli::Command::Export(args) => {
+            cli::export::handle_export(&db, &clock, args, out)?;
+        }
+        cli::Command::Report(args) => {
+            cli::report::handle_report(&db, &clock, &settings, args, out)?;
+        }
+        cli::Command::Backup { action } => {
+            cli::backup::handle_backup(&mut db, &clock, &db_path, &passphrase, action, out)?;
+        }
+        cli::Command::Db { action } => {
+            cli::db::handle_db(&db, &clock, &db_path, &profile, &passphrase, action, out)?;
         }
     }
 
@@ -1,7 +1,15 @@
 mod clock_state;
+mod operation;
+mod period_lock;
 mod project;
 mod task_entry;
+mod task_history;
+mod task_template;
 
 pub use clock_state::ClockState;
+pub use operation::Operation;
+pub use period_lock::PeriodLockEvent;
 pub use project::Project;
 pub use task_entry::TaskEntry;
+pub use task_history::TaskHistoryEntry;
+pub use task_template::TaskTemplate;
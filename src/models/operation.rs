@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+
+use super::{ClockState, Project, TaskEntry, TaskTemplate};
+
+/// A recorded mutation, carrying enough data to revert and re-apply it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Operation {
+    CreateProject {
+        project: Project,
+    },
+    RenameProject {
+        id: i64,
+        old_name: String,
+        new_name: String,
+    },
+    DeleteProject {
+        project: Project,
+        tasks: Vec<TaskEntry>,
+        templates: Vec<TaskTemplate>,
+        clock: Option<ClockState>,
+    },
+    AddTask {
+        task: TaskEntry,
+    },
+    EditTask {
+        before: TaskEntry,
+        after: TaskEntry,
+    },
+    DeleteTask {
+        task: TaskEntry,
+    },
+    StartClock {
+        clock: ClockState,
+    },
+    StopClock {
+        clock: ClockState,
+        task: TaskEntry,
+    },
+    /// A batch of entries imported from an external source, with the projects it created.
+    Import {
+        source: String,
+        projects: Vec<Project>,
+        tasks: Vec<TaskEntry>,
+    },
+}
+
+impl Operation {
+    /// Short machine-readable name, stored alongside the payload.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Operation::CreateProject { .. } => "create_project",
+            Operation::RenameProject { .. } => "rename_project",
+            Operation::DeleteProject { .. } => "delete_project",
+            Operation::AddTask { .. } => "add_task",
+            Operation::EditTask { .. } => "edit_task",
+            Operation::DeleteTask { .. } => "delete_task",
+            Operation::StartClock { .. } => "start_clock",
+            Operation::StopClock { .. } => "stop_clock",
+            Operation::Import { .. } => "import",
+        }
+    }
+
+    /// Task entries whose visibility or content this operation changes.
+    pub fn affected_tasks(&self) -> Vec<&TaskEntry> {
+        match self {
+            Operation::DeleteProject { tasks, .. } | Operation::Import { tasks, .. } => {
+                tasks.iter().collect()
+            }
+            Operation::AddTask { task }
+            | Operation::DeleteTask { task }
+            | Operation::StopClock { task, .. } => vec![task],
+            Operation::EditTask { before, after } => vec![before, after],
+            Operation::CreateProject { .. }
+            | Operation::RenameProject { .. }
+            | Operation::StartClock { .. } => Vec::new(),
+        }
+    }
+
+    /// Human-readable summary used in undo/redo confirmations.
+    pub fn describe(&self) -> String {
+        match self {
+            Operation::CreateProject { project } => format!("create project '{}'", project.name),
+            Operation::RenameProject {
+                old_name, new_name, ..
+            } => format!("rename project '{old_name}' to '{new_name}'"),
+            Operation::DeleteProject { project, tasks, .. } => {
+                format!("delete project '{}' ({} tasks)", project.name, tasks.len())
+            }
+            Operation::AddTask { task } => format!("add task {} '{}'", task.id, task.description),
+            Operation::EditTask { after, .. } => {
+                format!("edit task {} '{}'", after.id, after.description)
+            }
+            Operation::DeleteTask { task } => {
+                format!("delete task {} '{}'", task.id, task.description)
+            }
+            Operation::StartClock { clock } => format!("start clock for '{}'", clock.description),
+            Operation::StopClock { task, .. } => {
+                format!("stop clock for '{}'", task.description)
+            }
+            Operation::Import { source, tasks, .. } => {
+                format!("import of {} entries from {source}", tasks.len())
+            }
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+/// One recorded change to the locked period (`lock` or `unlock`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodLockEvent {
+    pub id: i64,
+    pub action: String,
+    pub locked_to: Option<NaiveDate>,
+    pub previous_locked_to: Option<NaiveDate>,
+    pub changed_at: NaiveDateTime,
+}
@@ -1,6 +1,7 @@
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskEntry {
     pub id: i64,
     pub project_id: i64,
@@ -11,3 +12,12 @@ pub struct TaskEntry {
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
+
+impl TaskEntry {
+    /// The day the entry belongs to: its start date, or creation date for duration-only entries.
+    pub fn date(&self) -> NaiveDate {
+        self.start_time
+            .map(|t| t.date())
+            .unwrap_or_else(|| self.created_at.date())
+    }
+}
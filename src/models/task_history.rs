@@ -0,0 +1,17 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// One append-only record of a change to a task entry.
+///
+/// `create` and `update` records carry one row per affected field; `delete` and
+/// `restore` records have no field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskHistoryEntry {
+    pub id: i64,
+    pub task_entry_id: i64,
+    pub changed_at: NaiveDateTime,
+    pub action: String,
+    pub field: Option<String>,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
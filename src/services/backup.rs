@@ -0,0 +1,204 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, NaiveDateTime, Timelike};
+use serde::Serialize;
+
+use crate::db::{Database, SCHEMA_VERSION};
+use crate::error::AppError;
+
+/// File name prefix of the daily automatic backups; only these are rotated.
+const AUTO_PREFIX: &str = "auto-";
+const BACKUP_EXTENSION: &str = "db";
+
+#[derive(Serialize)]
+pub struct BackupInfo {
+    pub path: PathBuf,
+    pub automatic: bool,
+    pub size: u64,
+    pub modified: NaiveDateTime,
+}
+
+#[derive(Serialize)]
+pub struct RestoreResult {
+    pub restored_from: PathBuf,
+    /// Copy of the database as it was before the restore.
+    pub safety_backup: PathBuf,
+}
+
+/// Where backups are kept: a `backups` directory next to the database file.
+pub fn backup_dir(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("backups")
+}
+
+fn backup_info(path: PathBuf) -> Result<BackupInfo, AppError> {
+    let metadata = std::fs::metadata(&path)?;
+    let modified = DateTime::<Local>::from(metadata.modified()?).naive_local();
+    let automatic = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.starts_with(AUTO_PREFIX));
+    Ok(BackupInfo {
+        automatic,
+        size: metadata.len(),
+        modified: modified.with_nanosecond(0).unwrap_or(modified),
+        path,
+    })
+}
+
+/// Write a backup through a temporary file, so an interrupted backup never looks complete.
+fn write_backup(db: &Database, passphrase: &str, path: &Path) -> Result<BackupInfo, AppError> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+    let _ = std::fs::remove_file(&tmp_path);
+
+    db.backup_to(&tmp_path, passphrase)
+        .and_then(|()| Ok(std::fs::rename(&tmp_path, path)?))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&tmp_path);
+            AppError::SystemError(format!("Backup to '{}' failed: {e}", path.display()))
+        })?;
+    backup_info(path.to_path_buf())
+}
+
+/// Create a backup at `target`, or with a timestamped name in the backup directory.
+///
+/// A directory as `target` gets the timestamped name inside it. Existing files are
+/// never overwritten.
+pub fn create_backup(
+    db: &Database,
+    passphrase: &str,
+    db_path: &Path,
+    target: Option<&Path>,
+    now: NaiveDateTime,
+) -> Result<BackupInfo, AppError> {
+    let name = format!(
+        "vibe-clock-{}.{BACKUP_EXTENSION}",
+        now.format("%Y-%m-%dT%H%M%S")
+    );
+    let path = match target {
+        Some(t) if t.is_dir() => t.join(name),
+        Some(t) => t.to_path_buf(),
+        None => backup_dir(db_path).join(name),
+    };
+    if path.exists() {
+        return Err(AppError::UserError(format!(
+            "'{}' already exists.",
+            path.display()
+        )));
+    }
+    write_backup(db, passphrase, &path)
+}
+
+/// Take today's automatic backup unless it exists, then keep only the newest `keep`.
+///
+/// Returns the new backup's path. `keep == 0` disables automatic backups.
+pub fn auto_backup(
+    db: &Database,
+    passphrase: &str,
+    db_path: &Path,
+    keep: usize,
+    now: NaiveDateTime,
+) -> Result<Option<PathBuf>, AppError> {
+    if keep == 0 {
+        return Ok(None);
+    }
+    let dir = backup_dir(db_path);
+    let path = dir.join(format!(
+        "{AUTO_PREFIX}{}.{BACKUP_EXTENSION}",
+        now.format("%Y-%m-%d")
+    ));
+    if path.exists() {
+        return Ok(None);
+    }
+    write_backup(db, passphrase, &path)?;
+
+    // Names sort by date, so everything after the newest `keep` is old
+    let mut automatic: Vec<PathBuf> = list_backups(db_path)?
+        .into_iter()
+        .filter(|b| b.automatic)
+        .map(|b| b.path)
+        .collect();
+    automatic.sort();
+    automatic.reverse();
+    for old in automatic.iter().skip(keep) {
+        std::fs::remove_file(old)?;
+    }
+    Ok(Some(path))
+}
+
+/// Backups in the backup directory, newest first.
+pub fn list_backups(db_path: &Path) -> Result<Vec<BackupInfo>, AppError> {
+    let dir = backup_dir(db_path);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut backups = Vec::new();
+    for entry in std::fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|e| e == BACKUP_EXTENSION) {
+            backups.push(backup_info(path)?);
+        }
+    }
+    backups.sort_by(|a, b| b.modified.cmp(&a.modified).then(b.path.cmp(&a.path)));
+    Ok(backups)
+}
+
+/// Replace the live database with a backup.
+///
+/// The backup must open with the current passphrase and must not come from a newer
+/// version. The current database is backed up first, so the restore can be reverted.
+/// `file` may also name a file in the backup directory.
+pub fn restore_backup(
+    db: &mut Database,
+    passphrase: &str,
+    db_path: &Path,
+    file: &Path,
+    now: NaiveDateTime,
+) -> Result<RestoreResult, AppError> {
+    let in_backup_dir = backup_dir(db_path).join(file);
+    let path = if !file.exists() && in_backup_dir.exists() {
+        in_backup_dir
+    } else {
+        file.to_path_buf()
+    };
+    if !path.is_file() {
+        return Err(AppError::UserError(format!(
+            "Backup '{}' not found.",
+            file.display()
+        )));
+    }
+    if path.canonicalize()? == db_path.canonicalize()? {
+        return Err(AppError::UserError(
+            "That is the live database, not a backup.".to_string(),
+        ));
+    }
+
+    let version = Database::backup_schema_version(&path, passphrase)
+        .map_err(|e| AppError::UserError(e.to_string()))?;
+    if version > SCHEMA_VERSION {
+        return Err(AppError::UserError(format!(
+            "The backup has schema version {version}, but this version of vibe-clock only knows up to {SCHEMA_VERSION}. Upgrade vibe-clock first."
+        )));
+    }
+
+    let safety = backup_dir(db_path).join(format!(
+        "pre-restore-{}.{BACKUP_EXTENSION}",
+        now.format("%Y-%m-%dT%H%M%S")
+    ));
+    write_backup(db, passphrase, &safety)?;
+    db.restore_from(&path, passphrase)?;
+
+    Ok(RestoreResult {
+        restored_from: path,
+        safety_backup: safety,
+    })
+}
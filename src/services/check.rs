@@ -0,0 +1,205 @@
+use std::collections::HashSet;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::Serialize;
+
+use crate::db::{DATE_COLUMNS, DATETIME_COLUMNS, Database};
+use crate::error::AppError;
+
+/// What `db check --fix` does about a finding.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Repair {
+    /// Nothing automatic is safe; restore a backup instead.
+    None,
+    /// Store the duration implied by the start and end times.
+    RecomputeDuration { duration_min: i64 },
+    /// Replace an unreadable bookkeeping timestamp with the time of the repair.
+    ResetTimestamp { column: String },
+    /// Move the row into the `quarantine` table.
+    Quarantine,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Finding {
+    /// `None` for problems with the database file as a whole.
+    pub table: Option<String>,
+    pub row_id: Option<i64>,
+    pub problem: String,
+    pub repair: Repair,
+}
+
+impl Finding {
+    fn row(table: &str, row_id: i64, problem: String, repair: Repair) -> Self {
+        Finding {
+            table: Some(table.to_string()),
+            row_id: Some(row_id),
+            problem,
+            repair,
+        }
+    }
+}
+
+/// Datetime columns that carry the meaning of a row; an unreadable value there
+/// cannot be guessed, so the row is quarantined instead of reset.
+fn is_essential(table: &str, column: &str) -> bool {
+    matches!(
+        (table, column),
+        ("task_entry", "start_time" | "end_time") | ("clock_state", "start_time")
+    )
+}
+
+fn parses_as_datetime(value: &str) -> bool {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").is_ok()
+}
+
+/// Check the file, foreign keys, stored datetimes and task durations.
+pub fn check_database(db: &Database) -> Result<Vec<Finding>, AppError> {
+    let mut findings: Vec<Finding> = db
+        .integrity_problems()?
+        .into_iter()
+        .map(|problem| Finding {
+            table: None,
+            row_id: None,
+            problem,
+            repair: Repair::None,
+        })
+        .collect();
+
+    for violation in db.foreign_key_violations()? {
+        findings.push(Finding::row(
+            &violation.table,
+            violation.rowid,
+            format!("refers to a missing {} row", violation.parent),
+            Repair::Quarantine,
+        ));
+    }
+
+    for (table, column) in DATETIME_COLUMNS {
+        for (rowid, value) in db.column_values(table, column)? {
+            if parses_as_datetime(&value) {
+                continue;
+            }
+            let repair = if is_essential(table, column) {
+                Repair::Quarantine
+            } else {
+                Repair::ResetTimestamp {
+                    column: column.to_string(),
+                }
+            };
+            findings.push(Finding::row(
+                table,
+                rowid,
+                format!("{column} '{value}' is not a valid datetime"),
+                repair,
+            ));
+        }
+    }
+
+    for (table, column) in DATE_COLUMNS {
+        for (rowid, value) in db.column_values(table, column)? {
+            if NaiveDate::parse_from_str(&value, "%Y-%m-%d").is_err() {
+                findings.push(Finding::row(
+                    table,
+                    rowid,
+                    format!("{column} '{value}' is not a valid date"),
+                    Repair::Quarantine,
+                ));
+            }
+        }
+    }
+
+    for task in db.raw_task_times()? {
+        let parse = |value: &Option<String>| match value {
+            Some(v) => NaiveDateTime::parse_from_str(v, "%Y-%m-%dT%H:%M:%S")
+                .ok()
+                .map(Some),
+            None => Some(None),
+        };
+        // Unreadable times are reported above
+        let (Some(start), Some(end)) = (parse(&task.start_time), parse(&task.end_time)) else {
+            continue;
+        };
+        match (start, end) {
+            (None, Some(_)) => findings.push(Finding::row(
+                "task_entry",
+                task.id,
+                "has an end time but no start time".to_string(),
+                Repair::Quarantine,
+            )),
+            (Some(start), Some(end)) if end <= start => findings.push(Finding::row(
+                "task_entry",
+                task.id,
+                "ends before it starts".to_string(),
+                Repair::Quarantine,
+            )),
+            (Some(start), Some(end)) => {
+                // The clock logs at least a minute, even for shorter runs
+                let expected = (end - start).num_minutes().max(1);
+                if expected != task.duration_min {
+                    findings.push(Finding::row(
+                        "task_entry",
+                        task.id,
+                        format!(
+                            "duration_min is {} but its times span {expected} minutes",
+                            task.duration_min
+                        ),
+                        Repair::RecomputeDuration {
+                            duration_min: expected,
+                        },
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(findings)
+}
+
+/// Apply the repairs of `findings` in one transaction and return how many were applied.
+///
+/// A quarantined row takes its other findings with it.
+pub fn repair_database(
+    db: &Database,
+    findings: &[Finding],
+    now: NaiveDateTime,
+) -> Result<usize, AppError> {
+    let tx = db.conn.unchecked_transaction()?;
+    let mut quarantined = HashSet::new();
+    let mut applied = 0;
+
+    for finding in findings {
+        let (Some(table), Some(row_id)) = (&finding.table, finding.row_id) else {
+            continue;
+        };
+        if finding.repair == Repair::Quarantine
+            && quarantined.insert((table.as_str(), row_id))
+            && db.quarantine_row(table, row_id, &finding.problem, now)?
+        {
+            applied += 1;
+        }
+    }
+
+    for finding in findings {
+        let (Some(table), Some(row_id)) = (&finding.table, finding.row_id) else {
+            continue;
+        };
+        if quarantined.contains(&(table.as_str(), row_id)) {
+            continue;
+        }
+        match &finding.repair {
+            Repair::RecomputeDuration { duration_min } => {
+                db.set_task_duration(row_id, *duration_min)?;
+            }
+            Repair::ResetTimestamp { column } => {
+                db.reset_timestamp(table, column, row_id, now)?;
+            }
+            Repair::None | Repair::Quarantine => continue,
+        }
+        applied += 1;
+    }
+
+    tx.commit()?;
+    Ok(applied)
+}
@@ -1,25 +1,48 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
 use crate::clock_trait::Clock;
 use crate::db::Database;
 use crate::error::AppError;
+use crate::models::{ClockState, Operation, TaskEntry};
 
 pub fn start_clock(
     db: &Database,
     project_name: &str,
     description: &str,
     clock: &dyn Clock,
-) -> Result<(String, String), AppError> {
+) -> Result<ClockStatusInfo, AppError> {
     if db.get_clock_state()?.is_some() {
         return Err(AppError::UserError(
             "Clock already running. Use 'vibe-clock clock stop' first, or 'vibe-clock clock status' to check.".to_string(),
         ));
     }
 
-    let project = super::resolve_project(db, project_name)?;
+    let project = super::find_project(db, project_name)?;
 
     let now = clock.now();
+    let tx = db.conn.unchecked_transaction()?;
     db.insert_clock_state(project.id, description, now)?;
+    super::undo::record(
+        db,
+        &Operation::StartClock {
+            clock: ClockState {
+                id: 1,
+                project_id: project.id,
+                description: description.to_string(),
+                start_time: now,
+            },
+        },
+        now,
+    )?;
+    tx.commit()?;
 
-    Ok((project.name, now.format("%H:%M").to_string()))
+    Ok(ClockStatusInfo {
+        project_name: project.name,
+        description: description.to_string(),
+        start_time: now,
+        elapsed_min: 0,
+    })
 }
 
 pub fn stop_clock(db: &Database, clock: &dyn Clock) -> Result<StopResult, AppError> {
@@ -27,6 +50,8 @@ pub fn stop_clock(db: &Database, clock: &dyn Clock) -> Result<StopResult, AppErr
         .get_clock_state()?
         .ok_or_else(|| AppError::UserError("No clock is running.".to_string()))?;
 
+    super::period::ensure_date_unlocked(db, state.start_time.date())?;
+
     let now = clock.now();
     let duration_min = (now - state.start_time).num_minutes().max(1);
 
@@ -35,27 +60,26 @@ pub fn stop_clock(db: &Database, clock: &dyn Clock) -> Result<StopResult, AppErr
 
     // Transaction: delete clock state + insert task entry
     let tx = db.conn.unchecked_transaction()?;
-    tx.execute("DELETE FROM clock_state WHERE id = 1", [])?;
-    tx.execute(
-        "INSERT INTO task_entry (project_id, description, start_time, end_time, duration_min, created_at, updated_at) \
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-        rusqlite::params![
-            state.project_id,
-            state.description,
-            state.start_time.format("%Y-%m-%dT%H:%M:%S").to_string(),
-            now.format("%Y-%m-%dT%H:%M:%S").to_string(),
-            duration_min,
-            now.format("%Y-%m-%dT%H:%M:%S").to_string(),
-            now.format("%Y-%m-%dT%H:%M:%S").to_string(),
-        ],
+    db.delete_clock_state()?;
+    let task = db.insert_task_entry(
+        state.project_id,
+        &state.description,
+        Some(state.start_time),
+        Some(now),
+        duration_min,
+        now,
+    )?;
+    super::undo::record(
+        db,
+        &Operation::StopClock {
+            clock: state,
+            task: task.clone(),
+        },
+        now,
     )?;
     tx.commit()?;
 
-    Ok(StopResult {
-        project_name,
-        description: state.description,
-        duration_min,
-    })
+    Ok(StopResult { project_name, task })
 }
 
 pub fn clock_status(db: &Database, clock: &dyn Clock) -> Result<Option<ClockStatusInfo>, AppError> {
@@ -70,7 +94,7 @@ pub fn clock_status(db: &Database, clock: &dyn Clock) -> Result<Option<ClockStat
             Ok(Some(ClockStatusInfo {
                 project_name,
                 description: state.description,
-                start_time: state.start_time.format("%H:%M").to_string(),
+                start_time: state.start_time,
                 elapsed_min,
             }))
         }
@@ -85,15 +109,16 @@ pub fn recover_clock(
     clock_status(db, clock)
 }
 
+#[derive(Serialize)]
 pub struct StopResult {
     pub project_name: String,
-    pub description: String,
-    pub duration_min: i64,
+    pub task: TaskEntry,
 }
 
+#[derive(Serialize)]
 pub struct ClockStatusInfo {
     pub project_name: String,
     pub description: String,
-    pub start_time: String,
+    pub start_time: NaiveDateTime,
     pub elapsed_min: i64,
 }
@@ -0,0 +1,140 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::error::AppError;
+use crate::services::render::ReportRenderer;
+use crate::services::report::Report;
+
+/// Layout options for CSV output.
+#[derive(Clone, Copy)]
+pub struct CsvOptions {
+    pub delimiter: u8,
+    pub header: bool,
+    /// Emit one row per project (from `ProjectSummary`) instead of one row per entry.
+    pub summary: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: b',',
+            header: true,
+            summary: false,
+        }
+    }
+}
+
+/// Parse a `--delimiter` value: a single ASCII character, or `tab` / `\t`.
+///
+/// Quotes and control characters other than tab would make the output unreadable.
+pub fn parse_delimiter(s: &str) -> Result<u8, AppError> {
+    match s.as_bytes() {
+        _ if matches!(s, "tab" | "\\t" | "\t") => Ok(b'\t'),
+        &[b] if b.is_ascii() && !b.is_ascii_control() && b != b'"' => Ok(b),
+        _ => Err(AppError::UserError(format!(
+            "Invalid delimiter '{}'. Use a single character such as ',' or ';', or 'tab'.",
+            s.escape_default()
+        ))),
+    }
+}
+
+/// Validate a `--csv` output path; its directory must already exist.
+pub fn resolve_csv_path(output: &str) -> Result<PathBuf, AppError> {
+    let path = PathBuf::from(output);
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            return Err(AppError::UserError(format!(
+                "Directory '{}' does not exist.",
+                parent.display()
+            )));
+        }
+    }
+    Ok(path)
+}
+
+/// Minutes as decimal hours with two places, e.g. 90 -> "1.50".
+fn decimal_hours(minutes: i64) -> String {
+    format!("{:.2}", minutes as f64 / 60.0)
+}
+
+fn csv_error(e: impl std::fmt::Display) -> AppError {
+    AppError::SystemError(format!("Failed to write CSV: {e}"))
+}
+
+/// Write a report as CSV to `writer`.
+pub fn write_csv<W: Write>(
+    report: &Report,
+    options: &CsvOptions,
+    writer: W,
+) -> Result<(), AppError> {
+    let mut csv = csv::WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .from_writer(writer);
+
+    if options.summary {
+        if options.header {
+            csv.write_record(["project", "minutes", "hours"])
+                .map_err(csv_error)?;
+        }
+        for summary in &report.project_summaries {
+            csv.write_record([
+                summary.name.clone(),
+                summary.total.to_string(),
+                decimal_hours(summary.total),
+            ])
+            .map_err(csv_error)?;
+        }
+    } else {
+        if options.header {
+            csv.write_record([
+                "date",
+                "project",
+                "description",
+                "start",
+                "end",
+                "minutes",
+                "hours",
+            ])
+            .map_err(csv_error)?;
+        }
+        for section in &report.daily_sections {
+            for entry in &section.entries {
+                let time = |t: Option<chrono::NaiveDateTime>| {
+                    t.map(|t| t.format("%H:%M").to_string()).unwrap_or_default()
+                };
+                csv.write_record([
+                    section.date.format("%Y-%m-%d").to_string(),
+                    entry.project_name.clone(),
+                    entry.task.description.clone(),
+                    time(entry.task.start_time),
+                    time(entry.task.end_time),
+                    entry.task.duration_min.to_string(),
+                    decimal_hours(entry.task.duration_min),
+                ])
+                .map_err(csv_error)?;
+            }
+        }
+    }
+
+    csv.flush().map_err(csv_error)?;
+    Ok(())
+}
+
+/// Renders reports as CSV with the given [`CsvOptions`].
+pub struct CsvRenderer {
+    pub options: CsvOptions,
+}
+
+impl ReportRenderer for CsvRenderer {
+    fn name(&self) -> &'static str {
+        "CSV"
+    }
+
+    fn extension(&self) -> &'static str {
+        "csv"
+    }
+
+    fn render(&self, report: &Report, out: &mut dyn Write) -> Result<(), AppError> {
+        write_csv(report, &self.options, out)
+    }
+}
@@ -0,0 +1,172 @@
+use std::path::Path;
+
+use crate::clock_trait::Clock;
+use crate::error::AppError;
+use crate::services::import::{ImportEntry, ImportIssue, ImportRecord};
+
+/// Entry fields that can be read from a CSV column.
+const FIELDS: [&str; 6] = ["project", "description", "date", "start", "end", "duration"];
+
+/// Which CSV header supplies each entry field.
+pub struct ColumnMapping {
+    columns: Vec<(&'static str, String)>,
+}
+
+impl ColumnMapping {
+    /// Parse a `--map` spec such as `project=Client,description=Notes`.
+    ///
+    /// Unmapped fields default to a column of the same name; `duration` also
+    /// accepts a `minutes` column so files written by `report --format csv` import as-is.
+    pub fn parse(spec: Option<&str>) -> Result<Self, AppError> {
+        let mut columns: Vec<(&'static str, String)> =
+            FIELDS.iter().map(|f| (*f, f.to_string())).collect();
+
+        for pair in spec
+            .unwrap_or("")
+            .split(',')
+            .filter(|p| !p.trim().is_empty())
+        {
+            let (field, header) = pair.split_once('=').ok_or_else(|| {
+                AppError::UserError(format!(
+                    "Invalid mapping '{pair}'. Use field=Column, e.g. project=Client."
+                ))
+            })?;
+            let field = field.trim().to_lowercase();
+            let slot = columns
+                .iter_mut()
+                .find(|(f, _)| *f == field)
+                .ok_or_else(|| {
+                    AppError::UserError(format!(
+                        "Unknown field '{field}' in mapping. Valid fields: {}.",
+                        FIELDS.join(", ")
+                    ))
+                })?;
+            slot.1 = header.trim().to_string();
+        }
+        Ok(ColumnMapping { columns })
+    }
+
+    fn header_for<'a>(&'a self, field: &'a str) -> &'a str {
+        self.columns
+            .iter()
+            .find(|(f, _)| *f == field)
+            .map(|(_, h)| h.as_str())
+            .unwrap_or(field)
+    }
+}
+
+/// Column index of each field in a particular file.
+struct ColumnIndex {
+    project: usize,
+    description: usize,
+    date: Option<usize>,
+    start: Option<usize>,
+    end: Option<usize>,
+    duration: Option<usize>,
+}
+
+fn resolve_columns(
+    headers: &csv::StringRecord,
+    mapping: &ColumnMapping,
+) -> Result<ColumnIndex, AppError> {
+    let find = |name: &str| {
+        headers
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name.trim()))
+    };
+    let required = |field: &str| {
+        let header = mapping.header_for(field);
+        find(header).ok_or_else(|| {
+            AppError::UserError(format!(
+                "CSV has no '{header}' column for {field}. Use --map {field}=<column>."
+            ))
+        })
+    };
+
+    Ok(ColumnIndex {
+        project: required("project")?,
+        description: required("description")?,
+        date: find(mapping.header_for("date")),
+        start: find(mapping.header_for("start")),
+        end: find(mapping.header_for("end")),
+        duration: find(mapping.header_for("duration")).or_else(|| find("minutes")),
+    })
+}
+
+/// Read task entries from a CSV file with a header row.
+///
+/// Each row is validated with the same rules as `task add`; rows that fail are
+/// returned as errors tagged with their line number rather than aborting the read.
+pub fn read_csv(
+    path: &Path,
+    mapping: &ColumnMapping,
+    delimiter: u8,
+    clock: &dyn Clock,
+) -> Result<Vec<ImportRecord>, AppError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_path(path)
+        .map_err(|e| {
+            AppError::UserError(format!("Cannot read CSV file '{}': {e}", path.display()))
+        })?;
+
+    let headers = reader
+        .headers()
+        .map_err(|e| AppError::UserError(format!("Invalid CSV header: {e}")))?
+        .clone();
+    let columns = resolve_columns(&headers, mapping)?;
+
+    let mut records = Vec::new();
+    for record in reader.records() {
+        let parsed = match record {
+            Ok(record) => {
+                let line = record.position().map(|p| p.line()).unwrap_or(0);
+                match parse_row(&record, &columns, clock) {
+                    Ok(entry) => ImportRecord::Entry(entry),
+                    Err(e) => ImportRecord::Invalid(ImportIssue::new(
+                        format!("line {line}"),
+                        e.to_string(),
+                    )),
+                }
+            }
+            Err(e) => {
+                let line = e.position().map(|p| p.line()).unwrap_or(0);
+                ImportRecord::Invalid(ImportIssue::new(format!("line {line}"), e.to_string()))
+            }
+        };
+        records.push(parsed);
+    }
+    Ok(records)
+}
+
+fn parse_row(
+    record: &csv::StringRecord,
+    columns: &ColumnIndex,
+    clock: &dyn Clock,
+) -> Result<ImportEntry, AppError> {
+    let cell = |index: Option<usize>| {
+        index
+            .and_then(|i| record.get(i))
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    };
+
+    let times = super::task::resolve_entry_times(
+        cell(columns.start),
+        cell(columns.end),
+        cell(columns.duration),
+        cell(columns.date),
+        clock,
+    )?;
+
+    Ok(ImportEntry {
+        location: format!("line {}", record.position().map(|p| p.line()).unwrap_or(0)),
+        project: cell(Some(columns.project)).unwrap_or("").to_string(),
+        description: cell(Some(columns.description)).unwrap_or("").to_string(),
+        start_time: times.start_time,
+        end_time: times.end_time,
+        duration_min: times.duration_min,
+        key: None,
+    })
+}
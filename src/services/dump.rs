@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::db::{DUMP_TABLES, Database, Row};
+use crate::error::AppError;
+
+/// Identifies a vibe-clock dump, so other JSON files are rejected early.
+pub const DUMP_FORMAT: &str = "vibe-clock-dump";
+
+/// The complete contents of a database as plain JSON.
+///
+/// Rows are stored column by column exactly as in SQLite, trashed items and the
+/// change history included, so a restore reproduces the database with the same IDs.
+#[derive(Serialize, Deserialize)]
+pub struct Dump {
+    pub format: String,
+    pub schema_version: i64,
+    pub app_version: String,
+    pub exported_at: NaiveDateTime,
+    pub tables: BTreeMap<String, Vec<Row>>,
+}
+
+impl Dump {
+    /// Number of rows in `table`.
+    pub fn count(&self, table: &str) -> usize {
+        self.tables.get(table).map_or(0, Vec::len)
+    }
+}
+
+pub fn export_all(db: &Database, now: NaiveDateTime) -> Result<Dump, AppError> {
+    let mut tables = BTreeMap::new();
+    for table in DUMP_TABLES {
+        tables.insert(table.to_string(), db.dump_table(table)?);
+    }
+    Ok(Dump {
+        format: DUMP_FORMAT.to_string(),
+        schema_version: db.schema_version()?,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: now,
+        tables,
+    })
+}
+
+/// Restore a dump into an empty database in a single transaction.
+pub fn import_all(db: &Database, dump: &Dump) -> Result<(), AppError> {
+    if dump.format != DUMP_FORMAT {
+        return Err(AppError::UserError(format!(
+            "Not a vibe-clock dump (format '{}').",
+            dump.format
+        )));
+    }
+    let current = db.schema_version()?;
+    if dump.schema_version > current {
+        return Err(AppError::UserError(format!(
+            "The dump has schema version {}, but this version of vibe-clock only knows up to {current}. Upgrade vibe-clock first.",
+            dump.schema_version
+        )));
+    }
+    if let Some(unknown) = dump
+        .tables
+        .keys()
+        .find(|t| !DUMP_TABLES.contains(&t.as_str()))
+    {
+        return Err(AppError::UserError(format!(
+            "Unknown table '{unknown}' in dump."
+        )));
+    }
+    if !db.is_empty()? {
+        return Err(AppError::UserError(
+            "The database is not empty. Restore into a new database (set VIBE_CLOCK_DB to a new file)."
+                .to_string(),
+        ));
+    }
+
+    let tx = db.conn.unchecked_transaction()?;
+    for table in DUMP_TABLES {
+        if let Some(rows) = dump.tables.get(table) {
+            db.restore_rows(table, rows)
+                .map_err(|e| AppError::UserError(format!("Cannot restore '{table}': {e}")))?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
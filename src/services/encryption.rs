@@ -0,0 +1,80 @@
+use std::path::{Path, PathBuf};
+
+use crate::db::Database;
+use crate::error::AppError;
+
+/// Encrypt the plaintext database at `plain` with `passphrase`.
+///
+/// Writes to `output`, or replaces `plain` itself when no output is given. Returns the
+/// path of the encrypted file.
+pub fn encrypt(plain: &Path, output: Option<&Path>, passphrase: &str) -> Result<PathBuf, AppError> {
+    if !plain.is_file() {
+        return Err(AppError::UserError(format!(
+            "'{}' not found.",
+            plain.display()
+        )));
+    }
+    let target = match output {
+        Some(path) => {
+            refuse_existing(path)?;
+            path.to_path_buf()
+        }
+        None => plain.to_path_buf(),
+    };
+    let tmp = tmp_path(&target);
+    let _ = std::fs::remove_file(&tmp);
+
+    Database::encrypt_plaintext(plain, &tmp, passphrase).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp);
+        AppError::UserError(e.to_string())
+    })?;
+    std::fs::rename(&tmp, &target)?;
+    Ok(target)
+}
+
+/// Write an unencrypted copy of the database to `output`, which must not exist yet.
+///
+/// Refusing existing files also keeps the live database from being overwritten.
+pub fn decrypt(db: &Database, output: &Path) -> Result<(), AppError> {
+    refuse_existing(output)?;
+    let tmp = tmp_path(output);
+    let _ = std::fs::remove_file(&tmp);
+
+    // SQLite keeps the mode of an existing file, so the plaintext is never readable by others
+    create_private(&tmp)?;
+    db.export_plaintext(&tmp).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp);
+        AppError::SystemError(e.to_string())
+    })?;
+    std::fs::rename(&tmp, output)?;
+    Ok(())
+}
+
+fn refuse_existing(path: &Path) -> Result<(), AppError> {
+    if path.exists() {
+        return Err(AppError::UserError(format!(
+            "'{}' already exists.",
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Create an empty file only its owner can read.
+fn create_private(path: &Path) -> std::io::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        options.mode(0o600);
+    }
+    options.open(path).map(drop)
+}
+
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    PathBuf::from(tmp)
+}
@@ -0,0 +1,84 @@
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::TaskEntry;
+
+/// Marks entries logged as a plain duration in formats that only know intervals,
+/// so importing the file back yields a duration-only entry again.
+pub const DURATION_ONLY_MARKER: &str = "duration-only";
+
+pub struct ExportEntry {
+    pub task: TaskEntry,
+    pub project_name: String,
+}
+
+impl ExportEntry {
+    /// Whether the entry was logged as a duration rather than a start/end interval.
+    pub fn is_duration_only(&self) -> bool {
+        self.task.end_time.is_none()
+    }
+
+    /// Start and end of the entry. Duration-only entries are laid out from midnight of their day.
+    pub fn interval(&self) -> (NaiveDateTime, NaiveDateTime) {
+        let start = self
+            .task
+            .start_time
+            .unwrap_or_else(|| self.task.date().and_time(NaiveTime::MIN));
+        let end = self
+            .task
+            .end_time
+            .unwrap_or(start + Duration::minutes(self.task.duration_min));
+        (start, end)
+    }
+
+    /// The description on a single line.
+    pub fn description(&self) -> String {
+        self.task.description.replace(['\n', '\r'], " ")
+    }
+}
+
+/// Collect the entries to export, oldest first.
+///
+/// Both ends of the date range are optional and inclusive; `project` (name or ID)
+/// restricts the export to a single project.
+pub fn collect_entries(
+    db: &Database,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    project: Option<&str>,
+) -> Result<Vec<ExportEntry>, AppError> {
+    let project_id = project
+        .map(|p| super::find_project(db, p))
+        .transpose()?
+        .map(|p| p.id);
+    let from_str = from.map_or("0000-01-01".to_string(), |d| {
+        d.format("%Y-%m-%d").to_string()
+    });
+    let to_str = to.map_or("9999-12-31".to_string(), |d| {
+        d.format("%Y-%m-%d").to_string()
+    });
+
+    let projects = db.list_projects()?;
+    Ok(db
+        .list_tasks_for_date_range(&from_str, &to_str)?
+        .into_iter()
+        .filter(|task| project_id.is_none_or(|id| task.project_id == id))
+        .map(|task| {
+            let project_name = projects
+                .iter()
+                .find(|p| p.id == task.project_id)
+                .map(|p| p.name.clone())
+                .unwrap_or_else(|| "?".to_string());
+            ExportEntry { task, project_name }
+        })
+        .collect())
+}
+
+/// Convert a stored local time to UTC, for formats that timestamp in UTC.
+pub fn to_utc(local: NaiveDateTime) -> NaiveDateTime {
+    Local
+        .from_local_datetime(&local)
+        .earliest()
+        .map_or(local, |t| t.with_timezone(&Utc).naive_utc())
+}
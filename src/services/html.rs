@@ -0,0 +1,134 @@
+use std::io::Write;
+
+use crate::error::AppError;
+use crate::formatting::format_duration;
+use crate::services::render::{self, ReportRenderer};
+use crate::services::report::Report;
+
+/// Renders reports as a self-contained HTML page (inline styles, no external assets).
+pub struct HtmlRenderer;
+
+const STYLE: &str = "body{font-family:Helvetica,Arial,sans-serif;font-size:14px;margin:2em;color:#222}\
+h1{text-align:center}\
+table{border-collapse:collapse;margin-bottom:1.5em}\
+th,td{padding:4px 10px;text-align:left;border-bottom:1px solid #ddd}\
+th{border-bottom:2px solid #888}\
+td.num{text-align:right}";
+
+/// Escape text for HTML element content.
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+impl ReportRenderer for HtmlRenderer {
+    fn name(&self) -> &'static str {
+        "HTML"
+    }
+
+    fn extension(&self) -> &'static str {
+        "html"
+    }
+
+    fn extension_aliases(&self) -> &'static [&'static str] {
+        &["htm"]
+    }
+
+    fn render(&self, report: &Report, out: &mut dyn Write) -> Result<(), AppError> {
+        let range = format!(
+            "{} to {}",
+            report.from.format("%Y-%m-%d"),
+            report.to.format("%Y-%m-%d")
+        );
+
+        writeln!(out, "<!DOCTYPE html>")?;
+        writeln!(out, "<html lang=\"en\">")?;
+        writeln!(out, "<head>")?;
+        writeln!(out, "<meta charset=\"utf-8\">")?;
+        writeln!(out, "<title>Time Report {range}</title>")?;
+        writeln!(out, "<style>{STYLE}</style>")?;
+        writeln!(out, "</head>")?;
+        writeln!(out, "<body>")?;
+        writeln!(out, "<h1>Time Report</h1>")?;
+        writeln!(out, "<p>{range}</p>")?;
+
+        if report.project_summaries.is_empty() {
+            writeln!(
+                out,
+                "<p>{}</p>",
+                render::no_tasks_message(report, "%Y-%m-%d")
+            )?;
+            writeln!(out, "</body>")?;
+            writeln!(out, "</html>")?;
+            return Ok(());
+        }
+
+        // SECTION 1: Project Summary
+        writeln!(out, "<h2>Project Summary</h2>")?;
+        writeln!(out, "<table>")?;
+        writeln!(out, "<tr><th>Project</th><th>Total Hours</th></tr>")?;
+        let mut sorted_summaries: Vec<_> = report.project_summaries.iter().collect();
+        sorted_summaries.sort_by(|a, b| a.name.cmp(&b.name));
+        for summary in &sorted_summaries {
+            writeln!(
+                out,
+                "<tr><td>{}</td><td class=\"num\">{}</td></tr>",
+                escape(&summary.name),
+                format_duration(summary.total)
+            )?;
+        }
+        writeln!(out, "</table>")?;
+
+        // SECTION 2: Daily Detail
+        writeln!(out, "<h2>Daily Detail</h2>")?;
+        for section in &report.daily_sections {
+            writeln!(out, "<h3>{}</h3>", section.date.format("%Y-%m-%d"))?;
+            writeln!(out, "<table>")?;
+            writeln!(
+                out,
+                "<tr><th>ID</th><th>Description</th><th>Project</th><th>Start</th><th>End</th><th>Duration</th></tr>"
+            )?;
+            for entry in &section.entries {
+                let time = |t: Option<chrono::NaiveDateTime>| {
+                    t.map(|t| t.format("%H:%M").to_string())
+                        .unwrap_or_else(|| "-".to_string())
+                };
+                writeln!(
+                    out,
+                    "<tr><td class=\"num\">{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td></tr>",
+                    render::entry_id(entry, "*"),
+                    escape(&entry.task.description),
+                    escape(&entry.project_name),
+                    time(entry.task.start_time),
+                    time(entry.task.end_time),
+                    format_duration(entry.task.duration_min)
+                )?;
+            }
+            writeln!(out, "</table>")?;
+        }
+
+        writeln!(
+            out,
+            "<p><strong>Grand Total: {}</strong></p>",
+            format_duration(report.grand_total)
+        )?;
+
+        if render::has_edited_entries(report) {
+            writeln!(out, "<p>* {}</p>", render::EDITED_FOOTNOTE)?;
+        }
+
+        writeln!(out, "</body>")?;
+        writeln!(out, "</html>")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,1056 @@
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::Path;
+
+use chrono::{
+    Datelike, Days, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Weekday,
+};
+use chrono_tz::Tz;
+
+use crate::error::AppError;
+use crate::formatting::format_duration;
+use crate::services::export::{self, ExportEntry};
+use crate::services::import::{self, ImportIssue, ImportRecord};
+
+/// UTC timestamp format for `DTSTART`, `DTEND` and `DTSTAMP`.
+const ICS_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Content lines longer than this many octets are folded (RFC 5545, section 3.1).
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Escape a TEXT property value.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace(['\n', '\r'], "\\n")
+}
+
+/// Write one content line, folding it and terminating it with CRLF.
+fn write_line(out: &mut dyn Write, line: &str) -> Result<(), AppError> {
+    let mut rest = line;
+    let mut limit = MAX_LINE_OCTETS;
+    while rest.len() > limit {
+        let mut split = limit;
+        while !rest.is_char_boundary(split) {
+            split -= 1;
+        }
+        write!(out, "{}\r\n ", &rest[..split])?;
+        rest = &rest[split..];
+        // Continuation lines start with a space, which counts towards the limit
+        limit = MAX_LINE_OCTETS - 1;
+    }
+    write!(out, "{rest}\r\n")?;
+    Ok(())
+}
+
+fn utc_stamp(local: NaiveDateTime) -> String {
+    export::to_utc(local).format(ICS_FORMAT).to_string()
+}
+
+/// Write entries as an iCalendar file with one `VEVENT` per entry.
+///
+/// Each event's UID is derived from the task ID, so importing a newer export into a
+/// calendar updates the existing events instead of adding copies. Duration-only entries
+/// have no time of day and become all-day events.
+pub fn write_ics(entries: &[ExportEntry], out: &mut dyn Write) -> Result<(), AppError> {
+    write_line(out, "BEGIN:VCALENDAR")?;
+    write_line(out, "VERSION:2.0")?;
+    write_line(out, "PRODID:-//vibe-clock//vibe-clock//EN")?;
+    write_line(out, "CALSCALE:GREGORIAN")?;
+
+    for entry in entries {
+        let task = &entry.task;
+        let summary = if task.description.is_empty() {
+            entry.project_name.clone()
+        } else {
+            format!("{}: {}", entry.project_name, task.description)
+        };
+
+        write_line(out, "BEGIN:VEVENT")?;
+        write_line(out, &format!("UID:task-{}@vibe-clock", task.id))?;
+        write_line(out, &format!("DTSTAMP:{}", utc_stamp(task.updated_at)))?;
+        write_line(
+            out,
+            &format!("LAST-MODIFIED:{}", utc_stamp(task.updated_at)),
+        )?;
+        if entry.is_duration_only() {
+            let date = task.date();
+            let next = date.checked_add_days(Days::new(1)).unwrap_or(date);
+            write_line(
+                out,
+                &format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")),
+            )?;
+            write_line(out, &format!("DTEND;VALUE=DATE:{}", next.format("%Y%m%d")))?;
+            write_line(out, "TRANSP:TRANSPARENT")?;
+            write_line(
+                out,
+                &format!(
+                    "DESCRIPTION:Logged {} (no start or end time)",
+                    format_duration(task.duration_min)
+                ),
+            )?;
+        } else {
+            let (start, end) = entry.interval();
+            write_line(out, &format!("DTSTART:{}", utc_stamp(start)))?;
+            write_line(out, &format!("DTEND:{}", utc_stamp(end)))?;
+            write_line(
+                out,
+                &format!("DESCRIPTION:Logged {}", format_duration(task.duration_min)),
+            )?;
+        }
+        write_line(out, &format!("SUMMARY:{}", escape(&summary)))?;
+        write_line(out, &format!("CATEGORIES:{}", escape(&entry.project_name)))?;
+        write_line(out, "END:VEVENT")?;
+    }
+
+    write_line(out, "END:VCALENDAR")?;
+    Ok(())
+}
+
+/// Which part of a calendar event a [`ProjectRule`] looks at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RuleField {
+    /// The calendar name (`X-WR-CALNAME`)
+    Calendar,
+    /// Any of the event's `CATEGORIES`
+    Category,
+    /// The event title
+    Summary,
+}
+
+/// Assigns events to a project when a field contains a pattern (case-insensitive).
+#[derive(Debug)]
+pub struct ProjectRule {
+    field: RuleField,
+    pattern: String,
+    project: String,
+}
+
+impl ProjectRule {
+    /// Parse a `--rule` spec such as `category:Client A=Acme` or `standup=Internal`.
+    ///
+    /// Without a `calendar:`, `category:` or `summary:` prefix the summary is matched.
+    pub fn parse(spec: &str) -> Result<Self, AppError> {
+        let invalid = || {
+            AppError::UserError(format!(
+                "Invalid rule '{spec}'. Use [calendar:|category:|summary:]PATTERN=PROJECT."
+            ))
+        };
+        let (matcher, project) = spec.split_once('=').ok_or_else(invalid)?;
+        let (field, pattern) = match matcher.split_once(':') {
+            Some((field, pattern)) => {
+                let field = match field.trim().to_lowercase().as_str() {
+                    "calendar" => RuleField::Calendar,
+                    "category" => RuleField::Category,
+                    "summary" => RuleField::Summary,
+                    _ => return Err(invalid()),
+                };
+                (field, pattern)
+            }
+            None => (RuleField::Summary, matcher),
+        };
+        let (pattern, project) = (pattern.trim(), project.trim());
+        if pattern.is_empty() || project.is_empty() {
+            return Err(invalid());
+        }
+        Ok(ProjectRule {
+            field,
+            pattern: pattern.to_lowercase(),
+            project: project.to_string(),
+        })
+    }
+
+    fn matches(&self, text: &str) -> bool {
+        text.to_lowercase().contains(&self.pattern)
+    }
+}
+
+pub struct IcsImportOptions {
+    /// Rules tried in order; the first match picks the project.
+    pub rules: Vec<ProjectRule>,
+    /// Project for events no rule matches.
+    pub default_project: Option<String>,
+    /// The user's address, to recognise events they declined.
+    pub me: Option<String>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+/// A property after unfolding, with the line it started on.
+struct ContentLine {
+    line: usize,
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl ContentLine {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+fn parse_content_lines(content: &str) -> Vec<ContentLine> {
+    let mut logical: Vec<(usize, String)> = Vec::new();
+    for (index, raw) in content.split('\n').enumerate() {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        if let Some(continuation) = raw.strip_prefix([' ', '\t']) {
+            if let Some((_, last)) = logical.last_mut() {
+                last.push_str(continuation);
+            }
+        } else if !raw.is_empty() {
+            logical.push((index + 1, raw.to_string()));
+        }
+    }
+    logical
+        .into_iter()
+        .filter_map(|(line, text)| parse_content_line(line, &text))
+        .collect()
+}
+
+fn parse_content_line(line: usize, text: &str) -> Option<ContentLine> {
+    // The value starts at the first colon outside a quoted parameter value
+    let mut in_quotes = false;
+    let (colon, _) = text.char_indices().find(|&(_, c)| {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        c == ':' && !in_quotes
+    })?;
+    let mut head = text[..colon].split(';');
+    let name = head.next()?.trim().to_ascii_uppercase();
+    let params = head
+        .filter_map(|p| p.split_once('='))
+        .map(|(k, v)| {
+            (
+                k.trim().to_ascii_uppercase(),
+                v.trim_matches('"').to_string(),
+            )
+        })
+        .collect();
+    Some(ContentLine {
+        line,
+        name,
+        params,
+        value: text[colon + 1..].to_string(),
+    })
+}
+
+/// Undo TEXT escaping; line breaks become spaces since descriptions are single-line.
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match (c, c == '\\') {
+            (_, true) => match chars.next() {
+                Some('n' | 'N') => out.push(' '),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            },
+            _ => out.push(c),
+        }
+    }
+    out.trim().to_string()
+}
+
+struct Event {
+    line: usize,
+    props: Vec<ContentLine>,
+}
+
+impl Event {
+    fn get(&self, name: &str) -> Option<&ContentLine> {
+        self.props.iter().find(|p| p.name == name)
+    }
+
+    fn all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a ContentLine> {
+        self.props.iter().filter(move |p| p.name == name)
+    }
+}
+
+/// Split a calendar into its name and top-level events, ignoring alarms and other components.
+fn collect_events(lines: Vec<ContentLine>) -> (Option<String>, Vec<Event>) {
+    let mut calendar = None;
+    let mut events = Vec::new();
+    let mut stack: Vec<String> = Vec::new();
+    let mut current: Option<Event> = None;
+
+    for line in lines {
+        match line.name.as_str() {
+            "BEGIN" => {
+                let component = line.value.trim().to_ascii_uppercase();
+                if component == "VEVENT" {
+                    current = Some(Event {
+                        line: line.line,
+                        props: Vec::new(),
+                    });
+                }
+                stack.push(component);
+            }
+            "END" => {
+                if stack.pop().as_deref() == Some("VEVENT") {
+                    events.extend(current.take());
+                }
+            }
+            _ => match stack.last().map(String::as_str) {
+                Some("VEVENT") => {
+                    if let Some(event) = current.as_mut() {
+                        event.props.push(line);
+                    }
+                }
+                Some("VCALENDAR") if line.name == "X-WR-CALNAME" => {
+                    calendar = Some(unescape(&line.value));
+                }
+                _ => {}
+            },
+        }
+    }
+    (calendar, events)
+}
+
+/// The time zone a date-time value is expressed in.
+#[derive(Clone, Copy)]
+enum Zone {
+    Utc,
+    Named(Tz),
+    /// No zone given: the time is the same everywhere, so it is taken as local time.
+    Floating,
+}
+
+impl Zone {
+    fn to_local(self, naive: NaiveDateTime) -> NaiveDateTime {
+        match self {
+            Zone::Utc => chrono::Utc
+                .from_utc_datetime(&naive)
+                .with_timezone(&Local)
+                .naive_local(),
+            Zone::Named(tz) => tz
+                .from_local_datetime(&naive)
+                .earliest()
+                .map_or(naive, |t| t.with_timezone(&Local).naive_local()),
+            Zone::Floating => naive,
+        }
+    }
+}
+
+struct EventTime {
+    naive: NaiveDateTime,
+    zone: Zone,
+    all_day: bool,
+}
+
+impl EventTime {
+    fn local(&self) -> NaiveDateTime {
+        self.zone.to_local(self.naive)
+    }
+}
+
+fn parse_time_value(value: &str, tzid: Option<&str>) -> Result<EventTime, String> {
+    let value = value.trim();
+    let invalid = || format!("Invalid date/time '{value}'.");
+    if value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").map_err(|_| invalid())?;
+        return Ok(EventTime {
+            naive: date.and_time(NaiveTime::MIN),
+            zone: Zone::Floating,
+            all_day: true,
+        });
+    }
+    let (text, zone) = match value.strip_suffix('Z') {
+        Some(text) => (text, Zone::Utc),
+        // Unknown zone names (e.g. Windows ones) fall back to local time
+        None => (
+            value,
+            tzid.and_then(|t| t.parse::<Tz>().ok())
+                .map_or(Zone::Floating, Zone::Named),
+        ),
+    };
+    let naive = NaiveDateTime::parse_from_str(text, "%Y%m%dT%H%M%S").map_err(|_| invalid())?;
+    Ok(EventTime {
+        naive,
+        zone,
+        all_day: false,
+    })
+}
+
+fn parse_event_time(prop: &ContentLine) -> Result<EventTime, String> {
+    parse_time_value(&prop.value, prop.param("TZID"))
+}
+
+/// Parse a `DURATION` value such as `PT1H30M` or `P1D`.
+fn parse_ics_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let rest = value.strip_prefix('+').unwrap_or(value).strip_prefix('P')?;
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => in_time = true,
+            _ => {
+                let n: i64 = std::mem::take(&mut number).parse().ok()?;
+                let part = match (c, in_time) {
+                    ('W', false) => Duration::try_weeks(n),
+                    ('D', false) => Duration::try_days(n),
+                    ('H', true) => Duration::try_hours(n),
+                    ('M', true) => Duration::try_minutes(n),
+                    ('S', true) => Duration::try_seconds(n),
+                    _ => None,
+                };
+                total = total.checked_add(&part?)?;
+            }
+        }
+    }
+    // `P` and `PT` alone name no length at all
+    let has_part = rest.ends_with(|c: char| c.is_ascii_alphabetic() && c != 'T');
+    (number.is_empty() && has_part).then_some(total)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// The subset of `RRULE` that is expanded: DAILY, WEEKLY (with BYDAY) and MONTHLY
+/// on the start day, bounded by COUNT or UNTIL.
+struct Recurrence {
+    frequency: Frequency,
+    interval: i64,
+    count: Option<usize>,
+    until: Option<EventTime>,
+    by_day: Vec<Weekday>,
+}
+
+fn parse_rrule(value: &str) -> Result<Recurrence, String> {
+    let mut rule = Recurrence {
+        frequency: Frequency::Daily,
+        interval: 1,
+        count: None,
+        until: None,
+        by_day: Vec::new(),
+    };
+    let mut frequency = None;
+    for part in value.trim().split(';').filter(|p| !p.is_empty()) {
+        let (key, val) = part
+            .split_once('=')
+            .ok_or_else(|| format!("invalid RRULE part '{part}'"))?;
+        match key.to_ascii_uppercase().as_str() {
+            "FREQ" => {
+                frequency = Some(match val.to_ascii_uppercase().as_str() {
+                    "DAILY" => Frequency::Daily,
+                    "WEEKLY" => Frequency::Weekly,
+                    "MONTHLY" => Frequency::Monthly,
+                    other => return Err(format!("{other} recurrence is not supported")),
+                })
+            }
+            "INTERVAL" => {
+                rule.interval = val
+                    .parse()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| format!("invalid INTERVAL '{val}'"))?
+            }
+            "COUNT" => {
+                rule.count = Some(val.parse().map_err(|_| format!("invalid COUNT '{val}'"))?)
+            }
+            "UNTIL" => rule.until = Some(parse_time_value(val, None)?),
+            "BYDAY" => {
+                for day in val.split(',') {
+                    rule.by_day.push(match day.to_ascii_uppercase().as_str() {
+                        "MO" => Weekday::Mon,
+                        "TU" => Weekday::Tue,
+                        "WE" => Weekday::Wed,
+                        "TH" => Weekday::Thu,
+                        "FR" => Weekday::Fri,
+                        "SA" => Weekday::Sat,
+                        "SU" => Weekday::Sun,
+                        other => return Err(format!("BYDAY={other} is not supported")),
+                    });
+                }
+            }
+            "WKST" => {}
+            other => return Err(format!("RRULE {other} is not supported")),
+        }
+    }
+    rule.frequency = frequency.ok_or("RRULE has no FREQ")?;
+    if rule.frequency != Frequency::Weekly && !rule.by_day.is_empty() {
+        return Err("BYDAY is only supported for weekly events".to_string());
+    }
+    Ok(rule)
+}
+
+/// Local start times of a recurring event's occurrences up to the end of `last_day`.
+///
+/// Fails when an occurrence falls outside the representable dates, e.g. for a huge INTERVAL.
+fn expand(
+    start: &EventTime,
+    rule: &Recurrence,
+    last_day: NaiveDate,
+) -> Result<Vec<NaiveDateTime>, String> {
+    let out_of_range = || "recurrence runs past the supported date range".to_string();
+    let first = start.naive;
+    let until = rule.until.as_ref().map(|u| {
+        if u.all_day {
+            u.naive.date().and_hms_opt(23, 59, 59).unwrap_or(u.naive)
+        } else {
+            u.local()
+        }
+    });
+    let week_start = first
+        .date()
+        .checked_sub_days(Days::new(first.weekday().num_days_from_monday().into()))
+        .ok_or_else(out_of_range)?;
+    let shift = |t: NaiveDateTime, length: Option<Duration>| {
+        length
+            .and_then(|d| t.checked_add_signed(d))
+            .ok_or_else(out_of_range)
+    };
+    let mut by_day = rule.by_day.clone();
+    by_day.sort_by_key(|d| d.num_days_from_monday());
+
+    let mut occurrences = Vec::new();
+    let mut seen = 0;
+    // Cap the number of periods so a malformed rule cannot loop for long
+    for period in 0..100_000_i64 {
+        let step = period.checked_mul(rule.interval).ok_or_else(out_of_range)?;
+        let (period_start, candidates): (NaiveDate, Vec<NaiveDateTime>) = match rule.frequency {
+            Frequency::Daily => {
+                let t = shift(first, Duration::try_days(step))?;
+                (t.date(), vec![t])
+            }
+            Frequency::Weekly if by_day.is_empty() => {
+                let t = shift(first, Duration::try_weeks(step))?;
+                (t.date(), vec![t])
+            }
+            Frequency::Weekly => {
+                let week = shift(
+                    week_start.and_time(NaiveTime::MIN),
+                    Duration::try_weeks(step),
+                )?
+                .date();
+                let mut days = Vec::new();
+                for d in &by_day {
+                    let day = week
+                        .checked_add_days(Days::new(d.num_days_from_monday().into()))
+                        .ok_or_else(out_of_range)?
+                        .and_time(first.time());
+                    if day >= first {
+                        days.push(day);
+                    }
+                }
+                (week, days)
+            }
+            Frequency::Monthly => {
+                let months = i64::from(first.month0())
+                    .checked_add(step)
+                    .ok_or_else(out_of_range)?;
+                let year = i32::try_from(months / 12)
+                    .ok()
+                    .and_then(|years| first.year().checked_add(years))
+                    .ok_or_else(out_of_range)?;
+                let month = u32::try_from(months % 12).unwrap_or(0) + 1;
+                let month_start =
+                    NaiveDate::from_ymd_opt(year, month, 1).ok_or_else(out_of_range)?;
+                // Months without the start day (e.g. the 31st) have no occurrence
+                let day = NaiveDate::from_ymd_opt(year, month, first.day());
+                (
+                    month_start,
+                    day.map(|d| d.and_time(first.time())).into_iter().collect(),
+                )
+            }
+        };
+        if period_start > last_day {
+            break;
+        }
+        for t in candidates {
+            let local = start.zone.to_local(t);
+            if until.is_some_and(|u| local > u) || rule.count.is_some_and(|c| seen >= c) {
+                return Ok(occurrences);
+            }
+            seen += 1;
+            if local.date() <= last_day {
+                occurrences.push(local);
+            }
+        }
+    }
+    Ok(occurrences)
+}
+
+/// Read timed events from an iCalendar file.
+///
+/// Cancelled, declined and all-day events are skipped, as are events that have not
+/// happened yet. Recurring events are expanded up to today. Each event is assigned to
+/// the project of the first matching rule, or the default project.
+pub fn read_ics(
+    path: &Path,
+    options: &IcsImportOptions,
+    now: NaiveDateTime,
+) -> Result<Vec<ImportRecord>, AppError> {
+    let content = import::read_source(path)?;
+    let lines = parse_content_lines(&content);
+    if !lines
+        .first()
+        .is_some_and(|l| l.name == "BEGIN" && l.value.trim().eq_ignore_ascii_case("VCALENDAR"))
+    {
+        return Err(AppError::UserError(format!(
+            "'{}' is not an iCalendar file.",
+            path.display()
+        )));
+    }
+    let (calendar, events) = collect_events(lines);
+
+    // Occurrences that were moved or changed are stored as separate events with a RECURRENCE-ID
+    let mut overrides: HashMap<String, HashSet<NaiveDateTime>> = HashMap::new();
+    for event in &events {
+        if let (Some(uid), Some(rid)) = (event.get("UID"), event.get("RECURRENCE-ID")) {
+            if let Ok(time) = parse_event_time(rid) {
+                overrides
+                    .entry(uid.value.trim().to_string())
+                    .or_default()
+                    .insert(time.local());
+            }
+        }
+    }
+
+    let reader = EventReader {
+        calendar: calendar.as_deref(),
+        options,
+        overrides: &overrides,
+        now,
+    };
+    Ok(events.iter().flat_map(|e| reader.records(e)).collect())
+}
+
+struct EventReader<'a> {
+    calendar: Option<&'a str>,
+    options: &'a IcsImportOptions,
+    overrides: &'a HashMap<String, HashSet<NaiveDateTime>>,
+    now: NaiveDateTime,
+}
+
+impl EventReader<'_> {
+    fn in_range(&self, date: NaiveDate) -> bool {
+        self.options.from.is_none_or(|from| date >= from)
+            && self.options.to.is_none_or(|to| date <= to)
+    }
+
+    fn project_for(&self, event: &Event, summary: &str) -> Option<String> {
+        let categories: Vec<String> = event
+            .all("CATEGORIES")
+            .flat_map(|p| p.value.split(','))
+            .map(unescape)
+            .collect();
+        self.options
+            .rules
+            .iter()
+            .find(|rule| match rule.field {
+                RuleField::Calendar => self.calendar.is_some_and(|c| rule.matches(c)),
+                RuleField::Category => categories.iter().any(|c| rule.matches(c)),
+                RuleField::Summary => rule.matches(summary),
+            })
+            .map(|rule| rule.project.clone())
+            .or_else(|| self.options.default_project.clone())
+    }
+
+    fn declined(&self, event: &Event) -> bool {
+        let Some(me) = self.options.me.as_deref() else {
+            return false;
+        };
+        event.all("ATTENDEE").any(|attendee| {
+            let address = attendee.value.trim();
+            let address = address
+                .get(..7)
+                .filter(|p| p.eq_ignore_ascii_case("mailto:"))
+                .map_or(address, |_| &address[7..]);
+            address.eq_ignore_ascii_case(me)
+                && attendee
+                    .param("PARTSTAT")
+                    .is_some_and(|s| s.eq_ignore_ascii_case("DECLINED"))
+        })
+    }
+
+    fn records(&self, event: &Event) -> Vec<ImportRecord> {
+        let location = format!("line {}", event.line);
+        let skip = |reason: &str| vec![ImportRecord::Skipped(ImportIssue::new(&location, reason))];
+        let invalid =
+            |reason: String| vec![ImportRecord::Invalid(ImportIssue::new(&location, reason))];
+
+        let Some(uid) = event.get("UID").map(|p| p.value.trim()) else {
+            return invalid("Event has no UID.".to_string());
+        };
+        let Some(dtstart) = event.get("DTSTART") else {
+            return invalid("Event has no DTSTART.".to_string());
+        };
+        let start = match parse_event_time(dtstart) {
+            Ok(start) => start,
+            Err(e) => return invalid(e),
+        };
+        let recurrence = event.get("RRULE");
+        if recurrence.is_none() && !self.in_range(start.local().date()) {
+            return Vec::new();
+        }
+
+        if start.all_day {
+            return skip("all-day event");
+        }
+        if event
+            .get("STATUS")
+            .is_some_and(|s| s.value.trim().eq_ignore_ascii_case("CANCELLED"))
+        {
+            return skip("cancelled");
+        }
+        if self.declined(event) {
+            return skip("declined");
+        }
+        let summary = event
+            .get("SUMMARY")
+            .map(|p| unescape(&p.value))
+            .unwrap_or_default();
+        let Some(project) = self.project_for(event, &summary) else {
+            return skip("no --rule matches and no --project was given");
+        };
+        let length = match (event.get("DTEND"), event.get("DURATION")) {
+            (Some(end), _) => match parse_event_time(end) {
+                Ok(end) => end.local() - start.local(),
+                Err(e) => return invalid(e),
+            },
+            (None, Some(duration)) => match parse_ics_duration(&duration.value) {
+                Some(length) => length,
+                None => return invalid(format!("Invalid DURATION '{}'.", duration.value.trim())),
+            },
+            (None, None) => return invalid("Event has no DTEND or DURATION.".to_string()),
+        };
+
+        let Some(rrule) = recurrence else {
+            let start_local = start.local();
+            if start_local > self.now {
+                return skip("has not happened yet");
+            }
+            let key = match event.get("RECURRENCE-ID").map(parse_event_time) {
+                Some(Ok(rid)) => format!("ics:{uid}@{}", rid.local().format("%Y%m%dT%H%M%S")),
+                _ => format!("ics:{uid}"),
+            };
+            let Some(end) = start_local.checked_add_signed(length) else {
+                return invalid("Event ends past the supported date range.".to_string());
+            };
+            return vec![import::timed_record(
+                location.clone(),
+                project,
+                summary,
+                start_local,
+                end,
+                key,
+            )];
+        };
+
+        let rule = match parse_rrule(&rrule.value) {
+            Ok(rule) => rule,
+            Err(e) => return skip(&format!("recurring event not imported: {e}")),
+        };
+        let excluded: HashSet<NaiveDateTime> = event
+            .all("EXDATE")
+            .flat_map(|p| {
+                p.value
+                    .split(',')
+                    .filter_map(|v| parse_time_value(v, p.param("TZID")).ok())
+                    .map(|t| t.local())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let moved = self.overrides.get(uid);
+        let last_day = self
+            .options
+            .to
+            .map_or(self.now.date(), |to| to.min(self.now.date()));
+
+        let occurrences = match expand(&start, &rule, last_day) {
+            Ok(occurrences) => occurrences,
+            Err(e) => return invalid(format!("Recurring event not imported: {e}.")),
+        };
+        let mut records = Vec::new();
+        for t in occurrences
+            .into_iter()
+            .filter(|t| self.in_range(t.date()) && *t <= self.now)
+            .filter(|t| !excluded.contains(t) && !moved.is_some_and(|m| m.contains(t)))
+        {
+            let Some(end) = t.checked_add_signed(length) else {
+                return invalid("Event ends past the supported date range.".to_string());
+            };
+            records.push(import::timed_record(
+                format!("{location} ({})", t.format("%Y-%m-%d")),
+                project.clone(),
+                summary.clone(),
+                t,
+                end,
+                format!("ics:{uid}@{}", t.format("%Y%m%dT%H%M%S")),
+            ));
+        }
+        records
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn floating(s: &str) -> EventTime {
+        parse_time_value(s, None).unwrap()
+    }
+
+    fn occurrences(dtstart: &str, rrule: &str, last_day: &str) -> Vec<NaiveDateTime> {
+        expand(
+            &floating(dtstart),
+            &parse_rrule(rrule).unwrap(),
+            date(last_day),
+        )
+        .unwrap()
+    }
+
+    fn calendar(event: &str) -> String {
+        format!("BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\n{event}END:VEVENT\r\nEND:VCALENDAR\r\n")
+    }
+
+    /// The records of the first event in `content`, read on 2026-03-31 into project Acme.
+    fn read_event(content: &str) -> Vec<ImportRecord> {
+        let (_, events) = collect_events(parse_content_lines(content));
+        let options = IcsImportOptions {
+            rules: Vec::new(),
+            default_project: Some("Acme".to_string()),
+            me: None,
+            from: None,
+            to: None,
+        };
+        let reader = EventReader {
+            calendar: None,
+            options: &options,
+            overrides: &HashMap::new(),
+            now: at("2026-03-31 12:00"),
+        };
+        reader.records(&events[0])
+    }
+
+    fn is_invalid(records: &[ImportRecord]) -> bool {
+        matches!(records, [ImportRecord::Invalid(_)])
+    }
+
+    #[test]
+    fn unfolds_continuation_lines() {
+        let lines = parse_content_lines(
+            "BEGIN:VEVENT\r\nSUMMARY:Quarterly plan\r\n ning with\r\n\tthe team\r\nEND:VEVENT\r\n",
+        );
+        let summary = lines.iter().find(|l| l.name == "SUMMARY").unwrap();
+        assert_eq!(summary.value, "Quarterly planning withthe team");
+        assert_eq!(summary.line, 2);
+        assert_eq!(lines.last().unwrap().line, 5);
+    }
+
+    #[test]
+    fn parses_quoted_parameters() {
+        let line = parse_content_line(1, "DTSTART;TZID=\"Europe/Berlin\":20260302T090000").unwrap();
+        assert_eq!(line.name, "DTSTART");
+        assert_eq!(line.param("TZID"), Some("Europe/Berlin"));
+        assert_eq!(line.value, "20260302T090000");
+    }
+
+    #[test]
+    fn unescapes_text() {
+        assert_eq!(
+            unescape(r"Lunch\, then review\; notes"),
+            "Lunch, then review; notes"
+        );
+        assert_eq!(
+            unescape(r"Line one\nLine two\NThree"),
+            "Line one Line two Three"
+        );
+        assert_eq!(unescape(r"C:\\temp"), r"C:\temp");
+        assert_eq!(unescape(r"trailing\"), r"trailing\");
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_ics_duration("PT1H30M"), Some(Duration::minutes(90)));
+        assert_eq!(parse_ics_duration("+PT45M"), Some(Duration::minutes(45)));
+        assert_eq!(parse_ics_duration("P1D"), Some(Duration::days(1)));
+        assert_eq!(
+            parse_ics_duration("P1DT2H"),
+            Some(Duration::days(1) + Duration::hours(2))
+        );
+        assert_eq!(parse_ics_duration("P2W"), Some(Duration::weeks(2)));
+        assert_eq!(parse_ics_duration("PT30S"), Some(Duration::seconds(30)));
+        for invalid in [
+            "1H",
+            "PT",
+            "P1H",
+            "PT1D",
+            "PT1",
+            "PTXM",
+            "P99999999999999999W",
+            "P100000000000000DT2400000000000000H",
+        ] {
+            assert_eq!(parse_ics_duration(invalid), None, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn weekly_by_day_until() {
+        // Monday 2026-03-02; UNTIL is inclusive
+        let times = occurrences(
+            "20260302T090000",
+            "FREQ=WEEKLY;BYDAY=FR,MO;UNTIL=20260313T090000",
+            "2026-12-31",
+        );
+        assert_eq!(
+            times,
+            vec![
+                at("2026-03-02 09:00"),
+                at("2026-03-06 09:00"),
+                at("2026-03-09 09:00"),
+                at("2026-03-13 09:00"),
+            ]
+        );
+    }
+
+    #[test]
+    fn weekly_by_day_skips_days_before_start() {
+        // Wednesday start: the Monday of the first week comes before DTSTART
+        let times = occurrences(
+            "20260304T090000",
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;COUNT=3",
+            "2026-12-31",
+        );
+        assert_eq!(
+            times,
+            vec![
+                at("2026-03-04 09:00"),
+                at("2026-03-16 09:00"),
+                at("2026-03-18 09:00"),
+            ]
+        );
+    }
+
+    #[test]
+    fn monthly_count_skips_short_months() {
+        let times = occurrences("20260131T100000", "FREQ=MONTHLY;COUNT=3", "2026-12-31");
+        assert_eq!(
+            times,
+            vec![
+                at("2026-01-31 10:00"),
+                at("2026-03-31 10:00"),
+                at("2026-05-31 10:00"),
+            ]
+        );
+    }
+
+    #[test]
+    fn monthly_until_date_includes_that_day() {
+        let times = occurrences(
+            "20260115T100000",
+            "FREQ=MONTHLY;UNTIL=20260315",
+            "2026-12-31",
+        );
+        assert_eq!(
+            times,
+            vec![
+                at("2026-01-15 10:00"),
+                at("2026-02-15 10:00"),
+                at("2026-03-15 10:00"),
+            ]
+        );
+    }
+
+    #[test]
+    fn expansion_stops_at_last_day() {
+        let times = occurrences("20260301T090000", "FREQ=DAILY", "2026-03-03");
+        assert_eq!(times.len(), 3);
+    }
+
+    #[test]
+    fn expansion_is_capped() {
+        // 100,000 daily periods from 1700 end long before the last day
+        let times = occurrences("17000101T090000", "FREQ=DAILY", "2026-12-31");
+        assert_eq!(times.len(), 100_000);
+        assert_eq!(times[0], at("1700-01-01 09:00"));
+    }
+
+    #[test]
+    fn huge_intervals_fail_instead_of_overflowing() {
+        for rrule in [
+            "FREQ=DAILY;INTERVAL=1000000000000",
+            "FREQ=DAILY;INTERVAL=9223372036854775807",
+            "FREQ=WEEKLY;INTERVAL=100000000000000",
+            "FREQ=WEEKLY;BYDAY=MO;INTERVAL=100000000000000",
+            "FREQ=MONTHLY;INTERVAL=9223372036854775807",
+        ] {
+            let rule = parse_rrule(rrule).unwrap();
+            assert!(
+                expand(&floating("20260302T090000"), &rule, date("2026-12-31")).is_err(),
+                "{rrule}"
+            );
+        }
+    }
+
+    #[test]
+    fn out_of_range_events_are_invalid() {
+        for event in [
+            "DURATION:P99999999W\r\n",
+            "DURATION:PT1H\r\nRRULE:FREQ=DAILY;INTERVAL=1000000000000\r\n",
+            "DURATION:P99999999W\r\nRRULE:FREQ=DAILY;COUNT=2\r\n",
+        ] {
+            let content = calendar(&format!(
+                "UID:long\r\nSUMMARY:Long\r\nDTSTART:20260302T090000\r\n{event}"
+            ));
+            assert!(is_invalid(&read_event(&content)), "{event}");
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_rules() {
+        assert!(parse_rrule("FREQ=YEARLY").is_err());
+        assert!(parse_rrule("FREQ=MONTHLY;BYDAY=MO").is_err());
+        assert!(parse_rrule("FREQ=WEEKLY;BYSETPOS=1").is_err());
+        assert!(parse_rrule("INTERVAL=2").is_err());
+        assert!(parse_rrule("FREQ=DAILY;INTERVAL=0").is_err());
+    }
+
+    #[test]
+    fn exdate_removes_occurrences() {
+        let content = calendar(
+            "UID:standup\r\nSUMMARY:Standup\r\nDTSTART:20260302T090000\r\nDURATION:PT15M\r\n\
+             RRULE:FREQ=DAILY;COUNT=5\r\nEXDATE:20260303T090000,20260305T090000\r\n",
+        );
+        let starts: Vec<NaiveDateTime> = read_event(&content)
+            .into_iter()
+            .map(|record| match record {
+                ImportRecord::Entry(entry) => {
+                    assert_eq!(entry.duration_min, 15);
+                    entry.start_time.unwrap()
+                }
+                _ => panic!("expected an entry"),
+            })
+            .collect();
+        assert_eq!(
+            starts,
+            vec![
+                at("2026-03-02 09:00"),
+                at("2026-03-04 09:00"),
+                at("2026-03-06 09:00"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use serde::Serialize;
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::{Operation, Project, TaskEntry};
+
+/// An entry read from an external source, ready to be validated and inserted.
+pub struct ImportEntry {
+    /// Where the entry came from, used in error messages (e.g., "line 4").
+    pub location: String,
+    pub project: String,
+    pub description: String,
+    pub start_time: Option<NaiveDateTime>,
+    pub end_time: Option<NaiveDateTime>,
+    pub duration_min: i64,
+    /// Stable identity of the source record (e.g., "watson:<frame id>"). Records whose
+    /// key was already imported are skipped, which makes re-running an import idempotent.
+    pub key: Option<String>,
+}
+
+/// A source record that was not imported, and why.
+#[derive(Serialize)]
+pub struct ImportIssue {
+    pub location: String,
+    pub message: String,
+}
+
+impl ImportIssue {
+    pub fn new(location: impl Into<String>, message: impl Into<String>) -> Self {
+        ImportIssue {
+            location: location.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// One record read from an import source.
+pub enum ImportRecord {
+    Entry(ImportEntry),
+    /// Deliberately ignored (e.g., a still-running interval); doesn't fail the import.
+    Skipped(ImportIssue),
+    /// Unreadable or invalid; fails the import.
+    Invalid(ImportIssue),
+}
+
+pub struct ImportOptions {
+    /// Create projects that do not exist yet instead of rejecting their entries.
+    pub create_projects: bool,
+    /// Validate everything and report what would happen, but change nothing.
+    pub dry_run: bool,
+}
+
+#[derive(Serialize)]
+pub struct ImportedEntry {
+    pub location: String,
+    pub task: TaskEntry,
+    pub project_name: String,
+}
+
+#[derive(Serialize)]
+pub struct ImportSummary {
+    pub dry_run: bool,
+    pub imported: Vec<ImportedEntry>,
+    pub created_projects: Vec<Project>,
+    pub skipped: Vec<ImportIssue>,
+    pub errors: Vec<ImportIssue>,
+}
+
+impl ImportSummary {
+    /// Whether the import was written to the database.
+    pub fn committed(&self) -> bool {
+        !self.dry_run && self.errors.is_empty()
+    }
+}
+
+/// Import a batch of entries in a single transaction.
+///
+/// Every entry goes through the same checks as `task add`. If any entry fails,
+/// or in a dry run, nothing is written; otherwise the batch is recorded as one
+/// undoable operation labelled with `source`.
+pub fn import_entries(
+    db: &Database,
+    source: &str,
+    records: Vec<ImportRecord>,
+    options: &ImportOptions,
+    now: NaiveDateTime,
+) -> Result<ImportSummary, AppError> {
+    let tx = db.conn.unchecked_transaction()?;
+    let mut summary = ImportSummary {
+        dry_run: options.dry_run,
+        imported: Vec::new(),
+        created_projects: Vec::new(),
+        skipped: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    // Keys imported earlier in this batch, with where they came from
+    let mut batch_keys: HashMap<String, String> = HashMap::new();
+
+    for record in records {
+        let entry = match record {
+            ImportRecord::Entry(entry) => entry,
+            ImportRecord::Skipped(issue) => {
+                summary.skipped.push(issue);
+                continue;
+            }
+            ImportRecord::Invalid(issue) => {
+                summary.errors.push(issue);
+                continue;
+            }
+        };
+        if let Some(key) = &entry.key {
+            if let Some(first) = batch_keys.get(key) {
+                summary.skipped.push(ImportIssue::new(
+                    entry.location,
+                    format!("same entry as {first}"),
+                ));
+                continue;
+            }
+            if let Some(id) = db.find_imported_task_id(key)? {
+                summary.skipped.push(ImportIssue::new(
+                    entry.location,
+                    format!("already imported as task {id}"),
+                ));
+                continue;
+            }
+        }
+        match import_entry(db, &entry, options, &mut summary.created_projects, now) {
+            Ok((task, project_name)) => {
+                if let Some(key) = entry.key {
+                    batch_keys.insert(key, entry.location.clone());
+                }
+                summary.imported.push(ImportedEntry {
+                    location: entry.location,
+                    task,
+                    project_name,
+                });
+            }
+            Err(AppError::UserError(message)) => {
+                summary
+                    .errors
+                    .push(ImportIssue::new(entry.location, message));
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    if summary.committed() && !summary.imported.is_empty() {
+        super::undo::record(
+            db,
+            &Operation::Import {
+                source: source.to_string(),
+                projects: summary.created_projects.clone(),
+                tasks: summary.imported.iter().map(|e| e.task.clone()).collect(),
+            },
+            now,
+        )?;
+        tx.commit()?;
+    }
+    // Otherwise the transaction is rolled back when dropped
+    Ok(summary)
+}
+
+fn import_entry(
+    db: &Database,
+    entry: &ImportEntry,
+    options: &ImportOptions,
+    created_projects: &mut Vec<Project>,
+    now: NaiveDateTime,
+) -> Result<(TaskEntry, String), AppError> {
+    let project = resolve_or_create_project(db, &entry.project, options, created_projects, now)?;
+
+    if let (Some(start), Some(end)) = (entry.start_time, entry.end_time) {
+        if end <= start {
+            return Err(AppError::UserError(
+                "End time must be after start time.".to_string(),
+            ));
+        }
+    }
+    if entry.duration_min <= 0 {
+        return Err(AppError::UserError(
+            "Duration must be greater than zero.".to_string(),
+        ));
+    }
+    let date = entry.start_time.map(|t| t.date()).unwrap_or(now.date());
+    super::period::ensure_date_unlocked(db, date)?;
+
+    let task = db.insert_task_entry(
+        project.id,
+        &entry.description,
+        entry.start_time,
+        entry.end_time,
+        entry.duration_min,
+        now,
+    )?;
+    if let Some(key) = &entry.key {
+        db.insert_import_key(key, task.id, now)?;
+    }
+    Ok((task, project.name))
+}
+
+fn resolve_or_create_project(
+    db: &Database,
+    name: &str,
+    options: &ImportOptions,
+    created_projects: &mut Vec<Project>,
+    now: NaiveDateTime,
+) -> Result<Project, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::UserError("Project name is empty.".to_string()));
+    }
+    if let Some(project) = db.find_project_by_name(name)? {
+        return Ok(project);
+    }
+    if !options.create_projects {
+        return Err(AppError::UserError(format!(
+            "Project '{name}' not found (projects are not created with --no-create)."
+        )));
+    }
+    super::project::ensure_not_in_trash(db, name)?;
+
+    let project = db.insert_project(name, now)?;
+    created_projects.push(project.clone());
+    Ok(project)
+}
+
+/// Build the record for a timed interval read from another tracker.
+///
+/// Intervals shorter than a minute are skipped, since entries are kept in whole minutes.
+pub fn timed_record(
+    location: String,
+    project: String,
+    description: String,
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    key: String,
+) -> ImportRecord {
+    if end <= start {
+        return ImportRecord::Invalid(ImportIssue::new(
+            location,
+            "End time must be after start time.",
+        ));
+    }
+    let duration_min = (end - start).num_minutes();
+    if duration_min < 1 {
+        return ImportRecord::Skipped(ImportIssue::new(location, "shorter than a minute"));
+    }
+    ImportRecord::Entry(ImportEntry {
+        location,
+        project,
+        description,
+        start_time: Some(start),
+        end_time: Some(end),
+        duration_min,
+        key: Some(key),
+    })
+}
+
+/// Build the record for an entry exported as duration-only, anchored to midnight of `date`.
+pub fn duration_record(
+    location: String,
+    project: String,
+    description: String,
+    date: NaiveDate,
+    duration_min: i64,
+    key: String,
+) -> ImportRecord {
+    if duration_min < 1 {
+        return ImportRecord::Skipped(ImportIssue::new(location, "shorter than a minute"));
+    }
+    ImportRecord::Entry(ImportEntry {
+        location,
+        project,
+        description,
+        start_time: Some(date.and_time(NaiveTime::MIN)),
+        end_time: None,
+        duration_min,
+        key: Some(key),
+    })
+}
+
+/// Read an import file into memory, reporting a missing or unreadable file as a user error.
+pub fn read_source(path: &Path) -> Result<String, AppError> {
+    std::fs::read_to_string(path)
+        .map_err(|e| AppError::UserError(format!("Cannot read '{}': {e}", path.display())))
+}
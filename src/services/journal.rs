@@ -1,13 +1,15 @@
 use chrono::NaiveDate;
+use serde::Serialize;
 
 use crate::db::Database;
 use crate::error::AppError;
-use crate::models::TaskEntry;
+use crate::services::report::{DailyEntry, ProjectSummary};
 
+#[derive(Serialize)]
 pub struct DailyJournal {
     pub date: NaiveDate,
-    pub tasks: Vec<TaskEntry>,
-    pub project_totals: Vec<(String, i64)>,
+    pub tasks: Vec<DailyEntry>,
+    pub project_totals: Vec<ProjectSummary>,
     pub grand_total: i64,
 }
 
@@ -16,10 +18,11 @@ pub fn get_daily_journal(db: &Database, date: NaiveDate) -> Result<DailyJournal,
     let tasks = db.list_tasks_for_date(&date_str)?;
 
     let projects = db.list_projects()?;
-    let mut project_totals: Vec<(String, i64)> = Vec::new();
+    let mut entries: Vec<DailyEntry> = Vec::new();
+    let mut project_totals: Vec<ProjectSummary> = Vec::new();
     let mut grand_total: i64 = 0;
 
-    for task in &tasks {
+    for task in tasks {
         grand_total += task.duration_min;
         let project_name = projects
             .iter()
@@ -27,19 +30,25 @@ pub fn get_daily_journal(db: &Database, date: NaiveDate) -> Result<DailyJournal,
             .map(|p| p.name.clone())
             .unwrap_or_else(|| "?".to_string());
 
-        if let Some(entry) = project_totals
-            .iter_mut()
-            .find(|(name, _)| name == &project_name)
-        {
-            entry.1 += task.duration_min;
+        if let Some(entry) = project_totals.iter_mut().find(|s| s.name == project_name) {
+            entry.total += task.duration_min;
         } else {
-            project_totals.push((project_name, task.duration_min));
+            project_totals.push(ProjectSummary {
+                name: project_name.clone(),
+                total: task.duration_min,
+            });
         }
+
+        entries.push(DailyEntry {
+            task,
+            project_name,
+            edited: false,
+        });
     }
 
     Ok(DailyJournal {
         date,
-        tasks,
+        tasks: entries,
         project_totals,
         grand_total,
     })
@@ -0,0 +1,108 @@
+use std::io::Write;
+
+use crate::error::AppError;
+use crate::formatting::format_duration;
+use crate::services::render::{self, ReportRenderer};
+use crate::services::report::Report;
+
+/// Renders reports as Markdown tables, suitable for wikis.
+pub struct MarkdownRenderer;
+
+/// Escape text for a Markdown table cell.
+fn cell(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace('<', "&lt;")
+        .replace(['\n', '\r'], " ")
+}
+
+impl ReportRenderer for MarkdownRenderer {
+    fn name(&self) -> &'static str {
+        "Markdown"
+    }
+
+    fn extension(&self) -> &'static str {
+        "md"
+    }
+
+    fn extension_aliases(&self) -> &'static [&'static str] {
+        &["markdown"]
+    }
+
+    fn render(&self, report: &Report, out: &mut dyn Write) -> Result<(), AppError> {
+        writeln!(out, "# Time Report")?;
+        writeln!(out)?;
+        writeln!(
+            out,
+            "{} to {}",
+            report.from.format("%Y-%m-%d"),
+            report.to.format("%Y-%m-%d")
+        )?;
+        writeln!(out)?;
+
+        if report.project_summaries.is_empty() {
+            writeln!(out, "{}", render::no_tasks_message(report, "%Y-%m-%d"))?;
+            return Ok(());
+        }
+
+        // SECTION 1: Project Summary
+        writeln!(out, "## Project Summary")?;
+        writeln!(out)?;
+        writeln!(out, "| Project | Total Hours |")?;
+        writeln!(out, "|---|---:|")?;
+        let mut sorted_summaries: Vec<_> = report.project_summaries.iter().collect();
+        sorted_summaries.sort_by(|a, b| a.name.cmp(&b.name));
+        for summary in &sorted_summaries {
+            writeln!(
+                out,
+                "| {} | {} |",
+                cell(&summary.name),
+                format_duration(summary.total)
+            )?;
+        }
+        writeln!(out)?;
+
+        // SECTION 2: Daily Detail
+        writeln!(out, "## Daily Detail")?;
+        for section in &report.daily_sections {
+            writeln!(out)?;
+            writeln!(out, "### {}", section.date.format("%Y-%m-%d"))?;
+            writeln!(out)?;
+            writeln!(
+                out,
+                "| ID | Description | Project | Start | End | Duration |"
+            )?;
+            writeln!(out, "|---:|---|---|---|---|---:|")?;
+            for entry in &section.entries {
+                let time = |t: Option<chrono::NaiveDateTime>| {
+                    t.map(|t| t.format("%H:%M").to_string())
+                        .unwrap_or_else(|| "-".to_string())
+                };
+                writeln!(
+                    out,
+                    "| {} | {} | {} | {} | {} | {} |",
+                    render::entry_id(entry, "\\*"),
+                    cell(&entry.task.description),
+                    cell(&entry.project_name),
+                    time(entry.task.start_time),
+                    time(entry.task.end_time),
+                    format_duration(entry.task.duration_min)
+                )?;
+            }
+        }
+        writeln!(out)?;
+
+        writeln!(
+            out,
+            "**Grand Total: {}**",
+            format_duration(report.grand_total)
+        )?;
+
+        if render::has_edited_entries(report) {
+            writeln!(out)?;
+            writeln!(out, "\\* {}", render::EDITED_FOOTNOTE)?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,18 +1,110 @@
+pub mod backup;
+pub mod check;
 pub mod clock;
+pub mod csv_export;
+pub mod csv_import;
+pub mod dump;
+pub mod encryption;
+pub mod export;
+pub mod html;
+pub mod ics;
+pub mod import;
 pub mod journal;
+pub mod markdown;
 pub mod pdf;
+pub mod period;
+pub mod profile;
 pub mod project;
+pub mod rekey;
+pub mod render;
 pub mod report;
 pub mod task;
+pub mod template;
+pub mod timeclock;
+pub mod timewarrior;
+pub mod trash;
+pub mod undo;
+pub mod watson;
 
+use std::cell::OnceCell;
+
+use crate::config::{self, DirectoryDefaults, Settings};
 use crate::db::Database;
 use crate::error::AppError;
+use crate::models::Project;
+
+/// Where a project left out on the command line comes from.
+///
+/// The `.vibe-clock` lookup only runs once a field is actually missing.
+#[derive(Default, Debug)]
+pub struct ProjectDefaults {
+    /// The nearest `.vibe-clock` file above the working directory, once looked up.
+    directory: OnceCell<Option<DirectoryDefaults>>,
+    /// `defaults.project` from the config file.
+    pub configured: Option<String>,
+}
+
+impl ProjectDefaults {
+    /// Defaults from the config file; the working directory is read on first use.
+    pub fn new(settings: &Settings) -> Self {
+        Self {
+            directory: OnceCell::new(),
+            configured: settings.defaults.project.clone(),
+        }
+    }
+
+    /// The nearest `.vibe-clock` file above the working directory.
+    pub fn directory(&self) -> Result<Option<&DirectoryDefaults>, AppError> {
+        if let Some(directory) = self.directory.get() {
+            return Ok(directory.as_ref());
+        }
+        let cwd = std::env::current_dir()?;
+        let directory = config::find_directory_defaults(&cwd)
+            .map_err(|e| AppError::UserError(format!("{e:#}")))?;
+        Ok(self.directory.get_or_init(|| directory).as_ref())
+    }
+
+    /// Description from the `.vibe-clock` file, if it has one.
+    pub fn description(&self) -> Result<Option<&str>, AppError> {
+        Ok(self
+            .directory()?
+            .and_then(|directory| directory.description.as_deref()))
+    }
+}
 
-/// Resolve a project by name or ID.
+/// Resolve the project of a new entry.
+///
+/// Priority:
+/// 1. `name_or_id`, when given
+/// 2. `project` in the nearest `.vibe-clock` file
+/// 3. `defaults.project` in the config file
 pub fn resolve_project(
     db: &Database,
-    name_or_id: &str,
-) -> Result<crate::models::Project, AppError> {
+    name_or_id: Option<&str>,
+    defaults: &ProjectDefaults,
+) -> Result<Project, AppError> {
+    if let Some(name_or_id) = name_or_id {
+        return find_project(db, name_or_id);
+    }
+    if let Some(directory) = defaults.directory()? {
+        if let Some(name) = &directory.project {
+            return find_project(db, name).map_err(|e| {
+                AppError::UserError(format!("{e} (from {})", directory.path.display()))
+            });
+        }
+    }
+    if let Some(name) = &defaults.configured {
+        return find_project(db, name)
+            .map_err(|e| AppError::UserError(format!("{e} (from defaults.project)")));
+    }
+    Err(AppError::UserError(format!(
+        "Missing project. Provide a project name, use --template, add a {} file, or set defaults.project.",
+        config::DIRECTORY_FILE
+    )))
+}
+
+/// Find a project by name or ID.
+pub fn find_project(db: &Database, name_or_id: &str) -> Result<Project, AppError> {
     // Try as ID first
     if let Ok(id) = name_or_id.parse::<i64>() {
         if let Some(project) = db.find_project_by_id(id)? {
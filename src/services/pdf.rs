@@ -1,3 +1,4 @@
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use chrono::NaiveDate;
@@ -8,6 +9,7 @@ use genpdfi::{Alignment, Document, Element, Margins, SimplePageDecorator};
 
 use crate::error::AppError;
 use crate::formatting::format_duration;
+use crate::services::render::{self, ReportRenderer};
 use crate::services::report::Report;
 
 const FONT_REGULAR: &[u8] = include_bytes!("../../assets/fonts/LiberationSans-Regular.ttf");
@@ -31,36 +33,124 @@ fn bold_string(s: &str) -> style::StyledString {
     style::StyledString::new(s.to_owned(), Style::new().bold(), None)
 }
 
+/// Layout and formatting choices for PDF reports.
+#[derive(Clone, Debug)]
+pub struct PdfOptions {
+    pub title: String,
+    pub font_size: u8,
+    /// Page margins in millimetres.
+    pub margins: u8,
+    pub date_format: String,
+    pub time_format: String,
+}
+
+impl Default for PdfOptions {
+    fn default() -> Self {
+        Self {
+            title: "Time Report".to_string(),
+            font_size: 10,
+            margins: 15,
+            date_format: "%Y-%m-%d".to_string(),
+            time_format: "%H:%M".to_string(),
+        }
+    }
+}
+
+/// Renders reports as PDF documents.
+#[derive(Default)]
+pub struct PdfRenderer {
+    pub options: PdfOptions,
+}
+
+impl ReportRenderer for PdfRenderer {
+    fn name(&self) -> &'static str {
+        "PDF"
+    }
+
+    fn extension(&self) -> &'static str {
+        "pdf"
+    }
+
+    fn is_binary(&self) -> bool {
+        true
+    }
+
+    fn render(&self, report: &Report, out: &mut dyn Write) -> Result<(), AppError> {
+        build_document(report, &self.options)?
+            .render(out)
+            .map_err(|e| AppError::SystemError(format!("Failed to write PDF: {e}")))
+    }
+
+    fn render_to_file(&self, report: &Report, path: &Path) -> Result<(), AppError> {
+        render_pdf(report, path, &self.options)
+    }
+}
+
 /// Render a report as a PDF file at the given output path.
-pub fn render_pdf(report: &Report, output_path: &Path) -> Result<(), AppError> {
+pub fn render_pdf(
+    report: &Report,
+    output_path: &Path,
+    options: &PdfOptions,
+) -> Result<(), AppError> {
+    let doc = build_document(report, options)?;
+
+    // Atomic write: temp file -> rename
+    let tmp_path = output_path.with_extension("pdf.tmp");
+    doc.render_to_file(&tmp_path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp_path);
+        AppError::SystemError(format!(
+            "Failed to write PDF: {e}. No partial file was created."
+        ))
+    })?;
+
+    std::fs::rename(&tmp_path, output_path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp_path);
+        AppError::SystemError(format!(
+            "Failed to write PDF to '{}': {e}",
+            output_path.display()
+        ))
+    })?;
+
+    Ok(())
+}
+
+fn build_document(report: &Report, options: &PdfOptions) -> Result<Document, AppError> {
     let font_family = load_font_family()?;
     let mut doc = Document::new(font_family);
-    doc.set_title("Time Report");
+    doc.set_title(options.title.clone());
 
     let mut decorator = SimplePageDecorator::new();
-    decorator.set_margins(Margins::vh(15, 15));
+    decorator.set_margins(Margins::vh(options.margins, options.margins));
     doc.set_page_decorator(decorator);
-    doc.set_font_size(10);
+    doc.set_font_size(options.font_size);
 
     // Header
     let mut title = Paragraph::new("");
-    title.push(bold_string("Time Report"));
+    title.push(bold_string(&options.title));
     title.set_alignment(Alignment::Center);
     doc.push(title);
 
     doc.push(Paragraph::new(format!(
         "{} to {}",
-        report.from.format("%Y-%m-%d"),
-        report.to.format("%Y-%m-%d")
+        report.from.format(&options.date_format),
+        report.to.format(&options.date_format)
     )));
 
     doc.push(Paragraph::new(format!(
         "Generated: {}",
-        chrono::Local::now().format("%Y-%m-%d %H:%M")
+        chrono::Local::now().format(&format!("{} {}", options.date_format, options.time_format))
     )));
 
     doc.push(genpdfi::elements::Break::new(1.5));
 
+    if report.project_summaries.is_empty() {
+        doc.push(Paragraph::new(render::no_tasks_message(
+            report,
+            &options.date_format,
+        )));
+        return Ok(doc);
+    }
+
     // SECTION 1: Project Summary
     let mut summary_heading = Paragraph::new("");
     summary_heading.push(bold_string("Project Summary"));
@@ -102,7 +192,7 @@ pub fn render_pdf(report: &Report, output_path: &Path) -> Result<(), AppError> {
     doc.push(genpdfi::elements::Break::new(0.5));
 
     for section in &report.daily_sections {
-        let date_str = section.date.format("%Y-%m-%d").to_string();
+        let date_str = section.date.format(&options.date_format).to_string();
         let mut date_heading = Paragraph::new("");
         date_heading.push(bold_string(&date_str));
         doc.push(date_heading);
@@ -128,18 +218,18 @@ pub fn render_pdf(report: &Report, output_path: &Path) -> Result<(), AppError> {
             let start = entry
                 .task
                 .start_time
-                .map(|t| t.format("%H:%M").to_string())
+                .map(|t| t.format(&options.time_format).to_string())
                 .unwrap_or_else(|| "-".to_string());
 
             let end = entry
                 .task
                 .end_time
-                .map(|t| t.format("%H:%M").to_string())
+                .map(|t| t.format(&options.time_format).to_string())
                 .unwrap_or_else(|| "-".to_string());
 
             let row = table.row();
             let row = row
-                .element(Text::new(entry.task.id.to_string()))
+                .element(Text::new(render::entry_id(entry, "*")))
                 .element(Paragraph::new(entry.task.description.clone()))
                 .element(Text::new(entry.project_name.clone()))
                 .element(Text::new(start))
@@ -162,24 +252,11 @@ pub fn render_pdf(report: &Report, output_path: &Path) -> Result<(), AppError> {
     )));
     doc.push(total_para);
 
-    // Atomic write: temp file -> rename
-    let tmp_path = output_path.with_extension("pdf.tmp");
-    doc.render_to_file(&tmp_path).map_err(|e| {
-        let _ = std::fs::remove_file(&tmp_path);
-        AppError::SystemError(format!(
-            "Failed to write PDF: {e}. No partial file was created."
-        ))
-    })?;
-
-    std::fs::rename(&tmp_path, output_path).map_err(|e| {
-        let _ = std::fs::remove_file(&tmp_path);
-        AppError::SystemError(format!(
-            "Failed to write PDF to '{}': {e}",
-            output_path.display()
-        ))
-    })?;
+    if render::has_edited_entries(report) {
+        doc.push(Paragraph::new(format!("* {}", render::EDITED_FOOTNOTE)));
+    }
 
-    Ok(())
+    Ok(doc)
 }
 
 /// Resolve the output path for a PDF file based on CLI flags.
@@ -192,37 +269,5 @@ pub fn resolve_pdf_path(
     from: NaiveDate,
     to: NaiveDate,
 ) -> Result<Option<PathBuf>, AppError> {
-    let auto_name = if from == to {
-        format!("report-{}.pdf", from.format("%Y-%m-%d"))
-    } else {
-        format!(
-            "report-{}-to-{}.pdf",
-            from.format("%Y-%m-%d"),
-            to.format("%Y-%m-%d")
-        )
-    };
-
-    if let Some(out) = output {
-        let path = PathBuf::from(out);
-        let resolved = if path.extension().is_some_and(|ext| ext == "pdf") {
-            path
-        } else {
-            path.join(&auto_name)
-        };
-
-        if let Some(parent) = resolved.parent() {
-            if !parent.as_os_str().is_empty() && !parent.exists() {
-                return Err(AppError::UserError(format!(
-                    "Directory '{}' does not exist.",
-                    parent.display()
-                )));
-            }
-        }
-
-        Ok(Some(resolved))
-    } else if pdf_flag {
-        Ok(Some(PathBuf::from(&auto_name)))
-    } else {
-        Ok(None)
-    }
+    render::resolve_output_path(output, "pdf", &[], pdf_flag, from, to)
 }
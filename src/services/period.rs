@@ -0,0 +1,79 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::Serialize;
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::{PeriodLockEvent, TaskEntry};
+
+/// Lock all entries dated on or before `locked_to` against modification.
+///
+/// The lock can only be extended; shrinking it requires an explicit unlock first.
+pub fn lock_period(
+    db: &Database,
+    locked_to: NaiveDate,
+    now: NaiveDateTime,
+) -> Result<(), AppError> {
+    if let Some(current) = db.get_period_lock()? {
+        if locked_to < current {
+            return Err(AppError::UserError(format!(
+                "Entries up to {} are already locked. Run 'vibe-clock period unlock' before locking an earlier date.",
+                current.format("%Y-%m-%d")
+            )));
+        }
+    }
+
+    let tx = db.conn.unchecked_transaction()?;
+    db.set_period_lock(locked_to, now)?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Remove the period lock. Returns the previously locked date.
+pub fn unlock_period(db: &Database, now: NaiveDateTime) -> Result<NaiveDate, AppError> {
+    let current = db
+        .get_period_lock()?
+        .ok_or_else(|| AppError::UserError("No period is locked.".to_string()))?;
+
+    let tx = db.conn.unchecked_transaction()?;
+    db.clear_period_lock(now)?;
+    tx.commit()?;
+    Ok(current)
+}
+
+#[derive(Serialize)]
+pub struct PeriodStatus {
+    pub locked_to: Option<NaiveDate>,
+    pub history: Vec<PeriodLockEvent>,
+}
+
+pub fn period_status(db: &Database) -> Result<PeriodStatus, AppError> {
+    Ok(PeriodStatus {
+        locked_to: db.get_period_lock()?,
+        history: db.list_period_lock_history()?,
+    })
+}
+
+/// Refuse changes to entries dated inside the locked period.
+pub fn ensure_date_unlocked(db: &Database, date: NaiveDate) -> Result<(), AppError> {
+    if let Some(locked_to) = db.get_period_lock()? {
+        if date <= locked_to {
+            return Err(AppError::UserError(format!(
+                "{} is in a locked period (locked up to {}). Use 'vibe-clock period unlock' to modify it.",
+                date.format("%Y-%m-%d"),
+                locked_to.format("%Y-%m-%d")
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Refuse changes that would add, alter or hide any of the given entries in the locked period.
+pub fn ensure_tasks_unlocked<'a>(
+    db: &Database,
+    tasks: impl IntoIterator<Item = &'a TaskEntry>,
+) -> Result<(), AppError> {
+    for task in tasks {
+        ensure_date_unlocked(db, task.date())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::config;
+use crate::db::Database;
+use crate::error::AppError;
+
+#[derive(Serialize)]
+pub struct ProfileInfo {
+    pub name: String,
+    pub active: bool,
+    pub path: PathBuf,
+}
+
+fn user_error(err: anyhow::Error) -> AppError {
+    AppError::UserError(err.to_string())
+}
+
+/// All profiles, marking `active`.
+pub fn list_profiles(active: &str) -> Result<Vec<ProfileInfo>, AppError> {
+    config::list_profiles()?
+        .into_iter()
+        .map(|name| {
+            Ok(ProfileInfo {
+                path: config::profile_db_path(&name)?,
+                active: name == active,
+                name,
+            })
+        })
+        .collect()
+}
+
+/// Refuse names that are invalid or already taken, before a passphrase is asked for.
+pub fn ensure_new_profile(name: &str) -> Result<(), AppError> {
+    config::validate_profile_name(name).map_err(user_error)?;
+    if config::profile_exists(name)? {
+        return Err(AppError::UserError(format!(
+            "Profile '{name}' already exists."
+        )));
+    }
+    Ok(())
+}
+
+/// Create the database of a new profile, encrypted with `passphrase`.
+pub fn create_profile(name: &str, passphrase: &str, active: &str) -> Result<ProfileInfo, AppError> {
+    ensure_new_profile(name)?;
+    let path = config::profile_db_path(name)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    Database::open(&path, passphrase)?;
+    Ok(ProfileInfo {
+        name: name.to_string(),
+        active: name == active,
+        path,
+    })
+}
+
+/// Make `name` the profile used by later invocations.
+pub fn use_profile(name: &str) -> Result<ProfileInfo, AppError> {
+    config::validate_profile_name(name).map_err(user_error)?;
+    if !config::profile_exists(name)? {
+        return Err(AppError::UserError(format!(
+            "Profile '{name}' does not exist. Create it with: vibe-clock profile create {name}"
+        )));
+    }
+    config::set_active_profile(name)?;
+    Ok(ProfileInfo {
+        name: name.to_string(),
+        active: true,
+        path: config::profile_db_path(name)?,
+    })
+}
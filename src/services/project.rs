@@ -2,7 +2,7 @@ use chrono::NaiveDateTime;
 
 use crate::db::Database;
 use crate::error::AppError;
-use crate::models::Project;
+use crate::models::{Operation, Project};
 
 pub fn create_project(db: &Database, name: &str, now: NaiveDateTime) -> Result<Project, AppError> {
     let name = name.trim();
@@ -17,8 +17,30 @@ pub fn create_project(db: &Database, name: &str, now: NaiveDateTime) -> Result<P
             "Project '{name}' already exists."
         )));
     }
+    ensure_not_in_trash(db, name)?;
 
-    Ok(db.insert_project(name, now)?)
+    let tx = db.conn.unchecked_transaction()?;
+    let project = db.insert_project(name, now)?;
+    super::undo::record(
+        db,
+        &Operation::CreateProject {
+            project: project.clone(),
+        },
+        now,
+    )?;
+    tx.commit()?;
+    Ok(project)
+}
+
+/// Trashed projects keep their name reserved until restored or purged.
+pub(crate) fn ensure_not_in_trash(db: &Database, name: &str) -> Result<(), AppError> {
+    if let Some(trashed) = db.find_deleted_project_by_name(name)? {
+        return Err(AppError::UserError(format!(
+            "Project '{name}' is in the trash. Restore it with 'vibe-clock trash restore {} --project' or empty the trash first.",
+            trashed.id
+        )));
+    }
+    Ok(())
 }
 
 pub fn list_projects(db: &Database) -> Result<Vec<Project>, AppError> {
@@ -30,7 +52,7 @@ pub fn rename_project(
     id: i64,
     new_name: &str,
     now: NaiveDateTime,
-) -> Result<(), AppError> {
+) -> Result<Project, AppError> {
     let new_name = new_name.trim();
     if new_name.is_empty() {
         return Err(AppError::UserError(
@@ -38,11 +60,9 @@ pub fn rename_project(
         ));
     }
 
-    if db.find_project_by_id(id)?.is_none() {
-        return Err(AppError::UserError(format!(
-            "Project with ID {id} not found."
-        )));
-    }
+    let project = db
+        .find_project_by_id(id)?
+        .ok_or_else(|| AppError::UserError(format!("Project with ID {id} not found.")))?;
 
     if let Some(existing) = db.find_project_by_name(new_name)? {
         if existing.id != id {
@@ -51,12 +71,31 @@ pub fn rename_project(
             )));
         }
     }
+    ensure_not_in_trash(db, new_name)?;
 
+    let tx = db.conn.unchecked_transaction()?;
     db.update_project_name(id, new_name, now)?;
-    Ok(())
+    super::undo::record(
+        db,
+        &Operation::RenameProject {
+            id,
+            old_name: project.name,
+            new_name: new_name.to_string(),
+        },
+        now,
+    )?;
+    tx.commit()?;
+
+    db.find_project_by_id(id)?
+        .ok_or_else(|| AppError::SystemError(format!("Project {id} vanished during rename.")))
 }
 
-pub fn delete_project(db: &Database, id: i64, force: bool) -> Result<DeleteResult, AppError> {
+pub fn delete_project(
+    db: &Database,
+    id: i64,
+    force: bool,
+    now: NaiveDateTime,
+) -> Result<DeleteResult, AppError> {
     let project = db
         .find_project_by_id(id)?
         .ok_or_else(|| AppError::UserError(format!("Project with ID {id} not found.")))?;
@@ -70,18 +109,39 @@ pub fn delete_project(db: &Database, id: i64, force: bool) -> Result<DeleteResul
         });
     }
 
+    // Capture everything the cascade removes so the deletion can be undone
+    let tasks = db.list_tasks_for_project(id)?;
+    super::period::ensure_tasks_unlocked(db, &tasks)?;
+    let templates = db
+        .list_task_templates()?
+        .into_iter()
+        .filter(|t| t.project_id == id)
+        .collect();
+    let clock = db.get_clock_state()?.filter(|c| c.project_id == id);
+
+    let tx = db.conn.unchecked_transaction()?;
+
     // Stop running clock if it belongs to this project
-    if let Some(clock_state) = db.get_clock_state()? {
-        if clock_state.project_id == id {
-            db.delete_clock_state()?;
-        }
+    if clock.is_some() {
+        db.delete_clock_state()?;
     }
 
-    db.delete_project(id)?;
-    Ok(DeleteResult::Deleted { name: project.name })
+    db.delete_project(id, now)?;
+    super::undo::record(
+        db,
+        &Operation::DeleteProject {
+            project: project.clone(),
+            tasks,
+            templates,
+            clock,
+        },
+        now,
+    )?;
+    tx.commit()?;
+    Ok(DeleteResult::Deleted { project })
 }
 
 pub enum DeleteResult {
-    Deleted { name: String },
+    Deleted { project: Project },
     NeedsConfirmation { name: String, task_count: i64 },
 }
@@ -0,0 +1,103 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::crypto;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::services::backup;
+
+#[derive(Serialize)]
+pub struct RekeyResult {
+    /// Whether the keyring now holds the new passphrase. It is left alone when the
+    /// passphrase comes from `VIBE_CLOCK_KEY`, a key file or a key command.
+    pub keyring_updated: bool,
+    /// Why the keyring could not be updated, although the database was rekeyed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyring_error: Option<String>,
+    /// Backups in the backup directory that now use the new passphrase.
+    pub backups_rekeyed: Vec<PathBuf>,
+    /// Backups that still need their old passphrase, with the reason.
+    pub backups_not_rekeyed: Vec<BackupNotRekeyed>,
+}
+
+#[derive(Serialize)]
+pub struct BackupNotRekeyed {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+/// Change the database passphrase of `profile` from `current` to `new`.
+///
+/// `current` must match the passphrase the database was opened with, and `confirmation`
+/// must repeat `new`. The keyring is only touched once the database has been rekeyed.
+/// Backups in the backup directory are re-encrypted too, so they can still be restored;
+/// one that fails is reported and left under the old passphrase.
+pub fn rekey(
+    db: &Database,
+    db_path: &Path,
+    profile: &str,
+    passphrase: &str,
+    current: &str,
+    new: &str,
+    confirmation: &str,
+) -> Result<RekeyResult, AppError> {
+    if current != passphrase {
+        return Err(AppError::UserError(
+            "Current passphrase is incorrect.".to_string(),
+        ));
+    }
+    if new != confirmation {
+        return Err(AppError::UserError(
+            "New passphrases do not match.".to_string(),
+        ));
+    }
+    if new.is_empty() {
+        return Err(AppError::UserError(
+            "Passphrase cannot be empty.".to_string(),
+        ));
+    }
+    if new == current {
+        return Err(AppError::UserError(
+            "New passphrase is the same as the current one.".to_string(),
+        ));
+    }
+
+    db.rekey(new)?;
+
+    // The database is rekeyed now, so backup failures are reported rather than returned
+    let mut backups_rekeyed = Vec::new();
+    let mut backups_not_rekeyed = Vec::new();
+    match backup::list_backups(db_path) {
+        Ok(backups) => {
+            for info in backups {
+                match Database::rekey_backup(&info.path, current, new) {
+                    Ok(()) => backups_rekeyed.push(info.path),
+                    Err(e) => backups_not_rekeyed.push(BackupNotRekeyed {
+                        path: info.path,
+                        error: format!("{e:#}"),
+                    }),
+                }
+            }
+        }
+        Err(e) => backups_not_rekeyed.push(BackupNotRekeyed {
+            path: backup::backup_dir(db_path),
+            error: e.to_string(),
+        }),
+    }
+
+    let (keyring_updated, keyring_error) = if crypto::external_key_source().is_some() {
+        (false, None)
+    } else {
+        let keyring_error = crypto::store_in_keyring(profile, new)
+            .err()
+            .map(|e| e.to_string());
+        (keyring_error.is_none(), keyring_error)
+    };
+    Ok(RekeyResult {
+        keyring_updated,
+        keyring_error,
+        backups_rekeyed,
+        backups_not_rekeyed,
+    })
+}
@@ -0,0 +1,135 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+
+use crate::error::AppError;
+use crate::services::report::{DailyEntry, Report};
+
+/// Explains the marker on entries flagged by `--edited-after`.
+pub const EDITED_FOOTNOTE: &str = "Entry was added or edited after the requested cut-off date.";
+
+/// A file format a [`Report`] can be exported to.
+///
+/// Implementations only need [`render`](ReportRenderer::render); writing to a file
+/// goes through a temporary file so a failed export never leaves a partial report.
+pub trait ReportRenderer {
+    /// Human-readable format name used in messages, e.g. "Markdown".
+    fn name(&self) -> &'static str;
+
+    /// File extension (without the dot) used for auto-generated file names.
+    fn extension(&self) -> &'static str;
+
+    /// Other extensions that mark an `--output` path as a file of this format.
+    fn extension_aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Whether the output is binary and must not be written to a terminal.
+    fn is_binary(&self) -> bool {
+        false
+    }
+
+    /// Render the report into `out`.
+    fn render(&self, report: &Report, out: &mut dyn Write) -> Result<(), AppError>;
+
+    /// Render the report as a file at `path`, replacing any existing file.
+    fn render_to_file(&self, report: &Report, path: &Path) -> Result<(), AppError> {
+        let mut buf = Vec::new();
+        self.render(report, &mut buf)?;
+        write_atomic(path, &buf, self.name())
+    }
+}
+
+/// The message shown instead of empty tables when the report has no entries.
+pub fn no_tasks_message(report: &Report, date_format: &str) -> String {
+    format!(
+        "No tasks found between {} and {}.",
+        report.from.format(date_format),
+        report.to.format(date_format)
+    )
+}
+
+/// Whether any entry is flagged as edited after the cut-off date.
+pub fn has_edited_entries(report: &Report) -> bool {
+    report
+        .daily_sections
+        .iter()
+        .flat_map(|s| &s.entries)
+        .any(|e| e.edited)
+}
+
+/// The ID column of an entry, followed by `marker` if it was edited after the cut-off date.
+pub fn entry_id(entry: &DailyEntry, marker: &str) -> String {
+    if entry.edited {
+        format!("{}{marker}", entry.task.id)
+    } else {
+        entry.task.id.to_string()
+    }
+}
+
+/// Write `bytes` to `path` via a temporary file and rename.
+pub fn write_atomic(path: &Path, bytes: &[u8], format_name: &str) -> Result<(), AppError> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+    std::fs::write(&tmp_path, bytes)
+        .and_then(|()| std::fs::rename(&tmp_path, path))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&tmp_path);
+            AppError::SystemError(format!(
+                "Failed to write {format_name} to '{}': {e}",
+                path.display()
+            ))
+        })
+}
+
+/// Resolve the output path for a report file based on CLI flags.
+///
+/// A path ending in `.<extension>` or one of `aliases` is used as-is; any other path
+/// is treated as a directory and gets an auto-generated file name. Without `output`,
+/// the auto-generated name in the current directory is used when `auto` is set.
+///
+/// Returns `Ok(None)` if no file was requested.
+/// Returns `Err` if the output path is invalid (e.g., parent directory doesn't exist).
+pub fn resolve_output_path(
+    output: Option<&str>,
+    extension: &str,
+    aliases: &[&str],
+    auto: bool,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Option<PathBuf>, AppError> {
+    let auto_name = if from == to {
+        format!("report-{}.{extension}", from.format("%Y-%m-%d"))
+    } else {
+        format!(
+            "report-{}-to-{}.{extension}",
+            from.format("%Y-%m-%d"),
+            to.format("%Y-%m-%d")
+        )
+    };
+
+    if let Some(out) = output {
+        let path = PathBuf::from(out);
+        let is_file = path
+            .extension()
+            .is_some_and(|ext| ext == extension || aliases.iter().any(|alias| ext == *alias));
+        let resolved = if is_file { path } else { path.join(&auto_name) };
+
+        if let Some(parent) = resolved.parent() {
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                return Err(AppError::UserError(format!(
+                    "Directory '{}' does not exist.",
+                    parent.display()
+                )));
+            }
+        }
+
+        Ok(Some(resolved))
+    } else if auto {
+        Ok(Some(PathBuf::from(&auto_name)))
+    } else {
+        Ok(None)
+    }
+}
@@ -1,11 +1,14 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 
-use chrono::{NaiveDate, NaiveDateTime};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use serde::Serialize;
 
+use crate::config::Rounding;
 use crate::db::Database;
 use crate::error::AppError;
 use crate::models::TaskEntry;
 
+#[derive(Serialize)]
 pub struct Report {
     pub from: NaiveDate,
     pub to: NaiveDate,
@@ -14,32 +17,55 @@ pub struct Report {
     pub grand_total: i64,
 }
 
+#[derive(Serialize)]
 pub struct ProjectSummary {
     pub name: String,
     pub total: i64,
 }
 
+#[derive(Serialize)]
 pub struct DailySection {
     pub date: NaiveDate,
     pub entries: Vec<DailyEntry>,
 }
 
+#[derive(Serialize)]
 pub struct DailyEntry {
     pub task: TaskEntry,
     pub project_name: String,
+    /// Whether the entry was modified after the `edited_after` date given to [`generate_report`].
+    pub edited: bool,
 }
 
-pub fn generate_report(db: &Database, from: NaiveDate, to: NaiveDate) -> Result<Report, AppError> {
+/// Build a report for the inclusive date range.
+///
+/// When `edited_after` is set, entries changed after the end of that day are flagged.
+/// With `rounding`, each entry's duration is rounded before the totals are summed.
+pub fn generate_report(
+    db: &Database,
+    from: NaiveDate,
+    to: NaiveDate,
+    edited_after: Option<NaiveDate>,
+    rounding: Option<Rounding>,
+) -> Result<Report, AppError> {
     let from_str = from.format("%Y-%m-%d").to_string();
     let to_str = to.format("%Y-%m-%d").to_string();
     let tasks = db.list_tasks_for_date_range(&from_str, &to_str)?;
 
+    let edited_ids = match edited_after.and_then(|d| d.succ_opt()) {
+        Some(day_after) => db.task_ids_modified_since(day_after.and_time(NaiveTime::MIN))?,
+        None => HashSet::new(),
+    };
+
     let projects = db.list_projects()?;
     let mut project_summaries: Vec<ProjectSummary> = Vec::new();
     let mut daily_map: BTreeMap<NaiveDate, Vec<DailyEntry>> = BTreeMap::new();
     let mut grand_total: i64 = 0;
 
-    for task in tasks {
+    for mut task in tasks {
+        if let Some(rounding) = rounding {
+            task.duration_min = rounding.apply(task.duration_min);
+        }
         grand_total += task.duration_min;
 
         let project_name = projects
@@ -60,15 +86,14 @@ pub fn generate_report(db: &Database, from: NaiveDate, to: NaiveDate) -> Result<
             });
         }
 
-        let task_date = task
-            .start_time
-            .map(|t| t.date())
-            .unwrap_or_else(|| task.created_at.date());
+        let task_date = task.date();
 
-        daily_map
-            .entry(task_date)
-            .or_default()
-            .push(DailyEntry { task, project_name });
+        let edited = edited_ids.contains(&task.id);
+        daily_map.entry(task_date).or_default().push(DailyEntry {
+            task,
+            project_name,
+            edited,
+        });
     }
 
     let daily_sections: Vec<DailySection> = daily_map
@@ -2,27 +2,31 @@ use crate::clock_trait::Clock;
 use crate::db::Database;
 use crate::error::AppError;
 use crate::formatting;
-use crate::models::TaskEntry;
-use chrono::{NaiveDateTime, NaiveTime};
+use crate::models::{Operation, TaskEntry, TaskHistoryEntry};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
 
-#[allow(clippy::too_many_arguments)]
-pub fn add_task(
-    db: &Database,
-    project_name: &str,
-    description: &str,
+/// Resolved timing of a new entry.
+pub(crate) struct EntryTimes {
+    pub date: NaiveDate,
+    pub start_time: Option<NaiveDateTime>,
+    pub end_time: Option<NaiveDateTime>,
+    pub duration_min: i64,
+}
+
+/// Validate `--start/--end`, `--duration` and `--date` the way `task add` does.
+pub(crate) fn resolve_entry_times(
     start: Option<&str>,
     end: Option<&str>,
     duration: Option<&str>,
     date: Option<&str>,
     clock: &dyn Clock,
-) -> Result<TaskEntry, AppError> {
-    let project = super::resolve_project(db, project_name)?;
-    let now = clock.now();
-
+) -> Result<EntryTimes, AppError> {
     // Resolve the task date: from --date flag or today
     let task_date = match date {
-        Some(d) => formatting::parse_date(d).map_err(|e| AppError::UserError(e.to_string()))?,
-        None => now.date(),
+        Some(d) => {
+            formatting::parse_date(d, clock).map_err(|e| AppError::UserError(e.to_string()))?
+        }
+        None => clock.now().date(),
     };
 
     let (start_time, end_time, duration_min) =
@@ -51,14 +55,42 @@ pub fn add_task(
             ));
         };
 
-    let task = db.insert_task_entry(
-        project.id,
-        description,
+    Ok(EntryTimes {
+        date: task_date,
         start_time,
         end_time,
         duration_min,
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn add_task(
+    db: &Database,
+    project_name: &str,
+    description: &str,
+    start: Option<&str>,
+    end: Option<&str>,
+    duration: Option<&str>,
+    date: Option<&str>,
+    clock: &dyn Clock,
+) -> Result<TaskEntry, AppError> {
+    let project = super::find_project(db, project_name)?;
+    let now = clock.now();
+
+    let times = resolve_entry_times(start, end, duration, date, clock)?;
+    super::period::ensure_date_unlocked(db, times.date)?;
+
+    let tx = db.conn.unchecked_transaction()?;
+    let task = db.insert_task_entry(
+        project.id,
+        description,
+        times.start_time,
+        times.end_time,
+        times.duration_min,
         now,
     )?;
+    super::undo::record(db, &Operation::AddTask { task: task.clone() }, now)?;
+    tx.commit()?;
 
     Ok(task)
 }
@@ -74,20 +106,23 @@ pub fn edit_task(
     duration: Option<&str>,
     date: Option<&str>,
     clock: &dyn Clock,
-) -> Result<(), AppError> {
+) -> Result<TaskEntry, AppError> {
     let existing = db
         .find_task_entry_by_id(id)?
         .ok_or_else(|| AppError::UserError(format!("Task with ID {id} not found.")))?;
+    super::period::ensure_date_unlocked(db, existing.date())?;
 
     let project_id = if let Some(name) = project_name {
-        Some(super::resolve_project(db, name)?.id)
+        Some(super::find_project(db, name)?.id)
     } else {
         None
     };
 
     // Resolve the date context for this edit
     let new_date = match date {
-        Some(d) => Some(formatting::parse_date(d).map_err(|e| AppError::UserError(e.to_string()))?),
+        Some(d) => {
+            Some(formatting::parse_date(d, clock).map_err(|e| AppError::UserError(e.to_string()))?)
+        }
         None => None,
     };
 
@@ -115,7 +150,7 @@ pub fn edit_task(
         None
     };
 
-    let end_time: Option<Option<NaiveDateTime>> = if let Some(e) = end {
+    let mut end_time: Option<Option<NaiveDateTime>> = if let Some(e) = end {
         Some(Some(
             formatting::parse_time(e, time_date).map_err(|e| AppError::UserError(e.to_string()))?,
         ))
@@ -129,8 +164,31 @@ pub fn edit_task(
         None
     };
 
+    if let Some(Some(new_start)) = start_time {
+        super::period::ensure_date_unlocked(db, new_start.date())?;
+    }
+
     let duration_min = if let Some(d) = duration {
-        Some(formatting::parse_duration(d).map_err(|e| AppError::UserError(e.to_string()))?)
+        if end.is_some() {
+            return Err(AppError::UserError(
+                "Provide either --end or --duration, not both.".to_string(),
+            ));
+        }
+        let minutes =
+            formatting::parse_duration(d).map_err(|e| AppError::UserError(e.to_string()))?;
+        // A timed entry keeps its start; its end moves so the times match the new duration
+        let s = start_time.unwrap_or(existing.start_time);
+        let has_end = end_time.unwrap_or(existing.end_time).is_some();
+        if let (Some(s), true) = (s, has_end) {
+            let e = Duration::try_minutes(minutes)
+                .and_then(|d| s.checked_add_signed(d))
+                .filter(|e| e.date() == s.date())
+                .ok_or_else(|| {
+                    AppError::UserError("The new duration would run past midnight.".to_string())
+                })?;
+            end_time = Some(Some(e));
+        }
+        Some(minutes)
     } else if start_time.is_some() || end_time.is_some() {
         // Recalculate duration if times changed
         let s = start_time
@@ -152,6 +210,7 @@ pub fn edit_task(
     };
 
     let now = clock.now();
+    let tx = db.conn.unchecked_transaction()?;
     db.update_task_entry(
         id,
         description,
@@ -161,14 +220,41 @@ pub fn edit_task(
         duration_min,
         now,
     )?;
-    Ok(())
+    let updated = db
+        .find_task_entry_by_id(id)?
+        .ok_or_else(|| AppError::SystemError(format!("Task {id} vanished during update.")))?;
+    super::undo::record(
+        db,
+        &Operation::EditTask {
+            before: existing,
+            after: updated.clone(),
+        },
+        now,
+    )?;
+    tx.commit()?;
+    Ok(updated)
 }
 
-pub fn delete_task(db: &Database, id: i64) -> Result<(String, i64), AppError> {
+pub fn delete_task(db: &Database, id: i64, now: NaiveDateTime) -> Result<TaskEntry, AppError> {
     let task = db
         .find_task_entry_by_id(id)?
         .ok_or_else(|| AppError::UserError(format!("Task with ID {id} not found.")))?;
+    super::period::ensure_date_unlocked(db, task.date())?;
+
+    let tx = db.conn.unchecked_transaction()?;
+    db.delete_task_entry(id, now)?;
+    super::undo::record(db, &Operation::DeleteTask { task: task.clone() }, now)?;
+    tx.commit()?;
+    Ok(task)
+}
 
-    db.delete_task_entry(id)?;
-    Ok((task.description, task.duration_min))
+/// Return the recorded change history of a task entry, oldest first.
+///
+/// History is kept even after the entry is trashed or purged.
+pub fn task_history(db: &Database, id: i64) -> Result<Vec<TaskHistoryEntry>, AppError> {
+    let history = db.list_task_history(id)?;
+    if history.is_empty() && db.find_task_entry_by_id(id)?.is_none() {
+        return Err(AppError::UserError(format!("Task with ID {id} not found.")));
+    }
+    Ok(history)
 }
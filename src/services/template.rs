@@ -0,0 +1,104 @@
+use chrono::NaiveDateTime;
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::formatting;
+use crate::models::TaskTemplate;
+
+use super::ProjectDefaults;
+
+pub fn create_template(
+    db: &Database,
+    name: &str,
+    project_name: &str,
+    description: &str,
+    duration: Option<&str>,
+    now: NaiveDateTime,
+) -> Result<TaskTemplate, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::UserError(
+            "Template name cannot be empty.".to_string(),
+        ));
+    }
+
+    if db.find_task_template_by_name(name)?.is_some() {
+        return Err(AppError::UserError(format!(
+            "Template '{name}' already exists."
+        )));
+    }
+
+    let project = super::find_project(db, project_name)?;
+    let duration_min = match duration {
+        Some(d) => {
+            Some(formatting::parse_duration(d).map_err(|e| AppError::UserError(e.to_string()))?)
+        }
+        None => None,
+    };
+
+    Ok(db.insert_task_template(name, project.id, description, duration_min, now)?)
+}
+
+pub fn list_templates(db: &Database) -> Result<Vec<TaskTemplate>, AppError> {
+    Ok(db.list_task_templates()?)
+}
+
+pub fn delete_template(db: &Database, name: &str) -> Result<TaskTemplate, AppError> {
+    let template = find_template(db, name)?;
+    db.delete_task_template(template.id)?;
+    Ok(template)
+}
+
+fn find_template(db: &Database, name: &str) -> Result<TaskTemplate, AppError> {
+    db.find_task_template_by_name(name)?
+        .ok_or_else(|| AppError::UserError(format!("Template '{name}' not found.")))
+}
+
+/// Project, description and default duration for a new entry.
+pub struct EntryFields {
+    pub project: String,
+    pub description: String,
+    pub duration_min: Option<i64>,
+}
+
+/// Merge an optional template with explicitly provided fields.
+///
+/// Explicit values always win over the template's; the template only fills gaps.
+/// Whatever is still missing comes from `defaults`.
+pub fn resolve_entry_fields(
+    db: &Database,
+    template_name: Option<&str>,
+    project: Option<&str>,
+    description: Option<&str>,
+    defaults: &ProjectDefaults,
+) -> Result<EntryFields, AppError> {
+    let template = match template_name {
+        Some(name) => Some(find_template(db, name)?),
+        None => None,
+    };
+
+    let project = match (project, &template) {
+        (Some(p), _) => p.to_string(),
+        (None, Some(t)) => t.project_id.to_string(),
+        (None, None) => super::resolve_project(db, None, defaults)?.id.to_string(),
+    };
+
+    let description = match (description, &template) {
+        (Some(d), _) => d.to_string(),
+        (None, Some(t)) => t.description.clone(),
+        (None, None) => match defaults.description()? {
+            Some(d) => d.to_string(),
+            None => {
+                return Err(AppError::UserError(
+                    "Missing description. Provide a description or use --template.".to_string(),
+                ));
+            }
+        },
+    };
+
+    Ok(EntryFields {
+        project,
+        description,
+        duration_min: template.and_then(|t| t.duration_min),
+    })
+}
@@ -0,0 +1,193 @@
+use std::io::Write;
+use std::path::Path;
+
+use chrono::NaiveDateTime;
+
+use crate::error::AppError;
+use crate::services::export::{DURATION_ONLY_MARKER, ExportEntry};
+use crate::services::import::{self, ImportIssue, ImportRecord};
+
+const TIMECLOCK_FORMAT: &str = "%Y/%m/%d %H:%M:%S";
+
+fn parse_timestamp(date: &str, time: &str) -> Result<NaiveDateTime, AppError> {
+    let date = date.replace('-', "/");
+    ["%Y/%m/%d %H:%M:%S", "%Y/%m/%d %H:%M"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(&format!("{date} {time}"), fmt).ok())
+        .ok_or_else(|| AppError::UserError(format!("Invalid date/time '{date} {time}'.")))
+}
+
+/// Split the rest of an `i` line into account and description.
+///
+/// As in ledger, the account ends at two spaces or a tab.
+fn split_account(rest: &str) -> (&str, &str) {
+    let end = [rest.find("  "), rest.find('\t')]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(rest.len());
+    (rest[..end].trim(), rest[end..].trim())
+}
+
+struct ClockIn {
+    line: usize,
+    start: NaiveDateTime,
+    account: String,
+    description: String,
+    duration_only: bool,
+}
+
+/// The first whitespace-separated field of `text` and everything after it.
+fn next_field(text: &str) -> (&str, &str) {
+    let text = text.trim_start();
+    let end = text.find(char::is_whitespace).unwrap_or(text.len());
+    text.split_at(end)
+}
+
+/// Read a ledger `.timeclock` file of `i`/`o` lines.
+///
+/// The account of each clock-in becomes the project and the text after it the description.
+/// A pair preceded by a `; duration-only` comment is imported as a duration-only entry.
+pub fn read_timeclock(path: &Path) -> Result<Vec<ImportRecord>, AppError> {
+    let content = import::read_source(path)?;
+    let mut records = Vec::new();
+    let mut open: Option<ClockIn> = None;
+    let mut duration_only = false;
+
+    for (index, raw) in content.lines().enumerate() {
+        let line = index + 1;
+        let location = format!("line {line}");
+        let text = raw.trim_end();
+        if text.trim().is_empty() || text.starts_with([';', '#', '*']) {
+            if text.trim_start_matches([';', '#', '*']).trim() == DURATION_ONLY_MARKER {
+                duration_only = true;
+            }
+            continue;
+        }
+
+        let marked = std::mem::take(&mut duration_only);
+        let (code, rest) = next_field(text);
+        let (date, rest) = next_field(rest);
+        let (time, rest) = next_field(rest);
+        let rest = rest.trim_start();
+        let timestamp = match parse_timestamp(date, time) {
+            Ok(t) => t,
+            Err(e) => {
+                records.push(ImportRecord::Invalid(ImportIssue::new(
+                    location,
+                    e.to_string(),
+                )));
+                continue;
+            }
+        };
+
+        match code {
+            "i" | "I" => {
+                if let Some(previous) = open.take() {
+                    records.push(ImportRecord::Invalid(ImportIssue::new(
+                        format!("line {}", previous.line),
+                        "Clock-in without a matching clock-out.",
+                    )));
+                }
+                let (account, description) = split_account(rest);
+                open = Some(ClockIn {
+                    line,
+                    start: timestamp,
+                    account: account.to_string(),
+                    description: description.to_string(),
+                    duration_only: marked,
+                });
+            }
+            "o" | "O" => match open.take() {
+                Some(clock_in) => records.push(clock_out(clock_in, timestamp)),
+                None => records.push(ImportRecord::Invalid(ImportIssue::new(
+                    location,
+                    "Clock-out without a preceding clock-in.",
+                ))),
+            },
+            _ => records.push(ImportRecord::Invalid(ImportIssue::new(
+                location,
+                format!("Unknown entry type '{code}'; expected 'i' or 'o'."),
+            ))),
+        }
+    }
+
+    if let Some(clock_in) = open {
+        records.push(ImportRecord::Skipped(ImportIssue::new(
+            format!("line {}", clock_in.line),
+            "still clocked in",
+        )));
+    }
+    Ok(records)
+}
+
+fn clock_out(clock_in: ClockIn, end: NaiveDateTime) -> ImportRecord {
+    let location = format!("line {}", clock_in.line);
+    let start = clock_in.start.format("%Y-%m-%dT%H:%M:%S");
+    if clock_in.duration_only {
+        let duration_min = (end - clock_in.start).num_minutes();
+        // Several duration-only entries can share a day and project, so the key needs more
+        let key = format!(
+            "timeclock:{start}|{}|{}|{duration_min}",
+            clock_in.account, clock_in.description
+        );
+        return import::duration_record(
+            location,
+            clock_in.account,
+            clock_in.description,
+            clock_in.start.date(),
+            duration_min,
+            key,
+        );
+    }
+    let key = format!("timeclock:{start}|{}", clock_in.account);
+    import::timed_record(
+        location,
+        clock_in.account,
+        clock_in.description,
+        clock_in.start,
+        end,
+        key,
+    )
+}
+
+/// Write entries as a ledger timeclock file with one `i`/`o` pair per entry.
+///
+/// The project becomes the account. Duration-only entries are written as an interval
+/// from midnight, preceded by a `; duration-only` comment so they import back unchanged.
+pub fn write_timeclock(entries: &[ExportEntry], out: &mut dyn Write) -> Result<(), AppError> {
+    // The account ends at a tab or two spaces, and the format has no way to escape them
+    if let Some(entry) = entries
+        .iter()
+        .find(|e| e.project_name.contains(['\t', '\n', '\r']) || e.project_name.contains("  "))
+    {
+        return Err(AppError::UserError(format!(
+            "Project '{}' cannot be written to a timeclock file: account names cannot contain tabs, line breaks or two spaces in a row.",
+            entry.project_name.escape_default()
+        )));
+    }
+    for entry in entries {
+        let (start, end) = entry.interval();
+        if entry.is_duration_only() {
+            writeln!(out, "; {DURATION_ONLY_MARKER}")?;
+        }
+        let description = entry.description();
+        if description.is_empty() {
+            writeln!(
+                out,
+                "i {} {}",
+                start.format(TIMECLOCK_FORMAT),
+                entry.project_name
+            )?;
+        } else {
+            writeln!(
+                out,
+                "i {} {}  {description}",
+                start.format(TIMECLOCK_FORMAT),
+                entry.project_name
+            )?;
+        }
+        writeln!(out, "o {}", end.format(TIMECLOCK_FORMAT))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use std::io::Write;
+use std::path::Path;
+
+use chrono::{Local, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::services::export::{self, DURATION_ONLY_MARKER, ExportEntry};
+use crate::services::import::{self, ImportIssue, ImportRecord};
+
+/// Timestamp format of `timew export`, always in UTC.
+const TIMEW_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// One interval of `timew export` output.
+#[derive(Deserialize, Serialize)]
+struct Interval {
+    start: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    annotation: Option<String>,
+}
+
+fn parse_timestamp(s: &str) -> Result<NaiveDateTime, AppError> {
+    let utc = NaiveDateTime::parse_from_str(s, TIMEW_FORMAT)
+        .map_err(|_| AppError::UserError(format!("Invalid timestamp '{s}'.")))?;
+    Ok(Utc
+        .from_utc_datetime(&utc)
+        .with_timezone(&Local)
+        .naive_local())
+}
+
+fn format_timestamp(local: NaiveDateTime) -> String {
+    export::to_utc(local).format(TIMEW_FORMAT).to_string()
+}
+
+/// Read a Timewarrior JSON export (`timew export`).
+///
+/// The first tag becomes the project. The annotation becomes the description,
+/// falling back to the remaining tags. Still-open intervals are skipped, and
+/// intervals tagged `duration-only` are imported as duration-only entries.
+pub fn read_timewarrior(path: &Path) -> Result<Vec<ImportRecord>, AppError> {
+    let content = import::read_source(path)?;
+    let intervals: Vec<Interval> = serde_json::from_str(&content)
+        .map_err(|e| AppError::UserError(format!("Invalid Timewarrior export: {e}")))?;
+
+    Ok(intervals
+        .into_iter()
+        .enumerate()
+        .map(|(i, interval)| read_interval(format!("interval {}", i + 1), interval))
+        .collect())
+}
+
+fn read_interval(location: String, interval: Interval) -> ImportRecord {
+    let Some(end) = interval.end.as_deref() else {
+        return ImportRecord::Skipped(ImportIssue::new(location, "still running"));
+    };
+    let Some((project, rest)) = interval.tags.split_first() else {
+        return ImportRecord::Invalid(ImportIssue::new(
+            location,
+            "Interval has no tags to use as a project.",
+        ));
+    };
+    let (start_time, end_time) = match (parse_timestamp(&interval.start), parse_timestamp(end)) {
+        (Ok(s), Ok(e)) => (s, e),
+        (Err(e), _) | (_, Err(e)) => {
+            return ImportRecord::Invalid(ImportIssue::new(location, e.to_string()));
+        }
+    };
+    let duration_only = rest.iter().any(|t| t == DURATION_ONLY_MARKER);
+    let other_tags: Vec<&str> = rest
+        .iter()
+        .map(String::as_str)
+        .filter(|t| *t != DURATION_ONLY_MARKER)
+        .collect();
+    let description = interval
+        .annotation
+        .clone()
+        .unwrap_or_else(|| other_tags.join(" "));
+
+    if duration_only {
+        let duration_min = (end_time - start_time).num_minutes();
+        // Several duration-only entries can share a day and project, so the key needs more
+        let key = format!(
+            "timewarrior:{}|{project}|{description}|{duration_min}",
+            interval.start
+        );
+        return import::duration_record(
+            location,
+            project.clone(),
+            description,
+            start_time.date(),
+            duration_min,
+            key,
+        );
+    }
+    import::timed_record(
+        location,
+        project.clone(),
+        description,
+        start_time,
+        end_time,
+        format!("timewarrior:{}", interval.start),
+    )
+}
+
+/// Write entries in the format of `timew export`, which `timew import` accepts.
+///
+/// The project is the first tag and the description the annotation. Duration-only
+/// entries are written as an interval from midnight with an extra `duration-only` tag.
+pub fn write_timewarrior(entries: &[ExportEntry], out: &mut dyn Write) -> Result<(), AppError> {
+    writeln!(out, "[")?;
+    for (i, entry) in entries.iter().enumerate() {
+        let (start, end) = entry.interval();
+        let mut tags = vec![entry.project_name.clone()];
+        if entry.is_duration_only() {
+            tags.push(DURATION_ONLY_MARKER.to_string());
+        }
+        let description = entry.description();
+        let interval = Interval {
+            start: format_timestamp(start),
+            end: Some(format_timestamp(end)),
+            tags,
+            annotation: (!description.is_empty()).then_some(description),
+        };
+        let separator = if i + 1 < entries.len() { "," } else { "" };
+        let json = serde_json::to_string(&interval)
+            .map_err(|e| AppError::SystemError(format!("Failed to serialize interval: {e}")))?;
+        writeln!(out, "{json}{separator}")?;
+    }
+    writeln!(out, "]")?;
+    Ok(())
+}
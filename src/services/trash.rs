@@ -0,0 +1,130 @@
+use chrono::{Duration, NaiveDateTime};
+use serde::Serialize;
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::{Project, TaskEntry};
+
+#[derive(Serialize)]
+pub struct TrashedProject {
+    pub project: Project,
+    pub task_count: i64,
+    pub deleted_at: NaiveDateTime,
+}
+
+#[derive(Serialize)]
+pub struct TrashedTask {
+    pub task: TaskEntry,
+    pub project_name: String,
+    pub deleted_at: NaiveDateTime,
+}
+
+#[derive(Serialize)]
+pub struct TrashContents {
+    pub projects: Vec<TrashedProject>,
+    pub tasks: Vec<TrashedTask>,
+}
+
+pub fn list_trash(db: &Database) -> Result<TrashContents, AppError> {
+    let trashed_projects = db.list_deleted_projects()?;
+    let mut projects = Vec::new();
+    for (project, deleted_at) in &trashed_projects {
+        projects.push(TrashedProject {
+            project: project.clone(),
+            task_count: db.count_tasks_for_project(project.id)?,
+            deleted_at: *deleted_at,
+        });
+    }
+
+    let active_projects = db.list_projects()?;
+    let tasks = db
+        .list_deleted_task_entries()?
+        .into_iter()
+        .map(|(task, deleted_at)| {
+            let project_name = active_projects
+                .iter()
+                .chain(trashed_projects.iter().map(|(p, _)| p))
+                .find(|p| p.id == task.project_id)
+                .map(|p| p.name.clone())
+                .unwrap_or_else(|| "?".to_string());
+            TrashedTask {
+                task,
+                project_name,
+                deleted_at,
+            }
+        })
+        .collect();
+
+    Ok(TrashContents { projects, tasks })
+}
+
+pub fn restore_task(db: &Database, id: i64, now: NaiveDateTime) -> Result<TaskEntry, AppError> {
+    let (task, _) = db
+        .list_deleted_task_entries()?
+        .into_iter()
+        .find(|(t, _)| t.id == id)
+        .ok_or_else(|| AppError::UserError(format!("Task with ID {id} is not in the trash.")))?;
+
+    if let Some(project) = db.find_deleted_project_by_id(task.project_id)? {
+        return Err(AppError::UserError(format!(
+            "Task {id} belongs to trashed project '{}'. Restore the project first with 'vibe-clock trash restore {} --project'.",
+            project.name, project.id
+        )));
+    }
+
+    super::period::ensure_date_unlocked(db, task.date())?;
+
+    db.undelete_task_entry(id, now)?;
+    Ok(task)
+}
+
+pub fn restore_project(db: &Database, id: i64, now: NaiveDateTime) -> Result<Project, AppError> {
+    let project = db
+        .find_deleted_project_by_id(id)?
+        .ok_or_else(|| AppError::UserError(format!("Project with ID {id} is not in the trash.")))?;
+
+    super::period::ensure_tasks_unlocked(db, &db.list_tasks_for_project(id)?)?;
+
+    db.undelete_project(id, now)?;
+    Ok(project)
+}
+
+#[derive(Serialize)]
+pub struct EmptyResult {
+    pub projects: usize,
+    pub tasks: usize,
+}
+
+/// Permanently delete trashed items, optionally only those trashed more than
+/// `older_than_days` days before `now`.
+pub fn empty_trash(
+    db: &Database,
+    older_than_days: Option<i64>,
+    now: NaiveDateTime,
+) -> Result<EmptyResult, AppError> {
+    // A cut-off too far back to represent is older than anything in the trash
+    let cutoff = older_than_days
+        .map(|days| Duration::try_days(days).and_then(|d| now.checked_sub_signed(d)));
+    let is_expired = |deleted_at: NaiveDateTime| match cutoff {
+        None => true,
+        Some(cutoff) => cutoff.is_some_and(|c| deleted_at <= c),
+    };
+
+    let tx = db.conn.unchecked_transaction()?;
+    let mut result = EmptyResult {
+        projects: 0,
+        tasks: 0,
+    };
+    for (task, deleted_at) in db.list_deleted_task_entries()? {
+        if is_expired(deleted_at) && db.purge_task_entry(task.id, now)? {
+            result.tasks += 1;
+        }
+    }
+    for (project, deleted_at) in db.list_deleted_projects()? {
+        if is_expired(deleted_at) && db.purge_project(project.id, now)? {
+            result.projects += 1;
+        }
+    }
+    tx.commit()?;
+    Ok(result)
+}
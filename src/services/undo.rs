@@ -0,0 +1,263 @@
+use chrono::NaiveDateTime;
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::{Operation, Project, TaskEntry, TaskTemplate};
+
+/// Record a mutating operation so it can later be undone.
+///
+/// Callers invoke this inside the same transaction as the mutation itself.
+pub fn record(db: &Database, op: &Operation, now: NaiveDateTime) -> Result<(), AppError> {
+    db.insert_operation(op, now)?;
+    Ok(())
+}
+
+/// Revert the most recent operation. Returns `None` if there is nothing to undo.
+pub fn undo(db: &Database, now: NaiveDateTime) -> Result<Option<Operation>, AppError> {
+    let Some((id, op)) = db.last_applied_operation()? else {
+        return Ok(None);
+    };
+
+    super::period::ensure_tasks_unlocked(db, op.affected_tasks())?;
+
+    let tx = db.conn.unchecked_transaction()?;
+    revert(db, &op, now)?;
+    db.set_operation_undone(id, true)?;
+    tx.commit()?;
+    Ok(Some(op))
+}
+
+/// Re-apply the most recently undone operation. Returns `None` if there is nothing to redo.
+pub fn redo(db: &Database, now: NaiveDateTime) -> Result<Option<Operation>, AppError> {
+    let Some((id, op)) = db.next_undone_operation()? else {
+        return Ok(None);
+    };
+
+    super::period::ensure_tasks_unlocked(db, op.affected_tasks())?;
+
+    let tx = db.conn.unchecked_transaction()?;
+    apply(db, &op, now)?;
+    db.set_operation_undone(id, false)?;
+    tx.commit()?;
+    Ok(Some(op))
+}
+
+fn conflict(message: String) -> AppError {
+    AppError::UserError(message)
+}
+
+fn ensure_no_clock(db: &Database) -> Result<(), AppError> {
+    if db.get_clock_state()?.is_some() {
+        return Err(conflict(
+            "A clock is currently running. Stop it before undoing or redoing this operation."
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn ensure_name_free(db: &Database, name: &str, own_id: i64) -> Result<(), AppError> {
+    if let Some(existing) = db.find_project_by_name(name)? {
+        if existing.id != own_id {
+            return Err(conflict(format!(
+                "Project name '{name}' is now used by another project."
+            )));
+        }
+    }
+    // Trashed projects keep their name reserved
+    if let Some(trashed) = db.find_deleted_project_by_name(name)? {
+        if trashed.id != own_id {
+            return Err(conflict(format!(
+                "Project name '{name}' is now used by a project in the trash (ID {}).",
+                trashed.id
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn ensure_task_exists(db: &Database, id: i64) -> Result<(), AppError> {
+    if db.find_task_entry_by_id(id)?.is_none() {
+        return Err(conflict(format!("Task with ID {id} no longer exists.")));
+    }
+    Ok(())
+}
+
+/// Bring a deleted project back, from the trash if it is still there, otherwise
+/// by re-inserting it together with the rows the deletion removed.
+fn bring_back_project(
+    db: &Database,
+    project: &Project,
+    tasks: &[TaskEntry],
+    templates: &[TaskTemplate],
+    now: NaiveDateTime,
+) -> Result<(), AppError> {
+    if db.undelete_project(project.id, now)? {
+        return Ok(());
+    }
+    if db.find_project_by_id(project.id)?.is_some() {
+        return Err(conflict(format!(
+            "Project '{}' already exists.",
+            project.name
+        )));
+    }
+    ensure_name_free(db, &project.name, project.id)?;
+    db.restore_project(project)?;
+    for task in tasks {
+        db.restore_task_entry(task, now)?;
+    }
+    for template in templates {
+        db.restore_task_template(template)?;
+    }
+    Ok(())
+}
+
+/// Bring a deleted task back, from the trash if it is still there, otherwise by re-inserting it.
+fn bring_back_task(db: &Database, task: &TaskEntry, now: NaiveDateTime) -> Result<(), AppError> {
+    if db.undelete_task_entry(task.id, now)? {
+        return Ok(());
+    }
+    if db.find_task_entry_by_id(task.id)?.is_some() {
+        return Err(conflict(format!(
+            "Task with ID {} already exists.",
+            task.id
+        )));
+    }
+    db.restore_task_entry(task, now)?;
+    Ok(())
+}
+
+fn write_task(db: &Database, task: &TaskEntry, now: NaiveDateTime) -> Result<(), AppError> {
+    db.update_task_entry(
+        task.id,
+        Some(&task.description),
+        Some(task.project_id),
+        Some(task.start_time),
+        Some(task.end_time),
+        Some(task.duration_min),
+        now,
+    )?;
+    Ok(())
+}
+
+fn revert(db: &Database, op: &Operation, now: NaiveDateTime) -> Result<(), AppError> {
+    match op {
+        Operation::CreateProject { project } => {
+            if db.count_tasks_for_project(project.id)? > 0 {
+                return Err(conflict(format!(
+                    "Project '{}' has tasks; delete them before undoing its creation.",
+                    project.name
+                )));
+            }
+            db.delete_project(project.id, now)?;
+        }
+        Operation::RenameProject { id, old_name, .. } => {
+            ensure_name_free(db, old_name, *id)?;
+            let project = db
+                .find_project_by_id(*id)?
+                .ok_or_else(|| conflict(format!("Project with ID {id} no longer exists.")))?;
+            db.update_project_name(*id, old_name, project.updated_at)?;
+        }
+        Operation::DeleteProject {
+            project,
+            tasks,
+            templates,
+            clock,
+        } => {
+            bring_back_project(db, project, tasks, templates, now)?;
+            if let Some(clock) = clock {
+                ensure_no_clock(db)?;
+                db.insert_clock_state(clock.project_id, &clock.description, clock.start_time)?;
+            }
+        }
+        Operation::AddTask { task } => {
+            ensure_task_exists(db, task.id)?;
+            db.delete_task_entry(task.id, now)?;
+        }
+        Operation::EditTask { before, .. } => {
+            ensure_task_exists(db, before.id)?;
+            write_task(db, before, now)?;
+        }
+        Operation::DeleteTask { task } => {
+            bring_back_task(db, task, now)?;
+        }
+        Operation::StartClock { .. } => {
+            db.delete_clock_state()?;
+        }
+        Operation::StopClock { clock, task } => {
+            ensure_no_clock(db)?;
+            ensure_task_exists(db, task.id)?;
+            db.delete_task_entry(task.id, now)?;
+            db.insert_clock_state(clock.project_id, &clock.description, clock.start_time)?;
+        }
+        Operation::Import {
+            projects, tasks, ..
+        } => {
+            for task in tasks {
+                ensure_task_exists(db, task.id)?;
+                db.delete_task_entry(task.id, now)?;
+            }
+            for project in projects {
+                if db.count_tasks_for_project(project.id)? > 0 {
+                    return Err(conflict(format!(
+                        "Project '{}' has tasks added after the import; delete them before undoing it.",
+                        project.name
+                    )));
+                }
+                db.delete_project(project.id, now)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn apply(db: &Database, op: &Operation, now: NaiveDateTime) -> Result<(), AppError> {
+    match op {
+        Operation::CreateProject { project } => {
+            bring_back_project(db, project, &[], &[], now)?;
+        }
+        Operation::RenameProject { id, new_name, .. } => {
+            ensure_name_free(db, new_name, *id)?;
+            let project = db
+                .find_project_by_id(*id)?
+                .ok_or_else(|| conflict(format!("Project with ID {id} no longer exists.")))?;
+            db.update_project_name(*id, new_name, project.updated_at)?;
+        }
+        Operation::DeleteProject { project, clock, .. } => {
+            if clock.is_some() {
+                db.delete_clock_state()?;
+            }
+            db.delete_project(project.id, now)?;
+        }
+        Operation::AddTask { task } => {
+            bring_back_task(db, task, now)?;
+        }
+        Operation::EditTask { after, .. } => {
+            ensure_task_exists(db, after.id)?;
+            write_task(db, after, now)?;
+        }
+        Operation::DeleteTask { task } => {
+            ensure_task_exists(db, task.id)?;
+            db.delete_task_entry(task.id, now)?;
+        }
+        Operation::StartClock { clock } => {
+            ensure_no_clock(db)?;
+            db.insert_clock_state(clock.project_id, &clock.description, clock.start_time)?;
+        }
+        Operation::StopClock { task, .. } => {
+            db.delete_clock_state()?;
+            bring_back_task(db, task, now)?;
+        }
+        Operation::Import {
+            projects, tasks, ..
+        } => {
+            for project in projects {
+                bring_back_project(db, project, &[], &[], now)?;
+            }
+            for task in tasks {
+                bring_back_task(db, task, now)?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use std::path::Path;
+
+use chrono::{DateTime, Local, NaiveDateTime};
+use serde_json::Value;
+
+use crate::error::AppError;
+use crate::services::import::{self, ImportIssue, ImportRecord};
+
+fn local_from_unix(ts: i64) -> Result<NaiveDateTime, AppError> {
+    DateTime::from_timestamp(ts, 0)
+        .map(|t| t.with_timezone(&Local).naive_local())
+        .ok_or_else(|| AppError::UserError(format!("Invalid timestamp {ts}.")))
+}
+
+/// Read Watson's `frames` file.
+///
+/// Each frame is `[start, stop, project, id, tags, updated_at]` with Unix timestamps.
+/// The project maps directly and the tags become the description.
+pub fn read_watson(path: &Path) -> Result<Vec<ImportRecord>, AppError> {
+    let content = import::read_source(path)?;
+    let frames: Vec<Vec<Value>> = serde_json::from_str(&content)
+        .map_err(|e| AppError::UserError(format!("Invalid Watson frames file: {e}")))?;
+
+    Ok(frames
+        .iter()
+        .enumerate()
+        .map(|(i, frame)| {
+            let location = format!("frame {}", i + 1);
+            match parse_frame(frame) {
+                Ok((project, description, start, end, id)) => import::timed_record(
+                    location,
+                    project,
+                    description,
+                    start,
+                    end,
+                    format!("watson:{id}"),
+                ),
+                Err(e) => ImportRecord::Invalid(ImportIssue::new(location, e.to_string())),
+            }
+        })
+        .collect())
+}
+
+type Frame = (String, String, NaiveDateTime, NaiveDateTime, String);
+
+fn parse_frame(frame: &[Value]) -> Result<Frame, AppError> {
+    let invalid = || AppError::UserError("Expected [start, stop, project, id, tags, ...].".into());
+    let start = frame.first().and_then(Value::as_i64).ok_or_else(invalid)?;
+    let stop = frame.get(1).and_then(Value::as_i64).ok_or_else(invalid)?;
+    let project = frame.get(2).and_then(Value::as_str).ok_or_else(invalid)?;
+    let id = frame.get(3).and_then(Value::as_str).ok_or_else(invalid)?;
+    let tags: Vec<&str> = frame
+        .get(4)
+        .and_then(Value::as_array)
+        .map(|tags| tags.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    Ok((
+        project.to_string(),
+        tags.join(", "),
+        local_from_unix(start)?,
+        local_from_unix(stop)?,
+        id.to_string(),
+    ))
+}
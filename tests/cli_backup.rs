@@ -0,0 +1,249 @@
+use std::fs;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn vibe_clock(tmp: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_DB", tmp.path().join("test.db"));
+    cmd.env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"));
+    cmd.current_dir(tmp.path());
+    cmd.env("VIBE_CLOCK_KEY", "test-passphrase");
+    cmd
+}
+
+fn backup_files(tmp: &TempDir) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(tmp.path().join("backups"))
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+fn json_output(cmd: &mut Command) -> serde_json::Value {
+    let output = cmd.output().unwrap();
+    assert!(output.status.success(), "{output:?}");
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn first_run_of_the_day_takes_automatic_backup() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success();
+    vibe_clock(&tmp)
+        .args(["project", "add", "Beta"])
+        .assert()
+        .success();
+
+    let files = backup_files(&tmp);
+    assert_eq!(files.len(), 1, "{files:?}");
+    assert!(files[0].starts_with("auto-") && files[0].ends_with(".db"));
+}
+
+#[test]
+fn automatic_backups_can_be_disabled() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .env("VIBE_CLOCK_BACKUP_KEEP", "0")
+        .args(["project", "list"])
+        .assert()
+        .success();
+    assert!(!tmp.path().join("backups").exists());
+}
+
+#[test]
+fn automatic_backups_are_rotated() {
+    let tmp = TempDir::new().unwrap();
+    let dir = tmp.path().join("backups");
+    fs::create_dir_all(&dir).unwrap();
+    for day in ["2020-01-01", "2020-01-02", "2020-01-03"] {
+        fs::write(dir.join(format!("auto-{day}.db")), "old").unwrap();
+    }
+    fs::write(dir.join("vibe-clock-2020-01-01T000000.db"), "manual").unwrap();
+
+    vibe_clock(&tmp)
+        .env("VIBE_CLOCK_BACKUP_KEEP", "2")
+        .args(["project", "list"])
+        .assert()
+        .success();
+
+    let files = backup_files(&tmp);
+    assert_eq!(files.len(), 3, "{files:?}");
+    assert!(files.contains(&"auto-2020-01-03.db".to_string()));
+    assert!(!files.contains(&"auto-2020-01-02.db".to_string()));
+    // Manual backups are never rotated away
+    assert!(files.contains(&"vibe-clock-2020-01-01T000000.db".to_string()));
+}
+
+#[test]
+fn invalid_retention_only_warns() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .env("VIBE_CLOCK_BACKUP_KEEP", "lots")
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Automatic backup failed"));
+}
+
+#[test]
+fn create_writes_encrypted_backup() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success();
+    let target = tmp.path().join("manual.db");
+    let json = json_output(vibe_clock(&tmp).args([
+        "--output-format",
+        "json",
+        "backup",
+        "create",
+        target.to_str().unwrap(),
+    ]));
+    assert_eq!(json["backup"]["automatic"], false);
+    assert!(json["backup"]["size"].as_u64().unwrap() > 0);
+
+    let bytes = fs::read(&target).unwrap();
+    assert!(!bytes.starts_with(b"SQLite format 3"));
+
+    // The backup is a working database under the same passphrase
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_DB", &target)
+        .env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"))
+        .current_dir(tmp.path())
+        .env("VIBE_CLOCK_KEY", "test-passphrase")
+        .env("VIBE_CLOCK_BACKUP_KEEP", "0")
+        .args(["project", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Acme"));
+}
+
+#[test]
+fn create_refuses_to_overwrite() {
+    let tmp = TempDir::new().unwrap();
+    let target = tmp.path().join("existing.db");
+    fs::write(&target, "keep me").unwrap();
+    vibe_clock(&tmp)
+        .args(["backup", "create", target.to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("already exists"));
+    assert_eq!(fs::read_to_string(&target).unwrap(), "keep me");
+}
+
+#[test]
+fn list_shows_backups() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["backup", "create"])
+        .assert()
+        .success();
+    let json = json_output(vibe_clock(&tmp).args(["--output-format", "json", "backup", "list"]));
+    let backups = json["backups"].as_array().unwrap();
+    assert_eq!(backups.len(), 2);
+    assert_eq!(backups.iter().filter(|b| b["automatic"] == true).count(), 1);
+}
+
+#[test]
+fn restore_replaces_database() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success();
+    let target = tmp.path().join("snapshot.db");
+    vibe_clock(&tmp)
+        .args(["backup", "create", target.to_str().unwrap()])
+        .assert()
+        .success();
+    vibe_clock(&tmp)
+        .args(["project", "add", "Later"])
+        .assert()
+        .success();
+
+    let json = json_output(vibe_clock(&tmp).args([
+        "--output-format",
+        "json",
+        "backup",
+        "restore",
+        target.to_str().unwrap(),
+        "--yes",
+    ]));
+    let safety = json["safety_backup"].as_str().unwrap();
+    assert!(safety.contains("pre-restore-"));
+
+    vibe_clock(&tmp)
+        .args(["project", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Acme"))
+        .stdout(predicate::str::contains("Later").not());
+
+    // The safety backup still has the undone change
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_DB", safety)
+        .env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"))
+        .current_dir(tmp.path())
+        .env("VIBE_CLOCK_KEY", "test-passphrase")
+        .env("VIBE_CLOCK_BACKUP_KEEP", "0")
+        .args(["project", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Later"));
+}
+
+#[test]
+fn restore_without_yes_can_be_cancelled() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["backup", "create", "snap.db"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+    vibe_clock(&tmp)
+        .args(["backup", "restore", "snap.db"])
+        .current_dir(tmp.path())
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cancelled."));
+}
+
+#[test]
+fn restore_rejects_wrong_passphrase() {
+    let tmp = TempDir::new().unwrap();
+    let other = tmp.path().join("other.db");
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_DB", &other)
+        .env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"))
+        .current_dir(tmp.path())
+        .env("VIBE_CLOCK_KEY", "another-passphrase")
+        .env("VIBE_CLOCK_BACKUP_KEEP", "0")
+        .args(["project", "add", "Secret"])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp)
+        .args(["backup", "restore", other.to_str().unwrap(), "--yes"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("wrong passphrase"));
+}
+
+#[test]
+fn restore_rejects_live_database() {
+    let tmp = TempDir::new().unwrap();
+    let live = tmp.path().join("test.db");
+    vibe_clock(&tmp)
+        .args(["backup", "restore", live.to_str().unwrap(), "--yes"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("live database"));
+}
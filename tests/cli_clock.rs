@@ -5,6 +5,8 @@ use tempfile::TempDir;
 fn vibe_clock(tmp: &TempDir) -> Command {
     let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
     cmd.env("VIBE_CLOCK_DB", tmp.path().join("test.db"));
+    cmd.env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"));
+    cmd.current_dir(tmp.path());
     cmd.env("VIBE_CLOCK_KEY", "test-key");
     cmd
 }
@@ -118,3 +120,59 @@ fn recovers_clock_state_after_crash() {
         .success()
         .stdout(predicate::str::contains("Clock stopped"));
 }
+
+#[test]
+fn start_uses_configured_default_project() {
+    let tmp = TempDir::new().unwrap();
+    setup_project(&tmp);
+    let config = tmp.path().join("config.toml");
+    std::fs::write(&config, "[defaults]\nproject = \"Acme\"\n").unwrap();
+    std::fs::write(tmp.path().join(".vibe-clock"), "").unwrap();
+
+    vibe_clock(&tmp)
+        .arg("--config")
+        .arg(&config)
+        .args(["clock", "start", "Code review"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Clock started for 'Code review' on project 'Acme'",
+        ));
+}
+
+#[test]
+fn directory_file_overrides_configured_default_project() {
+    let tmp = TempDir::new().unwrap();
+    setup_project(&tmp);
+    vibe_clock(&tmp)
+        .args(["project", "add", "Beta"])
+        .assert()
+        .success();
+    let config = tmp.path().join("config.toml");
+    std::fs::write(&config, "[defaults]\nproject = \"Acme\"\n").unwrap();
+    let repo = tmp.path().join("beta-repo");
+    std::fs::create_dir(&repo).unwrap();
+    std::fs::write(repo.join(".vibe-clock"), "project = \"Beta\"\n").unwrap();
+
+    vibe_clock(&tmp)
+        .current_dir(&repo)
+        .arg("--config")
+        .arg(&config)
+        .args(["clock", "start", "Release"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("on project 'Beta'"));
+}
+
+#[test]
+fn start_without_project_or_default_fails() {
+    let tmp = TempDir::new().unwrap();
+    setup_project(&tmp);
+    // An empty .vibe-clock stops the lookup before it reaches the parent directories
+    std::fs::write(tmp.path().join(".vibe-clock"), "").unwrap();
+    vibe_clock(&tmp)
+        .args(["clock", "start", "Something"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Missing project"));
+}
@@ -0,0 +1,297 @@
+use std::fs;
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn config_file(tmp: &TempDir) -> PathBuf {
+    tmp.path().join("config.toml")
+}
+
+fn vibe_clock(tmp: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_DB", tmp.path().join("test.db"));
+    cmd.env("VIBE_CLOCK_KEY", "test-key");
+    cmd.env("VIBE_CLOCK_BACKUP_KEEP", "0");
+    cmd.arg("--config").arg(config_file(tmp));
+    cmd.current_dir(tmp.path());
+    cmd
+}
+
+fn set(tmp: &TempDir, key: &str, value: &str) {
+    vibe_clock(tmp)
+        .args(["config", "set", key, value])
+        .assert()
+        .success();
+}
+
+/// An empty config file and a project to log tasks against.
+fn setup(tmp: &TempDir) {
+    fs::write(config_file(tmp), "").unwrap();
+    vibe_clock(tmp)
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success();
+}
+
+fn add_task(tmp: &TempDir, start: &str, end: &str) {
+    vibe_clock(tmp)
+        .args([
+            "task",
+            "add",
+            "Acme",
+            "Work",
+            "--start",
+            start,
+            "--end",
+            end,
+            "--date",
+            "2026-02-25",
+        ])
+        .assert()
+        .success();
+}
+
+#[test]
+fn set_get_and_list() {
+    let tmp = TempDir::new().unwrap();
+    set(&tmp, "week.start", "sunday");
+    set(&tmp, "rounding.minutes", "15");
+
+    let text = fs::read_to_string(config_file(&tmp)).unwrap();
+    assert!(text.contains("[week]"), "{text}");
+    assert!(text.contains("minutes = 15"), "{text}");
+
+    vibe_clock(&tmp)
+        .args(["config", "get", "week.start"])
+        .assert()
+        .success()
+        .stdout("sunday\n");
+
+    let output = vibe_clock(&tmp)
+        .args(["--output-format", "json", "config", "list"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let settings = json["settings"].as_array().unwrap();
+    let rounding = settings
+        .iter()
+        .find(|s| s["key"] == "rounding.minutes")
+        .unwrap();
+    assert_eq!(rounding["value"], "15");
+    let title = settings.iter().find(|s| s["key"] == "pdf.title").unwrap();
+    assert!(title["value"].is_null());
+}
+
+#[test]
+fn unset_removes_setting() {
+    let tmp = TempDir::new().unwrap();
+    set(&tmp, "pdf.title", "Invoice hours");
+    vibe_clock(&tmp)
+        .args(["config", "unset", "pdf.title"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed pdf.title"));
+    vibe_clock(&tmp)
+        .args(["config", "get", "pdf.title"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("not set"));
+}
+
+#[test]
+fn set_keeps_comments_in_the_file() {
+    let tmp = TempDir::new().unwrap();
+    fs::write(
+        config_file(&tmp),
+        "# Work laptop\n[week]\nstart = \"monday\" # ISO weeks\n",
+    )
+    .unwrap();
+    set(&tmp, "week.start", "sunday");
+    set(&tmp, "pdf.title", "Hours");
+    let text = fs::read_to_string(config_file(&tmp)).unwrap();
+    assert!(text.starts_with("# Work laptop\n[week]\n"), "{text}");
+    assert!(text.contains("start = \"sunday\" # ISO weeks\n"), "{text}");
+    assert!(text.contains("[pdf]\ntitle = \"Hours\"\n"), "{text}");
+}
+
+#[test]
+fn invalid_values_are_user_errors() {
+    let tmp = TempDir::new().unwrap();
+    for (key, value, message) in [
+        (
+            "week.start",
+            "funday",
+            "week.start must be a day of the week",
+        ),
+        ("rounding.mode", "sideways", "rounding.mode must be"),
+        ("rounding.minutes", "quarter", "must be a whole number"),
+        ("display.date_format", "%Q", "not a valid strftime format"),
+        ("report.format", "docx", "report.format must be one of"),
+        (
+            "report.csv_delimiter",
+            "\n",
+            "report.csv_delimiter must be one printable character",
+        ),
+        ("no.such", "1", "Unknown setting 'no.such'"),
+    ] {
+        vibe_clock(&tmp)
+            .args(["config", "set", key, value])
+            .assert()
+            .code(1)
+            .stderr(predicate::str::contains(message));
+    }
+    assert!(!config_file(&tmp).exists());
+}
+
+#[test]
+fn invalid_config_file_is_reported() {
+    let tmp = TempDir::new().unwrap();
+    fs::write(config_file(&tmp), "[week]\nstart = \"funday\"\n").unwrap();
+    vibe_clock(&tmp)
+        .args(["project", "list"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Invalid config file"))
+        .stderr(predicate::str::contains("week.start"));
+
+    fs::write(config_file(&tmp), "[colours]\nfg = \"red\"\n").unwrap();
+    vibe_clock(&tmp)
+        .args(["project", "list"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("colours"));
+}
+
+#[test]
+fn missing_explicit_config_file_is_an_error() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["project", "list"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("does not exist"));
+}
+
+#[test]
+fn database_path_setting_picks_the_database() {
+    let tmp = TempDir::new().unwrap();
+    let db = tmp.path().join("elsewhere/time.db");
+    set(&tmp, "database.path", db.to_str().unwrap());
+    let mut cmd = vibe_clock(&tmp);
+    cmd.env_remove("VIBE_CLOCK_DB")
+        .env("VIBE_CLOCK_DATA_DIR", tmp.path().join("data"))
+        .env_remove("VIBE_CLOCK_PROFILE")
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success();
+    assert!(db.exists());
+}
+
+#[test]
+fn rounding_applies_to_report_entries() {
+    let tmp = TempDir::new().unwrap();
+    setup(&tmp);
+    add_task(&tmp, "09:00", "09:07");
+    add_task(&tmp, "10:00", "10:22");
+    set(&tmp, "rounding.minutes", "15");
+    set(&tmp, "rounding.mode", "up");
+
+    let output = vibe_clock(&tmp)
+        .args(["--output-format", "json", "report", "--from", "2026-02-25"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["report"]["grand_total"], 45);
+}
+
+#[test]
+fn report_defaults_come_from_config() {
+    let tmp = TempDir::new().unwrap();
+    setup(&tmp);
+    add_task(&tmp, "09:00", "10:00");
+    set(&tmp, "report.format", "csv");
+    set(&tmp, "report.csv_delimiter", ";");
+
+    vibe_clock(&tmp)
+        .args(["report", "--from", "2026-02-25"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Acme;Work"));
+    vibe_clock(&tmp)
+        .args(["report", "--from", "2026-02-25", "--format", "md"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("| Acme"));
+}
+
+#[test]
+fn display_formats_apply_to_text_output() {
+    let tmp = TempDir::new().unwrap();
+    setup(&tmp);
+    add_task(&tmp, "14:00", "15:30");
+    set(&tmp, "display.date_format", "%d.%m.%Y");
+    set(&tmp, "display.time_format", "%I:%M %p");
+
+    vibe_clock(&tmp)
+        .args(["journal", "2026-02-25"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Journal for 25.02.2026"))
+        .stdout(predicate::str::contains("02:00 PM"));
+    vibe_clock(&tmp)
+        .args(["report", "--from", "2026-02-25"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Report: 25.02.2026 to 25.02.2026"));
+}
+
+#[test]
+fn report_range_replaces_from() {
+    let tmp = TempDir::new().unwrap();
+    setup(&tmp);
+    vibe_clock(&tmp)
+        .args(["report"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("--from is required"));
+
+    vibe_clock(&tmp)
+        .args(["task", "add", "Acme", "Today's work", "--duration", "60"])
+        .assert()
+        .success();
+    set(&tmp, "report.range", "week");
+    vibe_clock(&tmp)
+        .args(["report"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Today's work"));
+}
+
+#[test]
+fn config_env_var_picks_the_file() {
+    let tmp = TempDir::new().unwrap();
+    let path = tmp.path().join("from-env.toml");
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_CONFIG", &path)
+        .current_dir(tmp.path())
+        .args(["config", "set", "week.start", "sunday"])
+        .assert()
+        .success();
+    assert!(fs::read_to_string(&path).unwrap().contains("sunday"));
+
+    // A missing file behaves like the default location: no settings
+    fs::remove_file(&path).unwrap();
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_CONFIG", &path)
+        .current_dir(tmp.path())
+        .env("VIBE_CLOCK_DB", tmp.path().join("test.db"))
+        .env("VIBE_CLOCK_KEY", "test-key")
+        .env("VIBE_CLOCK_BACKUP_KEEP", "0")
+        .args(["project", "list"])
+        .assert()
+        .success();
+}
@@ -0,0 +1,462 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn vibe_clock(tmp: &TempDir, key: &str) -> Command {
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_DB", tmp.path().join("test.db"));
+    cmd.env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"));
+    cmd.current_dir(tmp.path());
+    cmd.env("VIBE_CLOCK_KEY", key);
+    cmd.env("VIBE_CLOCK_BACKUP_KEEP", "0");
+    cmd
+}
+
+fn seed(tmp: &TempDir) {
+    vibe_clock(tmp, "old-key")
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn rekey_changes_passphrase() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+
+    vibe_clock(&tmp, "old-key")
+        .args(["db", "rekey"])
+        .write_stdin("old-key\nnew-key\nnew-key\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Passphrase changed."))
+        .stdout(predicate::str::contains("VIBE_CLOCK_KEY"));
+
+    vibe_clock(&tmp, "new-key")
+        .args(["project", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Acme"));
+    vibe_clock(&tmp, "old-key")
+        .args(["project", "list"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn rekey_json_reports_keyring() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+
+    let output = vibe_clock(&tmp, "old-key")
+        .args(["--output-format", "json", "db", "rekey"])
+        .write_stdin("old-key\nnew-key\nnew-key\n")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["rekeyed"]["keyring_updated"], false);
+}
+
+#[test]
+fn rekey_rejects_wrong_current_passphrase() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+
+    vibe_clock(&tmp, "old-key")
+        .args(["db", "rekey"])
+        .write_stdin("guess\nnew-key\nnew-key\n")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Current passphrase is incorrect"));
+
+    vibe_clock(&tmp, "old-key")
+        .args(["project", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Acme"));
+}
+
+#[test]
+fn rekey_rejects_mismatched_confirmation() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+
+    vibe_clock(&tmp, "old-key")
+        .args(["db", "rekey"])
+        .write_stdin("old-key\nnew-key\nnew-kee\n")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("do not match"));
+
+    vibe_clock(&tmp, "old-key")
+        .args(["project", "list"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn backups_restore_after_rekey() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+    vibe_clock(&tmp, "old-key")
+        .args(["backup", "create"])
+        .assert()
+        .success();
+    vibe_clock(&tmp, "old-key")
+        .args(["project", "add", "Later"])
+        .assert()
+        .success();
+
+    // The rekey run itself takes the day's automatic backup under the old passphrase
+    let output = vibe_clock(&tmp, "old-key")
+        .env("VIBE_CLOCK_BACKUP_KEEP", "7")
+        .args(["--output-format", "json", "db", "rekey"])
+        .write_stdin("old-key\nnew-key\nnew-key\n")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let rekeyed = json["rekeyed"]["backups_rekeyed"].as_array().unwrap();
+    assert_eq!(rekeyed.len(), 2);
+    assert_eq!(
+        json["rekeyed"]["backups_not_rekeyed"],
+        serde_json::json!([])
+    );
+
+    for backup in rekeyed {
+        vibe_clock(&tmp, "new-key")
+            .args(["backup", "restore", backup.as_str().unwrap(), "--yes"])
+            .assert()
+            .success();
+    }
+    vibe_clock(&tmp, "new-key")
+        .args(["project", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Acme"));
+}
+
+#[test]
+fn rekey_warns_about_backups_it_cannot_open() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+    let backups = tmp.path().join("backups");
+    std::fs::create_dir(&backups).unwrap();
+    std::fs::write(backups.join("foreign.db"), b"not a database").unwrap();
+
+    vibe_clock(&tmp, "old-key")
+        .args(["db", "rekey"])
+        .write_stdin("old-key\nnew-key\nnew-key\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Passphrase changed."))
+        .stderr(predicate::str::contains(
+            "foreign.db' still uses the old passphrase",
+        ));
+}
+
+fn vibe_clock_at(db: &std::path::Path, key: &str) -> Command {
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_DB", db);
+    cmd.env("VIBE_CLOCK_CONFIG", db.with_file_name("config.toml"));
+    cmd.current_dir(db.parent().unwrap());
+    cmd.env("VIBE_CLOCK_KEY", key);
+    cmd.env("VIBE_CLOCK_BACKUP_KEEP", "0");
+    cmd
+}
+
+#[test]
+fn decrypt_requires_explicit_flag() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+    let plain = tmp.path().join("plain.db");
+
+    vibe_clock(&tmp, "old-key")
+        .args(["db", "decrypt", "--output", plain.to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("--allow-plaintext"));
+    assert!(!plain.exists());
+}
+
+#[test]
+fn decrypt_then_encrypt_round_trips() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+    let plain = tmp.path().join("plain.db");
+
+    vibe_clock(&tmp, "old-key")
+        .args([
+            "db",
+            "decrypt",
+            "--output",
+            plain.to_str().unwrap(),
+            "--allow-plaintext",
+        ])
+        .assert()
+        .success();
+    let bytes = std::fs::read(&plain).unwrap();
+    assert!(bytes.starts_with(b"SQLite format 3\0"));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = std::fs::metadata(&plain).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600, "plaintext copy has mode {mode:o}");
+    }
+
+    let encrypted = tmp.path().join("encrypted.db");
+    vibe_clock(&tmp, "other-key")
+        .args([
+            "db",
+            "encrypt",
+            plain.to_str().unwrap(),
+            "--output",
+            encrypted.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    // The plaintext source is left alone when --output is given
+    assert!(
+        std::fs::read(&plain)
+            .unwrap()
+            .starts_with(b"SQLite format 3\0")
+    );
+
+    vibe_clock_at(&encrypted, "other-key")
+        .args(["project", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Acme"));
+}
+
+#[test]
+fn encrypt_replaces_plaintext_file() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+    let plain = tmp.path().join("plain.db");
+    vibe_clock(&tmp, "old-key")
+        .args([
+            "db",
+            "decrypt",
+            "--output",
+            plain.to_str().unwrap(),
+            "--allow-plaintext",
+        ])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp, "old-key")
+        .args(["db", "encrypt", plain.to_str().unwrap()])
+        .assert()
+        .success();
+    assert!(
+        !std::fs::read(&plain)
+            .unwrap()
+            .starts_with(b"SQLite format 3")
+    );
+    vibe_clock_at(&plain, "old-key")
+        .args(["project", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Acme"));
+}
+
+#[test]
+fn encrypt_rejects_encrypted_file() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+    let live = tmp.path().join("test.db");
+    let output = tmp.path().join("out.db");
+
+    vibe_clock(&tmp, "old-key")
+        .args([
+            "db",
+            "encrypt",
+            live.to_str().unwrap(),
+            "--output",
+            output.to_str().unwrap(),
+        ])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("not a plaintext SQLite database"));
+    assert!(!output.exists());
+}
+
+/// Run raw SQL against the test database, bypassing the application's validation.
+fn corrupt(tmp: &TempDir, sql: &str) {
+    let db = vibe_clock::db::Database::open(&tmp.path().join("test.db"), "old-key").unwrap();
+    db.conn.pragma_update(None, "foreign_keys", "OFF").unwrap();
+    db.conn.execute_batch(sql).unwrap();
+}
+
+fn seed_tasks(tmp: &TempDir) {
+    seed(tmp);
+    for (description, start, end) in [("Planning", "09:00", "10:00"), ("Review", "11:00", "11:30")]
+    {
+        vibe_clock(tmp, "old-key")
+            .args([
+                "task",
+                "add",
+                "Acme",
+                description,
+                "--start",
+                start,
+                "--end",
+                end,
+                "--date",
+                "2026-03-02",
+            ])
+            .assert()
+            .success();
+    }
+}
+
+fn check_json(tmp: &TempDir, args: &[&str]) -> serde_json::Value {
+    let output = vibe_clock(tmp, "old-key")
+        .args(["--output-format", "json", "db", "check"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn check_healthy_database() {
+    let tmp = TempDir::new().unwrap();
+    seed_tasks(&tmp);
+
+    vibe_clock(&tmp, "old-key")
+        .args(["db", "check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No problems found."));
+}
+
+#[test]
+fn check_quarantines_unreadable_task() {
+    let tmp = TempDir::new().unwrap();
+    seed_tasks(&tmp);
+    corrupt(
+        &tmp,
+        "UPDATE task_entry SET start_time = '2026-03-02 9am' WHERE description = 'Planning';",
+    );
+    vibe_clock(&tmp, "old-key")
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("db check"));
+
+    // Without --fix nothing changes
+    let json = check_json(&tmp, &[]);
+    let findings = json["check"]["findings"].as_array().unwrap();
+    assert_eq!(findings.len(), 1, "{json}");
+    assert_eq!(findings[0]["table"], "task_entry");
+    assert_eq!(findings[0]["repair"]["action"], "quarantine");
+    assert!(json["check"].get("repaired").is_none());
+
+    let json = check_json(&tmp, &["--fix", "--yes"]);
+    assert_eq!(json["check"]["repaired"], 1);
+    assert_eq!(json["check"]["remaining"].as_array().unwrap().len(), 0);
+
+    vibe_clock(&tmp, "old-key")
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Review"))
+        .stdout(predicate::str::contains("Planning").not());
+
+    let db = vibe_clock::db::Database::open(&tmp.path().join("test.db"), "old-key").unwrap();
+    let (table, data): (String, String) = db
+        .conn
+        .query_row("SELECT source_table, row_data FROM quarantine", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(table, "task_entry");
+    assert!(data.contains("Planning"), "{data}");
+}
+
+#[test]
+fn check_recomputes_duration_and_resets_timestamps() {
+    let tmp = TempDir::new().unwrap();
+    seed_tasks(&tmp);
+    corrupt(
+        &tmp,
+        "UPDATE task_entry SET duration_min = 45 WHERE description = 'Planning';
+         UPDATE project SET created_at = 'yesterday' WHERE name = 'Acme';",
+    );
+
+    let json = check_json(&tmp, &["--fix", "--yes"]);
+    let findings = json["check"]["findings"].as_array().unwrap();
+    assert_eq!(findings.len(), 2, "{json}");
+    assert!(findings.iter().any(|f| f["repair"]
+        == serde_json::json!({ "action": "recompute_duration", "duration_min": 60 })));
+    assert!(findings.iter().any(|f| f["repair"]
+        == serde_json::json!({ "action": "reset_timestamp", "column": "created_at" })));
+    assert_eq!(json["check"]["repaired"], 2);
+
+    vibe_clock(&tmp, "old-key")
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("10:00  1h"));
+}
+
+#[test]
+fn check_reports_orphaned_rows() {
+    let tmp = TempDir::new().unwrap();
+    seed_tasks(&tmp);
+    corrupt(
+        &tmp,
+        "UPDATE task_entry SET project_id = 99 WHERE description = 'Review';",
+    );
+
+    vibe_clock(&tmp, "old-key")
+        .args(["db", "check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "task_entry #2: refers to a missing project row",
+        ))
+        .stdout(predicate::str::contains("db check --fix"));
+}
+
+#[test]
+fn check_fix_can_be_cancelled() {
+    let tmp = TempDir::new().unwrap();
+    seed_tasks(&tmp);
+    corrupt(
+        &tmp,
+        "UPDATE task_entry SET duration_min = 45 WHERE id = 1;",
+    );
+
+    vibe_clock(&tmp, "old-key")
+        .args(["db", "check", "--fix"])
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cancelled."));
+    let json = check_json(&tmp, &[]);
+    assert_eq!(json["check"]["findings"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn check_works_with_unreadable_clock() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+    vibe_clock(&tmp, "old-key")
+        .args(["clock", "start", "Acme", "Review"])
+        .assert()
+        .success();
+    corrupt(&tmp, "UPDATE clock_state SET start_time = 'soon';");
+
+    let json = check_json(&tmp, &["--fix", "--yes"]);
+    assert_eq!(json["check"]["repaired"], 1);
+    vibe_clock(&tmp, "old-key")
+        .args(["clock", "status"])
+        .assert()
+        .success();
+}
@@ -0,0 +1,180 @@
+use std::fs;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn vibe_clock(tmp: &TempDir, db: &str, key: &str) -> Command {
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_DB", tmp.path().join(db));
+    cmd.env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"));
+    cmd.current_dir(tmp.path());
+    cmd.env("VIBE_CLOCK_KEY", key);
+    cmd
+}
+
+fn seed(tmp: &TempDir) {
+    let run = |args: &[&str]| {
+        vibe_clock(tmp, "old.db", "old-key")
+            .args(args)
+            .assert()
+            .success();
+    };
+    run(&["project", "add", "Acme"]);
+    run(&["project", "add", "Legacy"]);
+    run(&[
+        "task",
+        "add",
+        "Acme",
+        "Planning",
+        "--start",
+        "09:00",
+        "--end",
+        "10:00",
+        "--date",
+        "2026-03-02",
+    ]);
+    run(&["task", "add", "Acme", "Typo", "--duration", "5m"]);
+    run(&["task", "delete", "2", "--yes"]);
+    run(&["project", "delete", "2", "--yes"]);
+    run(&["clock", "start", "Acme", "Review"]);
+}
+
+#[test]
+fn dump_contains_all_tables_and_schema_version() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+
+    let output = vibe_clock(&tmp, "old.db", "old-key")
+        .args(["export", "all", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let dump: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert_eq!(dump["format"], "vibe-clock-dump");
+    assert!(dump["schema_version"].as_i64().unwrap() >= 7);
+    assert_eq!(dump["tables"]["project"].as_array().unwrap().len(), 2);
+    assert_eq!(dump["tables"]["task_entry"].as_array().unwrap().len(), 2);
+    assert_eq!(dump["tables"]["task_entry"][0]["description"], "Planning");
+    assert_eq!(
+        dump["tables"]["task_entry"][0]["start_time"],
+        "2026-03-02T09:00:00"
+    );
+    assert!(dump["tables"]["task_entry"][1]["deleted_at"].is_string());
+    assert_eq!(dump["tables"]["clock_state"][0]["description"], "Review");
+    assert!(
+        !dump["tables"]["operation_log"]
+            .as_array()
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn restore_into_new_database_with_another_passphrase() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+    let file = tmp.path().join("dump.json");
+
+    vibe_clock(&tmp, "old.db", "old-key")
+        .args(["export", "all", "--json", "--output"])
+        .arg(&file)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Exported 2 projects and 2 task entries",
+        ));
+
+    vibe_clock(&tmp, "new.db", "new-key")
+        .args(["import", "all"])
+        .arg(&file)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Restored 2 projects and 2 task entries",
+        ));
+
+    // IDs, trash, the running clock and the undo log all carry over
+    vibe_clock(&tmp, "new.db", "new-key")
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1      Acme            Planning"));
+    vibe_clock(&tmp, "new.db", "new-key")
+        .args(["trash", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Legacy"))
+        .stdout(predicate::str::contains("Typo"));
+    vibe_clock(&tmp, "new.db", "new-key")
+        .args(["clock", "status"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("'Review' on project 'Acme'"));
+    vibe_clock(&tmp, "new.db", "new-key")
+        .arg("undo")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("start clock"));
+
+    // A second dump of the restored database is identical apart from its timestamp
+    let again = tmp.path().join("again.json");
+    vibe_clock(&tmp, "new.db", "new-key")
+        .args(["redo"])
+        .assert()
+        .success();
+    vibe_clock(&tmp, "new.db", "new-key")
+        .args(["export", "all", "--json", "--output"])
+        .arg(&again)
+        .assert()
+        .success();
+    let tables = |path: &std::path::Path| {
+        let dump: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        dump["tables"]["task_entry"].clone()
+    };
+    assert_eq!(tables(&file), tables(&again));
+}
+
+#[test]
+fn restore_refuses_non_empty_database_and_foreign_files() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+    let file = tmp.path().join("dump.json");
+    vibe_clock(&tmp, "old.db", "old-key")
+        .args(["export", "all", "--json", "--output"])
+        .arg(&file)
+        .assert()
+        .success();
+
+    vibe_clock(&tmp, "old.db", "old-key")
+        .args(["import", "all"])
+        .arg(&file)
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("The database is not empty."));
+
+    let newer = tmp.path().join("newer.json");
+    let content = fs::read_to_string(&file).unwrap();
+    let mut dump: serde_json::Value = serde_json::from_str(&content).unwrap();
+    dump["schema_version"] = 999.into();
+    fs::write(&newer, dump.to_string()).unwrap();
+    vibe_clock(&tmp, "new.db", "new-key")
+        .args(["import", "all"])
+        .arg(&newer)
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("schema version 999"));
+
+    let other = tmp.path().join("other.json");
+    fs::write(&other, r#"{"format":"something-else","schema_version":1,"app_version":"1","exported_at":"2026-01-01T00:00:00","tables":{}}"#).unwrap();
+    vibe_clock(&tmp, "new.db", "new-key")
+        .args(["import", "all"])
+        .arg(&other)
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Not a vibe-clock dump"));
+}
@@ -0,0 +1,230 @@
+use std::fs;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn vibe_clock(tmp: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_DB", tmp.path().join("test.db"));
+    cmd.env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"));
+    cmd.current_dir(tmp.path());
+    cmd.env("VIBE_CLOCK_KEY", "test-key");
+    cmd.env("TZ", "UTC");
+    cmd
+}
+
+fn seed(tmp: &TempDir) {
+    vibe_clock(tmp)
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success();
+    vibe_clock(tmp)
+        .args(["project", "add", "Beta"])
+        .assert()
+        .success();
+    vibe_clock(tmp)
+        .args([
+            "task",
+            "add",
+            "Acme",
+            "Planning",
+            "--start",
+            "09:00",
+            "--end",
+            "10:15",
+            "--date",
+            "2026-03-02",
+        ])
+        .assert()
+        .success();
+    vibe_clock(tmp)
+        .args([
+            "task",
+            "add",
+            "Beta",
+            "Review",
+            "--duration",
+            "45m",
+            "--date",
+            "2026-03-03",
+        ])
+        .assert()
+        .success();
+}
+
+#[test]
+fn exports_timeclock_with_duration_only_marker() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["export", "--format", "timeclock"])
+        .assert()
+        .success()
+        .stdout(
+            "i 2026/03/02 09:00:00 Acme  Planning\n\
+             o 2026/03/02 10:15:00\n\
+             ; duration-only\n\
+             i 2026/03/03 00:00:00 Beta  Review\n\
+             o 2026/03/03 00:45:00\n",
+        );
+}
+
+#[test]
+fn exports_timewarrior_in_utc() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["export", "--format", "timewarrior"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"{"start":"20260302T090000Z","end":"20260302T101500Z","tags":["Acme"],"annotation":"Planning"},"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"{"start":"20260303T000000Z","end":"20260303T004500Z","tags":["Beta","duration-only"],"annotation":"Review"}"#,
+        ));
+}
+
+#[test]
+fn date_range_and_project_filter() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["export", "--format", "timeclock", "--from", "2026-03-03"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Review"))
+        .stdout(predicate::str::contains("Planning").not());
+
+    vibe_clock(&tmp)
+        .args(["export", "--format", "timeclock", "--project", "Acme"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Planning"))
+        .stdout(predicate::str::contains("Review").not());
+
+    vibe_clock(&tmp)
+        .args(["export", "--format", "timeclock", "--project", "Nope"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Project 'Nope' not found."));
+}
+
+#[test]
+fn timeclock_rejects_project_names_it_cannot_represent() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["project", "add", "Big  Client"])
+        .assert()
+        .success();
+    vibe_clock(&tmp)
+        .args(["task", "add", "Big  Client", "Call", "--duration", "30m"])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp)
+        .args(["export", "--format", "timeclock"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Project 'Big  Client' cannot be written to a timeclock file",
+        ));
+}
+
+#[test]
+fn round_trips_through_the_importers() {
+    for format in ["timeclock", "timewarrior"] {
+        let source = TempDir::new().unwrap();
+        seed(&source);
+        let file = source.path().join("export.out");
+        vibe_clock(&source)
+            .args(["export", "--format", format, "--output"])
+            .arg(&file)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Exported 2 entries"));
+
+        let target = TempDir::new().unwrap();
+        vibe_clock(&target)
+            .args(["import", format])
+            .arg(&file)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Imported 2 entries"));
+
+        let exported = fs::read_to_string(&file).unwrap();
+        let again = target.path().join("again.out");
+        vibe_clock(&target)
+            .args(["export", "--format", format, "--output"])
+            .arg(&again)
+            .assert()
+            .success();
+        assert_eq!(fs::read_to_string(&again).unwrap(), exported);
+    }
+}
+
+#[test]
+fn exports_ics_events_with_stable_uids() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+
+    let output = vibe_clock(&tmp)
+        .args([
+            "export",
+            "ics",
+            "--from",
+            "2026-03-01",
+            "--to",
+            "2026-03-31",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let ics = String::from_utf8(output).unwrap();
+
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    assert!(ics.contains("UID:task-1@vibe-clock\r\n"));
+    assert!(ics.contains("DTSTART:20260302T090000Z\r\nDTEND:20260302T101500Z\r\n"));
+    assert!(ics.contains("SUMMARY:Acme: Planning\r\n"));
+    // Duration-only entries become all-day events
+    assert!(ics.contains("UID:task-2@vibe-clock\r\n"));
+    assert!(ics.contains("DTSTART;VALUE=DATE:20260303\r\nDTEND;VALUE=DATE:20260304\r\n"));
+    assert!(ics.contains("DESCRIPTION:Logged 45m (no start or end time)\r\n"));
+}
+
+#[test]
+fn ics_escapes_and_folds_long_summaries() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success();
+    let description = format!("Sync; notes, and {}", "x".repeat(80));
+    vibe_clock(&tmp)
+        .args(["task", "add", "Acme", &description, "--duration", "30m"])
+        .assert()
+        .success();
+
+    let output = vibe_clock(&tmp)
+        .args(["export", "--format", "ics"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let ics = String::from_utf8(output).unwrap();
+
+    assert!(ics.lines().all(|l| l.len() <= 76));
+    let unfolded = ics.replace("\r\n ", "");
+    assert!(unfolded.contains(&format!(
+        "SUMMARY:Acme: Sync\\; notes\\, and {}\r\n",
+        "x".repeat(80)
+    )));
+}
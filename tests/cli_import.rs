@@ -0,0 +1,531 @@
+use std::fs;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn vibe_clock(tmp: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_DB", tmp.path().join("test.db"));
+    cmd.env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"));
+    cmd.current_dir(tmp.path());
+    cmd.env("VIBE_CLOCK_KEY", "test-key");
+    cmd
+}
+
+fn write_csv(tmp: &TempDir, content: &str) -> String {
+    let path = tmp.path().join("entries.csv");
+    fs::write(&path, content).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+fn write_file(tmp: &TempDir, name: &str, content: &str) -> String {
+    let path = tmp.path().join(name);
+    fs::write(&path, content).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn imports_csv_and_creates_missing_projects() {
+    let tmp = TempDir::new().unwrap();
+    let file = write_csv(
+        &tmp,
+        "date,project,description,start,end,duration\n\
+         2026-03-02,Acme,Planning,09:00,10:00,\n\
+         2026-03-02,Beta,Design,,,1h30m\n",
+    );
+
+    vibe_clock(&tmp)
+        .args(["import", "csv", &file])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Imported 2 entries, new projects: Acme, Beta.",
+        ));
+
+    vibe_clock(&tmp)
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Planning"))
+        .stdout(predicate::str::contains("Design"))
+        .stdout(predicate::str::contains("2h 30m"));
+}
+
+#[test]
+fn column_mapping_renames_fields() {
+    let tmp = TempDir::new().unwrap();
+    let file = write_csv(
+        &tmp,
+        "Day;Client;Notes;Hours\n\
+         2026-03-02;Acme;Support;45m\n",
+    );
+
+    vibe_clock(&tmp)
+        .args([
+            "import",
+            "csv",
+            &file,
+            "--delimiter",
+            ";",
+            "--map",
+            "date=Day,project=Client,description=Notes,duration=Hours",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Imported 1 entries"));
+
+    vibe_clock(&tmp)
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Support"));
+}
+
+#[test]
+fn invalid_rows_abort_the_whole_import() {
+    let tmp = TempDir::new().unwrap();
+    let file = write_csv(
+        &tmp,
+        "date,project,description,start,end\n\
+         2026-03-02,Acme,Fine,09:00,10:00\n\
+         2026-03-02,Acme,Backwards,11:00,10:00\n\
+         2026-03-02,Acme,No timing,,\n",
+    );
+
+    vibe_clock(&tmp)
+        .args(["import", "csv", &file])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "line 3: End time must be after start time.",
+        ))
+        .stderr(predicate::str::contains("line 4: Provide either"))
+        .stderr(predicate::str::contains("2 rows could not be imported"));
+
+    vibe_clock(&tmp)
+        .args(["project", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No projects found"));
+}
+
+#[test]
+fn no_create_rejects_unknown_projects() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success();
+    let file = write_csv(
+        &tmp,
+        "project,description,duration\n\
+         Acme,Known,30m\n\
+         Other,Unknown,30m\n",
+    );
+
+    vibe_clock(&tmp)
+        .args(["import", "csv", &file, "--no-create"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "line 3: Project 'Other' not found",
+        ));
+}
+
+#[test]
+fn dry_run_previews_without_changes() {
+    let tmp = TempDir::new().unwrap();
+    let file = write_csv(
+        &tmp,
+        "date,project,description,duration\n\
+         2026-03-02,Acme,Planning,1h\n",
+    );
+
+    vibe_clock(&tmp)
+        .args(["import", "csv", &file, "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Planning"))
+        .stdout(predicate::str::contains(
+            "Dry run: 1 entries would be imported, new projects: Acme. Nothing was changed.",
+        ));
+
+    vibe_clock(&tmp)
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No tasks logged"));
+}
+
+#[test]
+fn import_is_undone_as_one_operation() {
+    let tmp = TempDir::new().unwrap();
+    let file = write_csv(
+        &tmp,
+        "date,project,description,duration\n\
+         2026-03-02,Acme,One,1h\n\
+         2026-03-02,Acme,Two,1h\n",
+    );
+
+    vibe_clock(&tmp)
+        .args(["import", "csv", &file])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp)
+        .arg("undo")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Undone: import of 2 entries"));
+
+    vibe_clock(&tmp)
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No tasks logged"));
+}
+
+#[test]
+fn csv_report_export_round_trips() {
+    let tmp = TempDir::new().unwrap();
+    let file = write_csv(
+        &tmp,
+        "date,project,description,start,end\n\
+         2026-03-02,Acme,Planning,09:00,10:30\n",
+    );
+    vibe_clock(&tmp)
+        .args(["import", "csv", &file])
+        .assert()
+        .success();
+
+    let exported = tmp.path().join("export.csv");
+    vibe_clock(&tmp)
+        .args([
+            "report",
+            "--from",
+            "2026-03-02",
+            "--csv",
+            exported.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp)
+        .args(["import", "csv", exported.to_str().unwrap(), "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("09:00  10:30  1h 30m"));
+}
+
+#[test]
+fn imports_timewarrior_export_and_skips_duplicates() {
+    let tmp = TempDir::new().unwrap();
+    let file = write_file(
+        &tmp,
+        "timew.json",
+        r#"[
+            {"id":3,"start":"20260302T090000Z","end":"20260302T101500Z","tags":["Acme","planning"]},
+            {"id":2,"start":"20260302T110000Z","end":"20260302T113000Z","tags":["Beta"],"annotation":"Code review"},
+            {"id":1,"start":"20260302T140000Z","tags":["Acme"]}
+        ]"#,
+    );
+
+    vibe_clock(&tmp)
+        .env("TZ", "UTC")
+        .args(["import", "timewarrior", &file])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Skipped interval 3: still running",
+        ))
+        .stdout(predicate::str::contains(
+            "Imported 2 entries, new projects: Acme, Beta (1 skipped).",
+        ));
+
+    vibe_clock(&tmp)
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("planning"))
+        .stdout(predicate::str::contains("Code review"))
+        .stdout(predicate::str::contains("09:00"))
+        .stdout(predicate::str::contains("1h 45m"));
+
+    // Re-running the same import changes nothing
+    vibe_clock(&tmp)
+        .env("TZ", "UTC")
+        .args(["import", "timewarrior", &file])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("already imported as task 1"))
+        .stdout(predicate::str::contains("Imported 0 entries (3 skipped)."));
+}
+
+#[test]
+fn imports_watson_frames() {
+    let tmp = TempDir::new().unwrap();
+    // 2026-03-02 09:00:00 UTC = 1772442000
+    let file = write_file(
+        &tmp,
+        "frames",
+        r#"[
+            [1772442000, 1772445600, "acme", "0f1e2d3c", ["support", "email"], 1772445600],
+            [1772445600, 1772445620, "acme", "9a8b7c6d", [], 1772445620]
+        ]"#,
+    );
+
+    vibe_clock(&tmp)
+        .env("TZ", "UTC")
+        .args(["import", "watson", &file])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Skipped frame 2: shorter than a minute",
+        ))
+        .stdout(predicate::str::contains(
+            "Imported 1 entries, new projects: acme",
+        ));
+
+    vibe_clock(&tmp)
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("support, email"))
+        .stdout(predicate::str::contains("09:00"))
+        .stdout(predicate::str::contains("10:00"));
+}
+
+#[test]
+fn duplicate_frames_in_one_file_import_once() {
+    let tmp = TempDir::new().unwrap();
+    let file = write_file(
+        &tmp,
+        "frames",
+        r#"[
+            [1772442000, 1772445600, "acme", "0f1e2d3c", [], 1772445600],
+            [1772442000, 1772445600, "acme", "0f1e2d3c", [], 1772445600]
+        ]"#,
+    );
+
+    vibe_clock(&tmp)
+        .env("TZ", "UTC")
+        .args(["import", "watson", &file, "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Skipped frame 2: same entry as frame 1",
+        ));
+    vibe_clock(&tmp)
+        .env("TZ", "UTC")
+        .args(["import", "watson", &file])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Imported 1 entries, new projects: acme (1 skipped).",
+        ));
+}
+
+#[test]
+fn imports_timeclock_file() {
+    let tmp = TempDir::new().unwrap();
+    let file = write_file(
+        &tmp,
+        "work.timeclock",
+        "; exported from ledger\n\
+         i 2026/03/02 09:00:00 Acme:Dev  Fix login bug\n\
+         o 2026/03/02 10:30:00\n\
+         i  2026/03/02\t13:00   Acme:Dev\n\
+         o 2026/03/02  13:45\n",
+    );
+
+    vibe_clock(&tmp)
+        .args(["import", "timeclock", &file])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Imported 2 entries, new projects: Acme:Dev.",
+        ));
+
+    vibe_clock(&tmp)
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Fix login bug"))
+        .stdout(predicate::str::contains("2h 15m"));
+}
+
+#[test]
+fn unmatched_timeclock_out_fails_the_import() {
+    let tmp = TempDir::new().unwrap();
+    let file = write_file(
+        &tmp,
+        "work.timeclock",
+        "i 2026/03/02 09:00:00 Acme  Planning\n\
+         o 2026/03/02 10:00:00\n\
+         o 2026/03/02 11:00:00\n",
+    );
+
+    vibe_clock(&tmp)
+        .args(["import", "timeclock", &file])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "line 3: Clock-out without a preceding clock-in.",
+        ));
+
+    vibe_clock(&tmp)
+        .args(["project", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Acme").not());
+}
+
+const CALENDAR: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+X-WR-CALNAME:Work\r\n\
+BEGIN:VEVENT\r\n\
+UID:standup\r\n\
+DTSTART;TZID=Europe/Berlin:20260302T093000\r\n\
+DTEND;TZID=Europe/Berlin:20260302T094500\r\n\
+RRULE:FREQ=WEEKLY;BYDAY=MO,WE;COUNT=4\r\n\
+EXDATE;TZID=Europe/Berlin:20260304T093000\r\n\
+SUMMARY:Standup\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:standup\r\n\
+RECURRENCE-ID;TZID=Europe/Berlin:20260309T093000\r\n\
+DTSTART;TZID=Europe/Berlin:20260309T100000\r\n\
+DTEND;TZID=Europe/Berlin:20260309T101500\r\n\
+SUMMARY:Standup (moved)\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:call\r\n\
+DTSTART:20260303T130000Z\r\n\
+DURATION:PT1H\r\n\
+SUMMARY:Call with Acme\\, re\r\n \
+view\r\n\
+CATEGORIES:Client A\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:declined\r\n\
+DTSTART:20260303T150000Z\r\n\
+DTEND:20260303T160000Z\r\n\
+SUMMARY:Budget meeting\r\n\
+ATTENDEE;PARTSTAT=DECLINED:mailto:me@example.com\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:holiday\r\n\
+DTSTART;VALUE=DATE:20260305\r\n\
+SUMMARY:Holiday\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:lunch\r\n\
+DTSTART:20260305T120000Z\r\n\
+DTEND:20260305T130000Z\r\n\
+SUMMARY:Lunch\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+#[test]
+fn imports_ics_events_with_project_rules() {
+    let tmp = TempDir::new().unwrap();
+    let file = write_file(&tmp, "work.ics", CALENDAR);
+    let import = |tmp: &TempDir| {
+        let mut cmd = vibe_clock(tmp);
+        cmd.env("TZ", "UTC").args([
+            "import",
+            "ics",
+            &file,
+            "--rule",
+            "standup=Internal",
+            "--rule",
+            "category:client a=Acme",
+            "--me",
+            "me@example.com",
+        ]);
+        cmd
+    };
+
+    import(&tmp)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Skipped line 27: declined"))
+        .stdout(predicate::str::contains("Skipped line 34: all-day event"))
+        .stdout(predicate::str::contains(
+            "Skipped line 39: no --rule matches and no --project was given",
+        ))
+        .stdout(predicate::str::contains(
+            "Imported 4 entries, new projects: Internal, Acme (3 skipped).",
+        ));
+
+    // The series expands to Mar 2 and 11; Mar 4 is excluded and Mar 9 was moved
+    vibe_clock(&tmp)
+        .args(["report", "--from", "2026-03-01", "--to", "2026-03-31"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Call with Acme, review"))
+        .stdout(predicate::str::contains("Standup (moved)"))
+        .stdout(predicate::str::contains("2026-03-04").not())
+        .stdout(predicate::str::contains("Budget meeting").not())
+        .stdout(predicate::str::contains("Lunch").not());
+
+    vibe_clock(&tmp)
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("08:30"));
+
+    // Importing the calendar again only skips
+    import(&tmp)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Imported 0 entries (7 skipped)."));
+}
+
+#[test]
+fn ics_date_range_and_default_project() {
+    let tmp = TempDir::new().unwrap();
+    let file = write_file(&tmp, "work.ics", CALENDAR);
+
+    vibe_clock(&tmp)
+        .env("TZ", "UTC")
+        .args([
+            "import",
+            "ics",
+            &file,
+            "--from",
+            "2026-03-05",
+            "--to",
+            "2026-03-05",
+            "--project",
+            "Meetings",
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Lunch"))
+        .stdout(predicate::str::contains("Standup").not())
+        .stdout(predicate::str::contains(
+            "Dry run: 1 entries would be imported, new projects: Meetings (1 skipped).",
+        ));
+}
+
+#[test]
+fn ics_rejects_invalid_rules_and_files() {
+    let tmp = TempDir::new().unwrap();
+    let file = write_file(&tmp, "work.ics", CALENDAR);
+    let not_ics = write_file(&tmp, "notes.txt", "hello\n");
+
+    vibe_clock(&tmp)
+        .args(["import", "ics", &file, "--rule", "room:A=Acme"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Invalid rule 'room:A=Acme'"));
+
+    vibe_clock(&tmp)
+        .args(["import", "ics", &not_ics, "--project", "Acme"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("is not an iCalendar file."));
+}
@@ -5,6 +5,8 @@ use tempfile::TempDir;
 fn vibe_clock(tmp: &TempDir) -> Command {
     let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
     cmd.env("VIBE_CLOCK_DB", tmp.path().join("test.db"));
+    cmd.env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"));
+    cmd.current_dir(tmp.path());
     cmd.env("VIBE_CLOCK_KEY", "test-key");
     cmd
 }
@@ -121,3 +123,41 @@ fn shows_per_project_totals_and_grand_total() {
     assert!(stdout.contains("Beta"));
     assert!(stdout.contains("TOTAL"));
 }
+
+#[test]
+fn accepts_relative_dates() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success();
+    vibe_clock(&tmp)
+        .args([
+            "task",
+            "add",
+            "Acme",
+            "Two days back",
+            "--duration",
+            "30",
+            "--date",
+            "2 days ago",
+        ])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp)
+        .args(["journal", "-2d"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Two days back"));
+    vibe_clock(&tmp)
+        .args(["journal", "-1d"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No tasks logged"));
+    vibe_clock(&tmp)
+        .args(["journal", "next tuesday"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Invalid date"));
+}
@@ -0,0 +1,134 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use serde_json::Value;
+use tempfile::TempDir;
+
+fn vibe_clock(tmp: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_DB", tmp.path().join("test.db"));
+    cmd.env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"));
+    cmd.current_dir(tmp.path());
+    cmd.env("VIBE_CLOCK_KEY", "test-key");
+    cmd
+}
+
+fn json_stdout(cmd: &mut Command) -> Value {
+    let output = cmd.assert().success().get_output().stdout.clone();
+    serde_json::from_slice(&output).expect("stdout should be a JSON document")
+}
+
+fn setup_project_with_task(tmp: &TempDir) {
+    vibe_clock(tmp)
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success();
+    vibe_clock(tmp)
+        .args([
+            "task",
+            "add",
+            "Acme",
+            "Billable work",
+            "--start",
+            "09:00",
+            "--end",
+            "10:30",
+            "--date",
+            "2026-03-02",
+        ])
+        .assert()
+        .success();
+}
+
+#[test]
+fn task_add_emits_created_entry() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success();
+
+    let doc = json_stdout(vibe_clock(&tmp).args([
+        "--output-format",
+        "json",
+        "task",
+        "add",
+        "Acme",
+        "Billable work",
+        "--duration",
+        "45m",
+    ]));
+    assert_eq!(doc["task"]["id"], 1);
+    assert_eq!(doc["task"]["description"], "Billable work");
+    assert_eq!(doc["task"]["duration_min"], 45);
+    assert_eq!(doc["task"]["start_time"], Value::Null);
+}
+
+#[test]
+fn project_list_includes_task_counts() {
+    let tmp = TempDir::new().unwrap();
+    setup_project_with_task(&tmp);
+
+    let doc = json_stdout(vibe_clock(&tmp).args(["project", "list", "--output-format", "json"]));
+    assert_eq!(doc["projects"][0]["name"], "Acme");
+    assert_eq!(doc["projects"][0]["task_count"], 1);
+}
+
+#[test]
+fn journal_json_has_entries_and_totals() {
+    let tmp = TempDir::new().unwrap();
+    setup_project_with_task(&tmp);
+
+    let doc =
+        json_stdout(vibe_clock(&tmp).args(["journal", "2026-03-02", "--output-format", "json"]));
+    let journal = &doc["journal"];
+    assert_eq!(journal["date"], "2026-03-02");
+    assert_eq!(journal["tasks"][0]["project_name"], "Acme");
+    assert_eq!(
+        journal["tasks"][0]["task"]["start_time"],
+        "2026-03-02T09:00:00"
+    );
+    assert_eq!(journal["project_totals"][0]["total"], 90);
+    assert_eq!(journal["grand_total"], 90);
+}
+
+#[test]
+fn report_tsv_has_header_and_one_row_per_entry() {
+    let tmp = TempDir::new().unwrap();
+    setup_project_with_task(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["report", "--from", "2026-03-02", "--output-format", "tsv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "date\tid\tproject\tdescription\tstart_time\tend_time\tduration_min\tedited\n",
+        ))
+        .stdout(predicate::str::contains(
+            "2026-03-02\t1\tAcme\tBillable work\t2026-03-02T09:00:00\t2026-03-02T10:30:00\t90\tfalse",
+        ));
+}
+
+#[test]
+fn clock_status_is_null_when_idle() {
+    let tmp = TempDir::new().unwrap();
+
+    let doc = json_stdout(vibe_clock(&tmp).args(["clock", "status", "--output-format", "json"]));
+    assert_eq!(doc["clock"], Value::Null);
+}
+
+#[test]
+fn errors_are_json_on_stderr() {
+    let tmp = TempDir::new().unwrap();
+
+    let output = vibe_clock(&tmp)
+        .args(["task", "delete", "42", "--yes", "--output-format", "json"])
+        .assert()
+        .code(1)
+        .get_output()
+        .stderr
+        .clone();
+    let doc: Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(doc["error"]["kind"], "user");
+    assert_eq!(doc["error"]["exit_code"], 1);
+    assert_eq!(doc["error"]["message"], "Task with ID 42 not found.");
+}
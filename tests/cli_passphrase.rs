@@ -0,0 +1,146 @@
+use std::fs;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+/// A command with no passphrase source configured.
+fn vibe_clock(tmp: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_DB", tmp.path().join("test.db"));
+    cmd.env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"));
+    cmd.current_dir(tmp.path());
+    cmd.env("VIBE_CLOCK_BACKUP_KEEP", "0");
+    cmd.env_remove("VIBE_CLOCK_KEY");
+    cmd.env_remove("VIBE_CLOCK_KEY_FILE");
+    cmd.env_remove("VIBE_CLOCK_KEY_COMMAND");
+    cmd
+}
+
+fn seed(tmp: &TempDir) {
+    vibe_clock(tmp)
+        .env("VIBE_CLOCK_KEY", "file-secret")
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success();
+}
+
+#[cfg(unix)]
+fn write_key_file(tmp: &TempDir, mode: u32) -> std::path::PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = tmp.path().join("key");
+    fs::write(&path, "file-secret\n").unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(mode)).unwrap();
+    path
+}
+
+#[cfg(unix)]
+#[test]
+fn reads_passphrase_from_key_file() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+    let key = write_key_file(&tmp, 0o600);
+
+    vibe_clock(&tmp)
+        .env("VIBE_CLOCK_KEY_FILE", &key)
+        .args(["project", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Acme"));
+}
+
+#[cfg(unix)]
+#[test]
+fn refuses_world_readable_key_file() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+    let key = write_key_file(&tmp, 0o644);
+
+    vibe_clock(&tmp)
+        .env("VIBE_CLOCK_KEY_FILE", &key)
+        .args(["project", "list"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("readable by everyone"));
+}
+
+#[test]
+fn missing_key_file_is_an_error() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .env("VIBE_CLOCK_KEY_FILE", tmp.path().join("nope"))
+        .args(["project", "list"])
+        .write_stdin("typed\n")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("VIBE_CLOCK_KEY_FILE"));
+    assert!(!tmp.path().join("test.db").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn reads_passphrase_from_command() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+
+    vibe_clock(&tmp)
+        .env("VIBE_CLOCK_KEY_COMMAND", "echo file-secret")
+        .args(["project", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Acme"));
+}
+
+#[cfg(unix)]
+#[test]
+fn failing_command_is_an_error() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .env("VIBE_CLOCK_KEY_COMMAND", "echo locked >&2; exit 3")
+        .args(["project", "list"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("VIBE_CLOCK_KEY_COMMAND"))
+        .stderr(predicate::str::contains("locked"));
+}
+
+#[cfg(unix)]
+#[test]
+fn command_reads_from_the_terminal() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+
+    vibe_clock(&tmp)
+        .env("VIBE_CLOCK_KEY_COMMAND", "read -r key; echo \"$key\"")
+        .args(["project", "list"])
+        .write_stdin("file-secret\n")
+        .assert()
+        .success();
+}
+
+#[test]
+fn env_key_takes_precedence() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+
+    vibe_clock(&tmp)
+        .env("VIBE_CLOCK_KEY", "file-secret")
+        .env("VIBE_CLOCK_KEY_COMMAND", "exit 1")
+        .args(["project", "list"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn wrong_passphrase_is_a_user_error() {
+    let tmp = TempDir::new().unwrap();
+    seed(&tmp);
+
+    vibe_clock(&tmp)
+        .env("VIBE_CLOCK_KEY", "typo")
+        .args(["project", "list"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Wrong passphrase"));
+}
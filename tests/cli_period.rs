@@ -0,0 +1,148 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn vibe_clock(tmp: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_DB", tmp.path().join("test.db"));
+    cmd.env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"));
+    cmd.current_dir(tmp.path());
+    cmd.env("VIBE_CLOCK_KEY", "test-key");
+    cmd
+}
+
+fn setup_locked_september(tmp: &TempDir) {
+    vibe_clock(tmp)
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success();
+    vibe_clock(tmp)
+        .args([
+            "task",
+            "add",
+            "Acme",
+            "Sent work",
+            "--start",
+            "09:00",
+            "--end",
+            "10:00",
+            "--date",
+            "2026-09-15",
+        ])
+        .assert()
+        .success();
+    vibe_clock(tmp)
+        .args(["period", "lock", "--to", "2026-09-30"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Entries up to 2026-09-30 are now locked.",
+        ));
+}
+
+#[test]
+fn refuses_adding_task_in_locked_period() {
+    let tmp = TempDir::new().unwrap();
+    setup_locked_september(&tmp);
+
+    vibe_clock(&tmp)
+        .args([
+            "task",
+            "add",
+            "Acme",
+            "Late entry",
+            "--duration",
+            "30",
+            "--date",
+            "2026-09-30",
+        ])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("locked period"));
+
+    vibe_clock(&tmp)
+        .args([
+            "task",
+            "add",
+            "Acme",
+            "October entry",
+            "--duration",
+            "30",
+            "--date",
+            "2026-10-01",
+        ])
+        .assert()
+        .success();
+}
+
+#[test]
+fn refuses_editing_and_deleting_locked_task() {
+    let tmp = TempDir::new().unwrap();
+    setup_locked_september(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["task", "edit", "1", "--description", "Changed"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("locked period"));
+
+    vibe_clock(&tmp)
+        .args(["task", "delete", "1", "--yes"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("locked period"));
+}
+
+#[test]
+fn refuses_moving_task_into_locked_period() {
+    let tmp = TempDir::new().unwrap();
+    setup_locked_september(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["task", "add", "Acme", "Today", "--duration", "30"])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp)
+        .args(["task", "edit", "2", "--date", "2026-09-01"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("locked period"));
+}
+
+#[test]
+fn unlock_allows_changes_and_is_recorded() {
+    let tmp = TempDir::new().unwrap();
+    setup_locked_september(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["period", "unlock"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("was locked up to 2026-09-30"));
+
+    vibe_clock(&tmp)
+        .args(["task", "edit", "1", "--description", "Corrected"])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp)
+        .args(["period", "status"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No period is locked."))
+        .stdout(predicate::str::is_match(r"lock\s+2026-09-30").unwrap())
+        .stdout(predicate::str::is_match(r"unlock\s+-\s+2026-09-30").unwrap());
+}
+
+#[test]
+fn refuses_shrinking_lock_without_unlock() {
+    let tmp = TempDir::new().unwrap();
+    setup_locked_september(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["period", "lock", "--to", "2026-08-31"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("period unlock"));
+}
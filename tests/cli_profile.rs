@@ -0,0 +1,158 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn vibe_clock(tmp: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_DATA_DIR", tmp.path());
+    cmd.env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"));
+    cmd.current_dir(tmp.path());
+    cmd.env("VIBE_CLOCK_KEY", "test-passphrase");
+    cmd.env("VIBE_CLOCK_BACKUP_KEEP", "0");
+    cmd.env_remove("VIBE_CLOCK_DB");
+    cmd.env_remove("VIBE_CLOCK_PROFILE");
+    cmd
+}
+
+fn json_output(cmd: &mut Command) -> serde_json::Value {
+    let output = cmd.output().unwrap();
+    assert!(output.status.success(), "{output:?}");
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn default_profile_uses_data_dir_database() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["project", "add", "Personal"])
+        .assert()
+        .success();
+    assert!(tmp.path().join("vibe-clock.db").exists());
+}
+
+#[test]
+fn profiles_keep_separate_databases() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["project", "add", "Personal"])
+        .assert()
+        .success();
+    vibe_clock(&tmp)
+        .args(["profile", "create", "work"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Profile 'work' created"));
+    assert!(tmp.path().join("profiles/work/vibe-clock.db").exists());
+
+    vibe_clock(&tmp)
+        .args(["--profile", "work", "project", "add", "Employer"])
+        .assert()
+        .success();
+    vibe_clock(&tmp)
+        .args(["project", "list", "--profile", "work"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Employer"))
+        .stdout(predicate::str::contains("Personal").not());
+    vibe_clock(&tmp)
+        .args(["project", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Personal"))
+        .stdout(predicate::str::contains("Employer").not());
+}
+
+#[test]
+fn use_switches_active_profile() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["profile", "create", "work"])
+        .assert()
+        .success();
+    vibe_clock(&tmp)
+        .args(["profile", "use", "work"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Now using profile 'work'."));
+    vibe_clock(&tmp)
+        .args(["project", "add", "Employer"])
+        .assert()
+        .success();
+
+    let json = json_output(vibe_clock(&tmp).args(["--output-format", "json", "profile", "list"]));
+    let profiles = json["profiles"].as_array().unwrap();
+    assert_eq!(profiles.len(), 2);
+    assert_eq!(profiles[0]["name"], "default");
+    assert_eq!(profiles[0]["active"], false);
+    assert_eq!(profiles[1]["name"], "work");
+    assert_eq!(profiles[1]["active"], true);
+
+    // The environment overrides the remembered choice
+    vibe_clock(&tmp)
+        .env("VIBE_CLOCK_PROFILE", "default")
+        .args(["project", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Employer").not());
+}
+
+#[test]
+fn clock_status_shows_profile() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["clock", "status"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Profile: default"));
+
+    vibe_clock(&tmp)
+        .args(["profile", "create", "work"])
+        .assert()
+        .success();
+    vibe_clock(&tmp)
+        .args(["--profile", "work", "clock", "status"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Profile: work"));
+
+    let json = json_output(vibe_clock(&tmp).args([
+        "--output-format",
+        "json",
+        "--profile",
+        "work",
+        "clock",
+        "status",
+    ]));
+    assert_eq!(json["profile"], "work");
+}
+
+#[test]
+fn unknown_profile_is_refused() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["--profile", "wrok", "project", "list"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Profile 'wrok' does not exist"));
+    assert!(!tmp.path().join("profiles").exists());
+
+    vibe_clock(&tmp)
+        .args(["profile", "use", "wrok"])
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn invalid_or_duplicate_names_are_refused() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["profile", "create", "../escape"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Invalid profile name"));
+    vibe_clock(&tmp)
+        .args(["profile", "create", "default"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("already exists"));
+}
@@ -5,6 +5,8 @@ use tempfile::TempDir;
 fn vibe_clock(tmp: &TempDir) -> Command {
     let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
     cmd.env("VIBE_CLOCK_DB", tmp.path().join("test.db"));
+    cmd.env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"));
+    cmd.current_dir(tmp.path());
     cmd.env("VIBE_CLOCK_KEY", "test-key");
     cmd
 }
@@ -7,6 +7,8 @@ use tempfile::TempDir;
 fn vibe_clock(tmp: &TempDir) -> Command {
     let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
     cmd.env("VIBE_CLOCK_DB", tmp.path().join("test.db"));
+    cmd.env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"));
+    cmd.current_dir(tmp.path());
     cmd.env("VIBE_CLOCK_KEY", "test-key");
     cmd
 }
@@ -770,3 +772,314 @@ fn report_tasks_ordered_by_start_time_within_day() {
         "Morning task (09:00) must appear before Afternoon task (14:00)"
     );
 }
+
+#[test]
+fn report_flags_entries_edited_after_date() {
+    let tmp = TempDir::new().unwrap();
+    setup_with_dated_tasks(&tmp);
+
+    // Pretend the entries so far were logged before the cut-off
+    let db = vibe_clock::db::Database::open(&tmp.path().join("test.db"), "test-key").unwrap();
+    db.conn
+        .execute(
+            "UPDATE task_entry_history SET changed_at = '2026-02-27T18:00:00'",
+            [],
+        )
+        .unwrap();
+    drop(db);
+
+    vibe_clock(&tmp)
+        .args(["task", "edit", "1", "--description", "Day 1 work (revised)"])
+        .assert()
+        .success();
+    // Logged after the cut-off for a day before it
+    let output = vibe_clock(&tmp)
+        .args([
+            "--output-format",
+            "json",
+            "task",
+            "add",
+            "Beta",
+            "Forgotten call",
+            "--duration",
+            "30m",
+            "--date",
+            "2026-02-26",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let late: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let late_id = late["task"]["id"].as_i64().unwrap();
+
+    vibe_clock(&tmp)
+        .args([
+            "report",
+            "--from",
+            "2026-02-25",
+            "--to",
+            "2026-02-26",
+            "--edited-after",
+            "2026-02-28",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"(?m)^1\*\s+Acme").unwrap())
+        .stdout(predicate::str::is_match(r"(?m)^2\s+Acme").unwrap())
+        .stdout(predicate::str::is_match(format!(r"(?m)^{late_id}\*\s+Beta")).unwrap())
+        .stdout(predicate::str::contains(
+            "* 2 entries added or edited after 2026-02-28",
+        ));
+
+    vibe_clock(&tmp)
+        .args([
+            "report",
+            "--from",
+            "2026-02-25",
+            "--to",
+            "2026-02-26",
+            "--edited-after",
+            "today",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "* 0 entries added or edited after",
+        ));
+}
+
+#[test]
+fn report_csv_to_stdout_has_one_row_per_entry() {
+    let tmp = TempDir::new().unwrap();
+    setup_with_dated_tasks(&tmp);
+
+    vibe_clock(&tmp)
+        .args([
+            "report",
+            "--from",
+            "2026-02-25",
+            "--to",
+            "2026-02-26",
+            "--format",
+            "csv",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            "date,project,description,start,end,minutes,hours\n\
+             2026-02-25,Acme,Day 1 work,09:00,11:00,120,2.00\n\
+             2026-02-25,Beta,Beta task,14:00,15:00,60,1.00\n\
+             2026-02-26,Acme,Day 2 work,09:00,10:30,90,1.50\n",
+        );
+}
+
+#[test]
+fn report_csv_summary_with_custom_delimiter_and_no_header() {
+    let tmp = TempDir::new().unwrap();
+    setup_with_dated_tasks(&tmp);
+
+    vibe_clock(&tmp)
+        .args([
+            "report",
+            "--from",
+            "2026-02-25",
+            "--to",
+            "2026-02-26",
+            "--format",
+            "csv",
+            "--summary",
+            "--delimiter",
+            ";",
+            "--no-header",
+        ])
+        .assert()
+        .success()
+        .stdout("Acme;210;3.50\nBeta;60;1.00\n");
+}
+
+#[test]
+fn report_csv_written_to_file() {
+    let tmp = TempDir::new().unwrap();
+    setup_with_dated_tasks(&tmp);
+    let csv_path = tmp.path().join("report.csv");
+
+    vibe_clock(&tmp)
+        .args([
+            "report",
+            "--from",
+            "2026-02-25",
+            "--csv",
+            csv_path.to_str().unwrap(),
+            "--delimiter",
+            "tab",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Project Summary"))
+        .stdout(predicate::str::contains("CSV report saved to"));
+
+    let content = fs::read_to_string(&csv_path).unwrap();
+    assert!(content.starts_with("date\tproject\tdescription\t"));
+    assert!(content.contains("2026-02-25\tAcme\tDay 1 work\t09:00\t11:00\t120\t2.00"));
+}
+
+#[test]
+fn report_csv_rejects_invalid_delimiter() {
+    let tmp = TempDir::new().unwrap();
+    setup_with_dated_tasks(&tmp);
+
+    for delimiter in ["::", "\"", "\n", "\r", "\u{1}", "é"] {
+        vibe_clock(&tmp)
+            .args([
+                "report",
+                "--from",
+                "2026-02-25",
+                "--format",
+                "csv",
+                "--delimiter",
+                delimiter,
+            ])
+            .assert()
+            .code(1)
+            .stderr(predicate::str::contains("Invalid delimiter"));
+    }
+}
+
+#[test]
+fn report_markdown_to_stdout() {
+    let tmp = TempDir::new().unwrap();
+    setup_with_dated_tasks(&tmp);
+
+    vibe_clock(&tmp)
+        .args([
+            "report",
+            "--from",
+            "2026-02-25",
+            "--to",
+            "2026-02-26",
+            "--format",
+            "md",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("# Time Report\n"))
+        .stdout(predicate::str::contains("| Acme | 3h 30m |"))
+        .stdout(predicate::str::contains("### 2026-02-26"))
+        .stdout(predicate::str::contains(
+            "| 2 | Day 2 work | Acme | 09:00 | 10:30 | 1h 30m |",
+        ))
+        .stdout(predicate::str::contains("**Grand Total: 4h 30m**"));
+}
+
+#[test]
+fn report_markdown_accepts_long_extension() {
+    let tmp = TempDir::new().unwrap();
+    setup_with_dated_tasks(&tmp);
+    let md_path = tmp.path().join("week.markdown");
+
+    vibe_clock(&tmp)
+        .args([
+            "report",
+            "--from",
+            "2026-02-25",
+            "--format",
+            "md",
+            "--output",
+        ])
+        .arg(&md_path)
+        .assert()
+        .success();
+    assert!(
+        md_path.is_file(),
+        "expected a file at {}",
+        md_path.display()
+    );
+}
+
+#[test]
+fn report_documents_say_when_range_is_empty() {
+    let tmp = TempDir::new().unwrap();
+    for format in ["md", "html"] {
+        vibe_clock(&tmp)
+            .args(["report", "--from", "2026-01-01", "--to", "2026-01-31"])
+            .args(["--format", format])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "No tasks found between 2026-01-01 and 2026-01-31.",
+            ))
+            .stdout(predicate::str::contains("Project Summary").not());
+    }
+}
+
+#[test]
+fn report_html_written_to_output_file() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["project", "add", "R&D"])
+        .assert()
+        .success();
+    vibe_clock(&tmp)
+        .args([
+            "task",
+            "add",
+            "R&D",
+            "Fix <script> tag",
+            "--duration",
+            "30m",
+            "--date",
+            "2026-02-25",
+        ])
+        .assert()
+        .success();
+    let html_path = tmp.path().join("week.html");
+
+    vibe_clock(&tmp)
+        .args([
+            "report",
+            "--from",
+            "2026-02-25",
+            "--format",
+            "html",
+            "--output",
+            html_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("HTML report saved to"));
+
+    let html = fs::read_to_string(&html_path).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<style>"));
+    assert!(html.contains("<td>R&amp;D</td>"));
+    assert!(html.contains("Fix &lt;script&gt; tag"));
+    assert!(!html.contains("<link"));
+}
+
+#[test]
+fn report_format_pdf_writes_auto_named_file() {
+    let tmp = TempDir::new().unwrap();
+    setup_with_dated_tasks(&tmp);
+
+    vibe_clock(&tmp)
+        .current_dir(tmp.path())
+        .args(["report", "--from", "2026-02-25", "--format", "pdf"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PDF report saved to"));
+
+    let bytes = fs::read(tmp.path().join("report-2026-02-25.pdf")).unwrap();
+    assert!(bytes.starts_with(b"%PDF"));
+}
+
+#[test]
+fn report_pdf_flag_conflicts_with_format() {
+    let tmp = TempDir::new().unwrap();
+    setup_with_dated_tasks(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["report", "--from", "2026-02-25", "--format", "md", "--pdf"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("--format pdf"));
+}
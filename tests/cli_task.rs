@@ -5,6 +5,8 @@ use tempfile::TempDir;
 fn vibe_clock(tmp: &TempDir) -> Command {
     let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
     cmd.env("VIBE_CLOCK_DB", tmp.path().join("test.db"));
+    cmd.env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"));
+    cmd.current_dir(tmp.path());
     cmd.env("VIBE_CLOCK_KEY", "test-key");
     cmd
 }
@@ -466,3 +468,171 @@ fn rejects_edit_nonexistent_task() {
         .code(1)
         .stderr(predicate::str::contains("not found"));
 }
+
+#[test]
+fn shows_field_level_history_for_task() {
+    let tmp = TempDir::new().unwrap();
+    setup_project(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["task", "add", "Acme", "Draft", "--duration", "1h"])
+        .assert()
+        .success();
+    vibe_clock(&tmp)
+        .args([
+            "task",
+            "edit",
+            "1",
+            "--description",
+            "Final",
+            "--duration",
+            "90",
+        ])
+        .assert()
+        .success();
+    vibe_clock(&tmp)
+        .args(["task", "delete", "1", "--yes"])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp)
+        .args(["task", "history", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("History for task 1:"))
+        .stdout(predicate::str::is_match(r"create\s+project_id\s+-\s+Acme").unwrap())
+        .stdout(predicate::str::is_match(r"update\s+description\s+Draft\s+Final").unwrap())
+        .stdout(predicate::str::is_match(r"update\s+duration_min\s+1h\s+1h 30m").unwrap())
+        .stdout(predicate::str::is_match(r"delete\s+-").unwrap());
+}
+
+#[test]
+fn rejects_history_for_unknown_task() {
+    let tmp = TempDir::new().unwrap();
+
+    vibe_clock(&tmp)
+        .args(["task", "history", "42"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Task with ID 42 not found."));
+}
+
+#[test]
+fn add_takes_project_from_directory_file() {
+    let tmp = TempDir::new().unwrap();
+    setup_project(&tmp);
+    let repo = tmp.path().join("repo");
+    std::fs::create_dir_all(repo.join("src/deep")).unwrap();
+    std::fs::write(repo.join(".vibe-clock"), "project = \"Acme\"\n").unwrap();
+
+    vibe_clock(&tmp)
+        .current_dir(repo.join("src/deep"))
+        .args(["task", "add", "Fixing the parser", "--duration", "30"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "for 'Fixing the parser' on project 'Acme'",
+        ));
+}
+
+#[test]
+fn add_takes_description_from_directory_file() {
+    let tmp = TempDir::new().unwrap();
+    setup_project(&tmp);
+    std::fs::write(
+        tmp.path().join(".vibe-clock"),
+        "project = \"Acme\"\ndescription = \"Acme website\"\n",
+    )
+    .unwrap();
+
+    vibe_clock(&tmp)
+        .current_dir(tmp.path())
+        .args(["task", "add", "--duration", "45"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "for 'Acme website' on project 'Acme'",
+        ));
+}
+
+#[test]
+fn add_reports_unknown_project_in_directory_file() {
+    let tmp = TempDir::new().unwrap();
+    setup_project(&tmp);
+    std::fs::write(tmp.path().join(".vibe-clock"), "project = \"Nope\"\n").unwrap();
+
+    vibe_clock(&tmp)
+        .current_dir(tmp.path())
+        .args(["task", "add", "Work", "--duration", "45"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Project 'Nope' not found"))
+        .stderr(predicate::str::contains(".vibe-clock"));
+}
+
+#[test]
+fn add_with_project_and_description_ignores_directory_file() {
+    let tmp = TempDir::new().unwrap();
+    setup_project(&tmp);
+    std::fs::write(tmp.path().join(".vibe-clock"), "not toml = = =\n").unwrap();
+
+    vibe_clock(&tmp)
+        .args(["task", "add", "Acme", "Work", "--duration", "45"])
+        .assert()
+        .success();
+    vibe_clock(&tmp)
+        .args(["task", "add", "Work", "--duration", "45"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(".vibe-clock"));
+}
+
+#[test]
+fn edit_duration_moves_end_of_timed_entry() {
+    let tmp = TempDir::new().unwrap();
+    setup_project(&tmp);
+    vibe_clock(&tmp)
+        .args([
+            "task",
+            "add",
+            "Acme",
+            "Meeting",
+            "--start",
+            "09:00",
+            "--end",
+            "10:00",
+            "--date",
+            "2026-03-01",
+        ])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp)
+        .args(["task", "edit", "1", "--duration", "2h"])
+        .assert()
+        .success();
+    vibe_clock(&tmp)
+        .args(["journal", "2026-03-01"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("11:00"))
+        .stdout(predicate::str::contains("2h"));
+    vibe_clock(&tmp)
+        .args(["db", "check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No problems found."));
+
+    for duration in ["16h", "999999999999"] {
+        vibe_clock(&tmp)
+            .args(["task", "edit", "1", "--duration", duration])
+            .assert()
+            .code(1)
+            .stderr(predicate::str::contains("past midnight"));
+    }
+    vibe_clock(&tmp)
+        .args(["task", "edit", "1", "--end", "12:00", "--duration", "1h"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("either --end or --duration"));
+}
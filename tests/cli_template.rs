@@ -0,0 +1,138 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn vibe_clock(tmp: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_DB", tmp.path().join("test.db"));
+    cmd.env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"));
+    cmd.current_dir(tmp.path());
+    cmd.env("VIBE_CLOCK_KEY", "test-key");
+    cmd
+}
+
+fn setup_template(tmp: &TempDir) {
+    vibe_clock(tmp)
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success();
+    vibe_clock(tmp)
+        .args([
+            "template",
+            "add",
+            "review",
+            "Acme",
+            "Code review",
+            "--duration",
+            "45m",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Template 'review' created."));
+}
+
+#[test]
+fn lists_templates() {
+    let tmp = TempDir::new().unwrap();
+    setup_template(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["template", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("review"))
+        .stdout(predicate::str::contains("Code review"))
+        .stdout(predicate::str::contains("45m"));
+}
+
+#[test]
+fn rejects_duplicate_template_name() {
+    let tmp = TempDir::new().unwrap();
+    setup_template(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["template", "add", "review", "Acme", "Other"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("already exists"));
+}
+
+#[test]
+fn task_add_uses_template_fields() {
+    let tmp = TempDir::new().unwrap();
+    setup_template(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["task", "add", "--template", "review"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Task logged: 45m for 'Code review' on project 'Acme'.",
+        ));
+}
+
+#[test]
+fn task_add_flags_override_template() {
+    let tmp = TempDir::new().unwrap();
+    setup_template(&tmp);
+
+    vibe_clock(&tmp)
+        .args([
+            "task",
+            "add",
+            "--template",
+            "review",
+            "Review PR 12",
+            "--duration",
+            "1h",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Task logged: 1h for 'Review PR 12' on project 'Acme'.",
+        ));
+}
+
+#[test]
+fn clock_start_uses_template() {
+    let tmp = TempDir::new().unwrap();
+    setup_template(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["clock", "start", "--template", "review"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Clock started for 'Code review' on project 'Acme'",
+        ));
+}
+
+#[test]
+fn task_add_without_project_or_template_fails() {
+    let tmp = TempDir::new().unwrap();
+    setup_template(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["task", "add", "Orphan", "--duration", "30"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Missing project"));
+}
+
+#[test]
+fn deletes_template() {
+    let tmp = TempDir::new().unwrap();
+    setup_template(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["template", "delete", "review"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Template 'review' deleted."));
+
+    vibe_clock(&tmp)
+        .args(["task", "add", "--template", "review"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Template 'review' not found."));
+}
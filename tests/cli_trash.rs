@@ -0,0 +1,234 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn vibe_clock(tmp: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_DB", tmp.path().join("test.db"));
+    cmd.env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"));
+    cmd.current_dir(tmp.path());
+    cmd.env("VIBE_CLOCK_KEY", "test-key");
+    cmd
+}
+
+fn setup_project_with_task(tmp: &TempDir) {
+    vibe_clock(tmp)
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success();
+    vibe_clock(tmp)
+        .args([
+            "task",
+            "add",
+            "Acme",
+            "Billable work",
+            "--start",
+            "09:00",
+            "--end",
+            "10:00",
+            "--date",
+            "2026-03-02",
+        ])
+        .assert()
+        .success();
+}
+
+#[test]
+fn deleted_task_moves_to_trash_and_out_of_reports() {
+    let tmp = TempDir::new().unwrap();
+    setup_project_with_task(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["task", "delete", "1", "--yes"])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp)
+        .args(["trash", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Billable work"));
+
+    vibe_clock(&tmp)
+        .args(["report", "--from", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No tasks found"));
+}
+
+#[test]
+fn restores_trashed_task() {
+    let tmp = TempDir::new().unwrap();
+    setup_project_with_task(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["task", "delete", "1", "--yes"])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp)
+        .args(["trash", "restore", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Task 1 restored."));
+
+    vibe_clock(&tmp)
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Billable work"));
+}
+
+#[test]
+fn restores_trashed_project_with_its_tasks() {
+    let tmp = TempDir::new().unwrap();
+    setup_project_with_task(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["project", "delete", "1", "--yes"])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp)
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No tasks logged"));
+
+    vibe_clock(&tmp)
+        .args(["trash", "restore", "1", "--project"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Project 'Acme' restored."));
+
+    vibe_clock(&tmp)
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Billable work"));
+}
+
+#[test]
+fn project_trash_and_purge_appear_in_task_history() {
+    let tmp = TempDir::new().unwrap();
+    setup_project_with_task(&tmp);
+
+    for args in [
+        ["project", "delete", "1", "--yes"].as_slice(),
+        &["trash", "restore", "1", "--project"],
+        &["project", "delete", "1", "--yes"],
+        &["trash", "empty", "--yes"],
+    ] {
+        vibe_clock(&tmp).args(args).assert().success();
+    }
+
+    let output = vibe_clock(&tmp)
+        .args(["--output-format", "json", "task", "history", "1"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let actions: Vec<&str> = json["history"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|h| h["action"].as_str().unwrap())
+        .filter(|action| *action != "create")
+        .collect();
+    assert_eq!(actions, ["delete", "restore", "delete", "purge"]);
+}
+
+#[test]
+fn trashed_project_name_is_reserved() {
+    let tmp = TempDir::new().unwrap();
+    setup_project_with_task(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["project", "delete", "1", "--yes"])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp)
+        .args(["project", "add", "Acme"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("is in the trash"));
+}
+
+#[test]
+fn empty_keeps_recent_items_when_older_than_given() {
+    let tmp = TempDir::new().unwrap();
+    setup_project_with_task(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["task", "delete", "1", "--yes"])
+        .assert()
+        .success();
+
+    for age in ["30d", "200000000000000d"] {
+        vibe_clock(&tmp)
+            .args(["trash", "empty", "--older-than", age, "--yes"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "Permanently deleted 0 projects and 0 tasks.",
+            ));
+    }
+    vibe_clock(&tmp)
+        .args([
+            "trash",
+            "empty",
+            "--older-than",
+            "9223372036854775807w",
+            "--yes",
+        ])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Invalid age"));
+
+    vibe_clock(&tmp)
+        .args(["trash", "empty", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Permanently deleted 0 projects and 1 tasks.",
+        ));
+
+    vibe_clock(&tmp)
+        .args(["trash", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Trash is empty."));
+}
+
+#[test]
+fn empty_asks_for_confirmation() {
+    let tmp = TempDir::new().unwrap();
+    setup_project_with_task(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["task", "delete", "1", "--yes"])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp)
+        .args(["trash", "empty"])
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cancelled."))
+        .stderr(predicate::str::contains("cannot be undone"));
+
+    vibe_clock(&tmp)
+        .args(["--output-format", "json", "trash", "empty"])
+        .write_stdin("")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"cancelled\": true"));
+
+    vibe_clock(&tmp)
+        .args(["trash", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Billable work"));
+}
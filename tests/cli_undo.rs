@@ -0,0 +1,189 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn vibe_clock(tmp: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("vibe-clock").unwrap();
+    cmd.env("VIBE_CLOCK_DB", tmp.path().join("test.db"));
+    cmd.env("VIBE_CLOCK_CONFIG", tmp.path().join("config.toml"));
+    cmd.current_dir(tmp.path());
+    cmd.env("VIBE_CLOCK_KEY", "test-key");
+    cmd
+}
+
+fn setup_project_with_task(tmp: &TempDir) {
+    vibe_clock(tmp)
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success();
+    vibe_clock(tmp)
+        .args([
+            "task",
+            "add",
+            "Acme",
+            "Code review",
+            "--start",
+            "09:00",
+            "--end",
+            "10:00",
+            "--date",
+            "2026-03-02",
+        ])
+        .assert()
+        .success();
+}
+
+#[test]
+fn reports_nothing_to_undo() {
+    let tmp = TempDir::new().unwrap();
+
+    vibe_clock(&tmp)
+        .args(["undo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nothing to undo."));
+}
+
+#[test]
+fn undoes_task_delete() {
+    let tmp = TempDir::new().unwrap();
+    setup_project_with_task(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["task", "delete", "1", "--yes"])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp)
+        .args(["undo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Undone: delete task 1 'Code review'.",
+        ));
+
+    vibe_clock(&tmp)
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Code review"));
+}
+
+#[test]
+fn undoes_project_delete_including_cascaded_tasks() {
+    let tmp = TempDir::new().unwrap();
+    setup_project_with_task(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["project", "delete", "1", "--yes"])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp)
+        .args(["undo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("delete project 'Acme' (1 tasks)"));
+
+    vibe_clock(&tmp)
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Code review"))
+        .stdout(predicate::str::contains("Acme"));
+}
+
+#[test]
+fn undoes_task_edit_and_redoes_it() {
+    let tmp = TempDir::new().unwrap();
+    setup_project_with_task(&tmp);
+
+    vibe_clock(&tmp)
+        .args(["task", "edit", "1", "--description", "Pairing"])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp).args(["undo"]).assert().success();
+    vibe_clock(&tmp)
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Code review"));
+
+    vibe_clock(&tmp)
+        .args(["redo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Redone: edit task 1 'Pairing'."));
+    vibe_clock(&tmp)
+        .args(["journal", "2026-03-02"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pairing"));
+}
+
+#[test]
+fn new_operation_clears_redo() {
+    let tmp = TempDir::new().unwrap();
+    setup_project_with_task(&tmp);
+
+    vibe_clock(&tmp).args(["undo"]).assert().success();
+    vibe_clock(&tmp)
+        .args(["project", "add", "Beta"])
+        .assert()
+        .success();
+
+    vibe_clock(&tmp)
+        .args(["redo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nothing to redo."));
+}
+
+#[test]
+fn undoes_clock_stop_and_resumes_clock() {
+    let tmp = TempDir::new().unwrap();
+    vibe_clock(&tmp)
+        .args(["project", "add", "Acme"])
+        .assert()
+        .success();
+    vibe_clock(&tmp)
+        .args(["clock", "start", "Acme", "Deep work"])
+        .assert()
+        .success();
+    vibe_clock(&tmp).args(["clock", "stop"]).assert().success();
+
+    vibe_clock(&tmp)
+        .args(["undo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("stop clock for 'Deep work'"));
+
+    vibe_clock(&tmp)
+        .args(["clock", "status"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Clock running"));
+}
+
+#[test]
+fn undoing_rename_reports_name_held_by_trashed_project() {
+    let tmp = TempDir::new().unwrap();
+    for args in [
+        ["project", "add", "Acme"].as_slice(),
+        &["project", "edit", "1", "--name", "Beta"],
+        &["project", "add", "Acme"],
+        // Undoing the second add moves that project to the trash
+        &["undo"],
+    ] {
+        vibe_clock(&tmp).args(args).assert().success();
+    }
+
+    vibe_clock(&tmp)
+        .args(["undo"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Project name 'Acme' is now used by a project in the trash",
+        ));
+}
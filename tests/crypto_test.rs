@@ -1,5 +1,5 @@
 use tempfile::TempDir;
-use vibe_clock::db::Database;
+use vibe_clock::db::{Database, WrongPassphrase};
 
 #[test]
 fn opens_db_with_env_key() {
@@ -48,5 +48,6 @@ fn rejects_wrong_key() {
 
     // Try opening with key B — should fail
     let result = Database::open(&db_path, "wrong-key");
-    assert!(result.is_err());
+    let err = result.err().unwrap();
+    assert!(err.is::<WrongPassphrase>(), "{err}");
 }
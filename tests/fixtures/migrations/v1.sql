@@ -0,0 +1,44 @@
+-- A database as created by vibe-clock at schema version 1, with sample data.
+CREATE TABLE IF NOT EXISTS schema_version (
+    version INTEGER PRIMARY KEY
+);
+
+INSERT OR IGNORE INTO schema_version (version) VALUES (1);
+
+CREATE TABLE IF NOT EXISTS project (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS task_entry (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    start_time TEXT,
+    end_time TEXT,
+    duration_min INTEGER NOT NULL CHECK(duration_min > 0),
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES project(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS clock_state (
+    id INTEGER PRIMARY KEY CHECK(id = 1),
+    project_id INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    start_time TEXT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES project(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_entry_project_id ON task_entry(project_id);
+CREATE INDEX IF NOT EXISTS idx_task_entry_start_time ON task_entry(start_time);
+
+INSERT INTO project (id, name, created_at, updated_at) VALUES (1, 'Acme', '2025-01-06T09:00:00', '2025-01-06T09:00:00');
+INSERT INTO project (id, name, created_at, updated_at) VALUES (2, 'Internal', '2025-01-06T09:00:00', '2025-01-06T09:00:00');
+INSERT INTO task_entry (id, project_id, description, start_time, end_time, duration_min, created_at, updated_at)
+    VALUES (1, 1, 'Planning', '2025-01-06T09:00:00', '2025-01-06T10:30:00', 90, '2025-01-06T10:30:00', '2025-01-06T10:30:00');
+INSERT INTO task_entry (id, project_id, description, start_time, end_time, duration_min, created_at, updated_at)
+    VALUES (2, 2, 'Email', NULL, NULL, 20, '2025-01-06T11:00:00', '2025-01-06T11:00:00');
+INSERT INTO clock_state (id, project_id, description, start_time) VALUES (1, 1, 'Review', '2025-01-06T13:00:00');
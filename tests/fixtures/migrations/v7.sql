@@ -0,0 +1,123 @@
+-- A database as created by vibe-clock at schema version 7, with sample data.
+CREATE TABLE IF NOT EXISTS schema_version (
+    version INTEGER PRIMARY KEY
+);
+
+INSERT OR IGNORE INTO schema_version (version) VALUES (1);
+
+CREATE TABLE IF NOT EXISTS project (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS task_entry (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    start_time TEXT,
+    end_time TEXT,
+    duration_min INTEGER NOT NULL CHECK(duration_min > 0),
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES project(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS clock_state (
+    id INTEGER PRIMARY KEY CHECK(id = 1),
+    project_id INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    start_time TEXT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES project(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_entry_project_id ON task_entry(project_id);
+CREATE INDEX IF NOT EXISTS idx_task_entry_start_time ON task_entry(start_time);
+
+INSERT OR IGNORE INTO schema_version (version) VALUES (2);
+
+CREATE TABLE IF NOT EXISTS task_template (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    project_id INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    duration_min INTEGER CHECK(duration_min IS NULL OR duration_min > 0),
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES project(id) ON DELETE CASCADE
+);
+
+INSERT OR IGNORE INTO schema_version (version) VALUES (3);
+
+CREATE TABLE IF NOT EXISTS operation_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    undone INTEGER NOT NULL DEFAULT 0
+);
+
+INSERT OR IGNORE INTO schema_version (version) VALUES (4);
+
+ALTER TABLE project ADD COLUMN deleted_at TEXT;
+ALTER TABLE task_entry ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_task_entry_deleted_at ON task_entry(deleted_at);
+
+INSERT OR IGNORE INTO schema_version (version) VALUES (5);
+
+CREATE TABLE IF NOT EXISTS task_entry_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_entry_id INTEGER NOT NULL,
+    changed_at TEXT NOT NULL,
+    action TEXT NOT NULL,
+    field TEXT,
+    old_value TEXT,
+    new_value TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_entry_history_task ON task_entry_history(task_entry_id);
+
+INSERT OR IGNORE INTO schema_version (version) VALUES (6);
+
+CREATE TABLE IF NOT EXISTS period_lock (
+    id INTEGER PRIMARY KEY CHECK(id = 1),
+    locked_to TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS period_lock_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    action TEXT NOT NULL,
+    locked_to TEXT,
+    previous_locked_to TEXT,
+    changed_at TEXT NOT NULL
+);
+
+INSERT OR IGNORE INTO schema_version (version) VALUES (7);
+
+CREATE TABLE IF NOT EXISTS import_key (
+    key TEXT PRIMARY KEY,
+    task_entry_id INTEGER NOT NULL REFERENCES task_entry(id) ON DELETE CASCADE,
+    imported_at TEXT NOT NULL
+);
+
+INSERT INTO project (id, name, created_at, updated_at) VALUES (1, 'Acme', '2025-01-06T09:00:00', '2025-01-06T09:00:00');
+INSERT INTO project (id, name, created_at, updated_at) VALUES (2, 'Internal', '2025-01-06T09:00:00', '2025-01-06T09:00:00');
+INSERT INTO task_entry (id, project_id, description, start_time, end_time, duration_min, created_at, updated_at)
+    VALUES (1, 1, 'Planning', '2025-01-06T09:00:00', '2025-01-06T10:30:00', 90, '2025-01-06T10:30:00', '2025-01-06T10:30:00');
+INSERT INTO task_entry (id, project_id, description, start_time, end_time, duration_min, created_at, updated_at)
+    VALUES (2, 2, 'Email', NULL, NULL, 20, '2025-01-06T11:00:00', '2025-01-06T11:00:00');
+INSERT INTO clock_state (id, project_id, description, start_time) VALUES (1, 1, 'Review', '2025-01-06T13:00:00');
+INSERT INTO task_template (id, name, project_id, description, duration_min, created_at, updated_at)
+    VALUES (1, 'standup', 2, 'Daily standup', 15, '2025-01-06T09:00:00', '2025-01-06T09:00:00');
+INSERT INTO operation_log (id, kind, payload, created_at, undone)
+    VALUES (1, 'create_project', '{"kind":"create_project","project":{"id":2,"name":"Internal","created_at":"2025-01-06T09:00:00","updated_at":"2025-01-06T09:00:00"}}', '2025-01-06T09:00:00', 0);
+UPDATE task_entry SET deleted_at = '2025-01-07T08:00:00' WHERE id = 2;
+INSERT INTO task_entry_history (id, task_entry_id, changed_at, action, field, old_value, new_value)
+    VALUES (1, 1, '2025-01-06T10:30:00', 'created', NULL, NULL, NULL);
+INSERT INTO period_lock (id, locked_to, updated_at) VALUES (1, '2024-12-31', '2025-01-06T09:00:00');
+INSERT INTO period_lock_history (id, action, locked_to, previous_locked_to, changed_at)
+    VALUES (1, 'lock', '2024-12-31', NULL, '2025-01-06T09:00:00');
+INSERT INTO import_key (key, task_entry_id, imported_at) VALUES ('csv:planning', 1, '2025-01-06T10:30:00');
@@ -0,0 +1,159 @@
+use std::path::Path;
+
+use chrono::NaiveDateTime;
+use rusqlite::Connection;
+use tempfile::TempDir;
+use vibe_clock::db::{Database, SCHEMA_VERSION};
+
+/// Databases as each historical schema version created them, with sample data.
+const FIXTURES: [(i64, &str); 7] = [
+    (1, include_str!("fixtures/migrations/v1.sql")),
+    (2, include_str!("fixtures/migrations/v2.sql")),
+    (3, include_str!("fixtures/migrations/v3.sql")),
+    (4, include_str!("fixtures/migrations/v4.sql")),
+    (5, include_str!("fixtures/migrations/v5.sql")),
+    (6, include_str!("fixtures/migrations/v6.sql")),
+    (7, include_str!("fixtures/migrations/v7.sql")),
+];
+
+fn now() -> NaiveDateTime {
+    NaiveDateTime::parse_from_str("2026-03-02T12:00:00", "%Y-%m-%dT%H:%M:%S").unwrap()
+}
+
+fn create_fixture(path: &Path, sql: &str) {
+    let conn = Connection::open(path).unwrap();
+    conn.execute_batch(sql).unwrap();
+}
+
+fn recorded_versions(db: &Database) -> Vec<i64> {
+    let mut stmt = db
+        .conn
+        .prepare("SELECT version FROM schema_version ORDER BY version")
+        .unwrap();
+    stmt.query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<rusqlite::Result<_>>()
+        .unwrap()
+}
+
+#[test]
+fn new_database_records_every_version() {
+    let tmp = TempDir::new().unwrap();
+    let db = Database::open_unencrypted(&tmp.path().join("new.db")).unwrap();
+
+    assert_eq!(db.schema_version().unwrap(), SCHEMA_VERSION);
+    assert_eq!(
+        recorded_versions(&db),
+        (1..=SCHEMA_VERSION).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn upgrades_each_historical_version() {
+    for (version, sql) in FIXTURES {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join(format!("v{version}.db"));
+        create_fixture(&path, sql);
+
+        let db = Database::open_unencrypted(&path)
+            .unwrap_or_else(|e| panic!("v{version} failed to upgrade: {e:#}"));
+        assert_eq!(db.schema_version().unwrap(), SCHEMA_VERSION, "v{version}");
+        assert_eq!(
+            recorded_versions(&db),
+            (1..=SCHEMA_VERSION).collect::<Vec<_>>(),
+            "v{version}"
+        );
+
+        // Existing data survives
+        let projects = db.list_projects().unwrap();
+        assert_eq!(projects.len(), 2, "v{version}");
+        let planning = db.find_task_entry_by_id(1).unwrap().unwrap();
+        assert_eq!(planning.description, "Planning", "v{version}");
+        assert_eq!(planning.duration_min, 90, "v{version}");
+        let trashed = db.list_deleted_task_entries().unwrap();
+        assert_eq!(trashed.len(), usize::from(version >= 4), "v{version}");
+
+        // Tables and columns added later work
+        let task = db
+            .insert_task_entry(1, "After upgrade", None, None, 30, now())
+            .unwrap();
+        assert!(db.delete_task_entry(task.id, now()).unwrap(), "v{version}");
+        let imported = db
+            .insert_task_entry(1, "Imported", None, None, 15, now())
+            .unwrap();
+        db.insert_import_key("test:1", imported.id, now()).unwrap();
+        assert_eq!(
+            db.find_imported_task_id("test:1").unwrap(),
+            Some(imported.id),
+            "v{version}"
+        );
+        drop(db);
+
+        // Opening again is a no-op
+        let db = Database::open_unencrypted(&path).unwrap();
+        assert_eq!(db.schema_version().unwrap(), SCHEMA_VERSION, "v{version}");
+    }
+}
+
+#[test]
+fn refuses_database_from_newer_binary() {
+    let tmp = TempDir::new().unwrap();
+    let path = tmp.path().join("future.db");
+    drop(Database::open_unencrypted(&path).unwrap());
+    let newer = SCHEMA_VERSION + 1;
+    Connection::open(&path)
+        .unwrap()
+        .execute("INSERT INTO schema_version (version) VALUES (?1)", [newer])
+        .unwrap();
+
+    let err = Database::open_unencrypted(&path)
+        .err()
+        .expect("newer database must be refused");
+    assert!(
+        err.to_string()
+            .contains(&format!("Database schema version {newer} is newer")),
+        "{err}"
+    );
+
+    // The database is left untouched
+    let conn = Connection::open(&path).unwrap();
+    let max: i64 = conn
+        .query_row("SELECT MAX(version) FROM schema_version", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(max, newer);
+}
+
+#[test]
+fn failed_migration_leaves_database_unchanged() {
+    let tmp = TempDir::new().unwrap();
+    let path = tmp.path().join("v3.db");
+    create_fixture(&path, FIXTURES[2].1);
+    // A stray column makes the version 4 migration fail halfway through
+    Connection::open(&path)
+        .unwrap()
+        .execute_batch("ALTER TABLE task_entry ADD COLUMN deleted_at TEXT;")
+        .unwrap();
+
+    let err = Database::open_unencrypted(&path)
+        .err()
+        .expect("migration must fail");
+    assert!(format!("{err:#}").contains("schema version 4"), "{err:#}");
+
+    let conn = Connection::open(&path).unwrap();
+    let max: i64 = conn
+        .query_row("SELECT MAX(version) FROM schema_version", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(max, 3);
+    let has_project_deleted_at: bool = conn
+        .query_row(
+            "SELECT EXISTS (SELECT 1 FROM pragma_table_info('project') WHERE name = 'deleted_at')",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert!(!has_project_deleted_at);
+}